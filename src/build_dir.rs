@@ -9,11 +9,11 @@ use std::fs::write;
 use anyhow::{ensure, Context};
 use camino::{Utf8Path, Utf8PathBuf};
 use tempfile::TempDir;
-use tracing::info;
+use tracing::{debug, info};
 
 use crate::{
     console::Console,
-    copy_tree::copy_tree,
+    copy_tree::{copy_target_dir, copy_tree},
     manifest::{fix_cargo_config, fix_manifest},
     options::Options,
     workspace::Workspace,
@@ -32,6 +32,8 @@ pub struct BuildDir {
     /// object is dropped. If None, there's nothing to clean up.
     #[allow(dead_code)]
     temp_dir: Option<TempDir>,
+    /// True if this is the original source directory, rather than a copy.
+    in_place: bool,
 }
 
 impl BuildDir {
@@ -72,7 +74,32 @@ impl BuildDir {
         } else {
             Some(temp_dir)
         };
-        let build_dir = BuildDir { path, temp_dir };
+        let build_dir = BuildDir {
+            path,
+            temp_dir,
+            in_place: false,
+        };
+        Ok(build_dir)
+    }
+
+    /// Make a new build dir copying from a source directory, and then copy in the
+    /// `target` directory from an already-built baseline build dir.
+    ///
+    /// This lets the new build dir start with an incremental build of only the
+    /// mutated package, rather than building everything from scratch.
+    pub fn copy_from_baseline(
+        baseline: &BuildDir,
+        source: &Utf8Path,
+        options: &Options,
+        console: &Console,
+    ) -> Result<BuildDir> {
+        let build_dir = BuildDir::copy_from(source, options, console)?;
+        let baseline_target = baseline.path.join("target");
+        if baseline_target.is_dir() {
+            copy_target_dir(&baseline_target, &build_dir.path.join("target"), console)?;
+        } else {
+            debug!(?baseline_target, "Baseline has no target directory to copy");
+        }
         Ok(build_dir)
     }
 
@@ -83,6 +110,7 @@ impl BuildDir {
             path: source_path
                 .canonicalize_utf8()
                 .context("canonicalize source path")?,
+            in_place: true,
         })
     }
 
@@ -90,6 +118,17 @@ impl BuildDir {
         self.path.as_path()
     }
 
+    /// The target directory that cargo should use when building in this directory,
+    /// or None to use whatever the environment and cargo config say.
+    ///
+    /// Copied build directories always use their own `target` directory, even if
+    /// `CARGO_TARGET_DIR` or `build.target-dir` point somewhere else, so that
+    /// parallel builds don't share and corrupt one target directory, and so that
+    /// the baseline's `target` can be copied into them.
+    pub fn cargo_target_dir(&self) -> Option<Utf8PathBuf> {
+        (!self.in_place).then(|| self.path.join("target"))
+    }
+
     pub fn overwrite_file(&self, relative_path: &Utf8Path, code: &str) -> Result<()> {
         let full_path = self.path.join(relative_path);
         // for safety, don't follow symlinks
@@ -138,6 +177,7 @@ mod test {
             workspace.root().canonicalize_utf8()?
         );
         assert!(build_dir.temp_dir.is_none());
+        assert_eq!(build_dir.cargo_target_dir(), None);
         Ok(())
     }
 
@@ -154,6 +194,10 @@ mod test {
         assert!(build_dir.path().join("Cargo.toml").is_file());
         assert!(build_dir.path().join("src").is_dir());
         assert!(build_dir.temp_dir.is_some());
+        assert_eq!(
+            build_dir.cargo_target_dir(),
+            Some(build_dir.path().join("target"))
+        );
         assert_ne!(
             build_dir.path().canonicalize_utf8()?,
             workspace.root().canonicalize_utf8()?
@@ -15,11 +15,11 @@ use tracing::{debug, debug_span, warn};
 use crate::build_dir::BuildDir;
 use crate::console::Console;
 use crate::interrupt::check_interrupted;
-use crate::options::{Options, TestTool};
+use crate::options::{Options, TestTool, Verbosity};
 use crate::outcome::{Phase, PhaseResult};
 use crate::output::ScenarioOutput;
 use crate::package::PackageSelection;
-use crate::process::{Exit, Process};
+use crate::process::{quote_argv, Exit, Process};
 use crate::Result;
 
 // Allowed nextest codes (those will be considered a mutation caught / ignored without a warning)
@@ -51,10 +51,19 @@ pub fn run_cargo(
         ("INSTA_UPDATE".to_owned(), "no".to_owned()),
         ("INSTA_FORCE_PASS".to_owned(), "0".to_owned()),
     ];
+    if let Some(target_dir) = build_dir.cargo_target_dir() {
+        env.push(("CARGO_TARGET_DIR".to_owned(), target_dir.into_string()));
+    }
     if let Some(encoded_rustflags) = encoded_rustflags(options) {
         debug!(?encoded_rustflags);
         env.push(("CARGO_ENCODED_RUSTFLAGS".to_owned(), encoded_rustflags));
     }
+    console.command_started(phase, &quote_argv(&argv), options);
+    if options.verbosity >= Verbosity::Verbose {
+        for (key, value) in &env {
+            scenario_output.message(&format!("env {key}={value}"))?;
+        }
+    }
     let process_status = Process::run(
         &argv,
         &env,
@@ -93,7 +102,13 @@ pub fn cargo_bin() -> String {
 /// cargo binary itself.
 // (This is split out so it's easier to test.)
 fn cargo_argv(packages: &PackageSelection, phase: Phase, options: &Options) -> Vec<String> {
-    let mut cargo_args = vec![cargo_bin()];
+    let mut cargo_args = if let Some(toolchain) = &options.toolchain {
+        // $CARGO is typically a specific toolchain's binary, which doesn't understand
+        // `+toolchain`, so go through the rustup proxy on the path.
+        vec!["cargo".to_owned(), format!("+{toolchain}")]
+    } else {
+        vec![cargo_bin()]
+    };
     match phase {
         Phase::Test => match &options.test_tool {
             TestTool::Cargo => cargo_args.push("test".to_string()),
@@ -123,6 +138,7 @@ fn cargo_argv(packages: &PackageSelection, phase: Phase, options: &Options) -> V
             cargo_args.push("check".to_string());
             cargo_args.push("--tests".to_string());
         }
+        Phase::PreMutant => unreachable!("the pre-mutant command doesn't run cargo"),
     }
     if let Some(profile) = &options.profile {
         match options.test_tool {
@@ -228,6 +244,23 @@ mod test {
         );
     }
 
+    #[test]
+    fn generate_cargo_args_with_toolchain() {
+        let args = Args::try_parse_from(["mutants", "--toolchain", "nightly"]).unwrap();
+        let options = Options::from_args(&args).unwrap();
+        assert_eq!(
+            cargo_argv(&PackageSelection::All, Phase::Build, &options),
+            [
+                "cargo",
+                "+nightly",
+                "test",
+                "--no-run",
+                "--verbose",
+                "--workspace"
+            ]
+        );
+    }
+
     #[test]
     fn generate_cargo_args_with_additional_cargo_test_args_and_package() {
         let mut options = Options::default();
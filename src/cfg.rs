@@ -0,0 +1,271 @@
+// Copyright 2024 Martin Pool
+
+//! Evaluate `#[cfg(...)]` predicates on features, to skip code that won't be built.
+
+use std::collections::BTreeSet;
+
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Expr, ExprLit, Lit, Meta, Token};
+use tracing::debug;
+
+use crate::package::Package;
+use crate::Features;
+
+/// The features of one package that are enabled by the feature options.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ActiveFeatures {
+    /// All features are enabled, from `--all-features`.
+    all: bool,
+
+    /// The enabled features, including those enabled by other features.
+    enabled: BTreeSet<String>,
+}
+
+impl ActiveFeatures {
+    /// Work out which features of a package are enabled by the feature options.
+    pub fn for_package(package: &Package, features: &Features) -> ActiveFeatures {
+        if features.all_features {
+            return ActiveFeatures {
+                all: true,
+                enabled: BTreeSet::new(),
+            };
+        }
+        let mut queue: Vec<String> = features
+            .features
+            .iter()
+            .flat_map(|arg| arg.split([',', ' ']))
+            .filter_map(|name| match name.split_once('/') {
+                Some((package_name, feature)) if package_name == package.name => Some(feature),
+                Some(_) => None,
+                None => Some(name),
+            })
+            .filter(|name| !name.is_empty())
+            .map(ToOwned::to_owned)
+            .collect();
+        if !features.no_default_features {
+            queue.push("default".to_owned());
+        }
+        let mut enabled = BTreeSet::new();
+        while let Some(name) = queue.pop() {
+            if !enabled.insert(name.clone()) {
+                continue;
+            }
+            for enables in package.features.get(&name).into_iter().flatten() {
+                // `dep:x` only enables a dependency; `x/y` and `x?/y` enable a feature
+                // of dependency `x`, and the first form also enables `x` itself.
+                if enables.starts_with("dep:") || enables.contains("?/") {
+                    continue;
+                }
+                let feature = enables.split('/').next().expect("split has a first part");
+                queue.push(feature.to_owned());
+            }
+        }
+        ActiveFeatures {
+            all: false,
+            enabled,
+        }
+    }
+
+    fn is_enabled(&self, feature: &str) -> bool {
+        self.all || self.enabled.contains(feature)
+    }
+}
+
+/// Combine the feature options with any feature flags in additional Cargo arguments,
+/// such as `--cargo-arg=--features=x`, since they also change what's built.
+pub fn features_with_cargo_args<'a>(
+    features: &Features,
+    cargo_args: impl IntoIterator<Item = &'a String>,
+) -> Features {
+    let mut features = features.clone();
+    let mut cargo_args = cargo_args.into_iter();
+    while let Some(arg) = cargo_args.next() {
+        match arg.as_str() {
+            "--all-features" => features.all_features = true,
+            "--no-default-features" => features.no_default_features = true,
+            "--features" | "-F" => features.features.extend(cargo_args.next().cloned()),
+            _ => {
+                if let Some(value) = arg
+                    .strip_prefix("--features=")
+                    .or_else(|| arg.strip_prefix("-F"))
+                {
+                    features.features.push(value.to_owned());
+                }
+            }
+        }
+    }
+    features
+}
+
+/// True if any of these attributes is a `#[cfg(...)]` that's known to be false for
+/// these features.
+///
+/// Predicates on anything other than features, like `unix`, are assumed to possibly be
+/// true.
+pub fn cfg_known_false(attrs: &[Attribute], active: &ActiveFeatures) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+        .filter_map(|attr| match attr.parse_args::<Meta>() {
+            Ok(meta) => Some(meta),
+            Err(err) => {
+                debug!(?err, "failed to parse cfg attribute");
+                None
+            }
+        })
+        .any(|meta| eval_cfg(&meta, active) == Some(false))
+}
+
+/// Evaluate a cfg predicate, returning None if its value isn't known.
+fn eval_cfg(meta: &Meta, active: &ActiveFeatures) -> Option<bool> {
+    match meta {
+        Meta::NameValue(name_value) if name_value.path.is_ident("feature") => {
+            if let Expr::Lit(ExprLit {
+                lit: Lit::Str(feature),
+                ..
+            }) = &name_value.value
+            {
+                Some(active.is_enabled(&feature.value()))
+            } else {
+                None
+            }
+        }
+        Meta::List(list) => {
+            let args = list
+                .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                .ok()?;
+            let values: Vec<Option<bool>> = args.iter().map(|arg| eval_cfg(arg, active)).collect();
+            if list.path.is_ident("all") {
+                if values.contains(&Some(false)) {
+                    Some(false)
+                } else if values.iter().all(|value| *value == Some(true)) {
+                    Some(true)
+                } else {
+                    None
+                }
+            } else if list.path.is_ident("any") {
+                if values.contains(&Some(true)) {
+                    Some(true)
+                } else if values.iter().all(|value| *value == Some(false)) {
+                    Some(false)
+                } else {
+                    None
+                }
+            } else if list.path.is_ident("not") && args.len() == 1 {
+                eval_cfg(&args[0], active).map(|value| !value)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use syn::parse_quote;
+
+    use super::*;
+
+    fn package_with_features(features: &[(&str, &[&str])]) -> Package {
+        let mut package = Package {
+            name: "demo".to_owned(),
+            version: "0.1.0".to_owned(),
+            relative_dir: "".into(),
+            top_sources: vec!["src/lib.rs".into()],
+            example_sources: Vec::new(),
+            features: BTreeMap::new(),
+            dependencies: BTreeSet::new(),
+        };
+        for (name, enables) in features {
+            package.features.insert(
+                (*name).to_owned(),
+                enables.iter().map(|s| (*s).to_owned()).collect(),
+            );
+        }
+        package
+    }
+
+    fn features(names: &[&str], no_default_features: bool) -> Features {
+        Features {
+            features: names.iter().map(|s| (*s).to_owned()).collect(),
+            no_default_features,
+            all_features: false,
+        }
+    }
+
+    #[test]
+    fn default_features_enable_other_features() {
+        let package = package_with_features(&[
+            ("default", &["std"]),
+            ("std", &["alloc", "dep:libc", "serde?/std"]),
+            ("alloc", &[]),
+            ("fast", &[]),
+        ]);
+        let active = ActiveFeatures::for_package(&package, &features(&[], false));
+        assert!(active.is_enabled("std"));
+        assert!(active.is_enabled("alloc"));
+        assert!(!active.is_enabled("fast"));
+        assert!(!active.is_enabled("libc"));
+        assert!(!active.is_enabled("serde"));
+
+        let active =
+            ActiveFeatures::for_package(&package, &features(&["fast,other/x demo/alloc"], true));
+        assert!(active.is_enabled("fast"));
+        assert!(active.is_enabled("alloc"));
+        assert!(!active.is_enabled("std"));
+        assert!(!active.is_enabled("x"));
+    }
+
+    #[test]
+    fn feature_flags_in_cargo_args_are_included() {
+        let cargo_args = [
+            "--features",
+            "a",
+            "--features=b",
+            "-Fc",
+            "--release",
+            "--all-features",
+        ]
+        .map(ToOwned::to_owned);
+        let combined = features_with_cargo_args(&features(&["x"], true), &cargo_args);
+        assert_eq!(combined.features, ["x", "a", "b", "c"]);
+        assert!(combined.no_default_features);
+        assert!(combined.all_features);
+
+        let combined = features_with_cargo_args(&features(&[], false), &[]);
+        assert_eq!(combined, features(&[], false));
+    }
+
+    #[test]
+    fn evaluate_cfg_predicates() {
+        let package = package_with_features(&[("default", &["std"]), ("std", &[])]);
+        let active = ActiveFeatures::for_package(&package, &features(&[], false));
+        let known_false = |attr: Attribute| cfg_known_false(&[attr], &active);
+        assert!(!known_false(parse_quote!(#[cfg(feature = "std")])));
+        assert!(known_false(parse_quote!(#[cfg(feature = "nightly")])));
+        assert!(known_false(parse_quote!(#[cfg(not(feature = "std"))])));
+        assert!(known_false(
+            parse_quote!(#[cfg(all(unix, feature = "nightly"))])
+        ));
+        assert!(!known_false(
+            parse_quote!(#[cfg(any(unix, feature = "nightly"))])
+        ));
+        assert!(!known_false(parse_quote!(#[cfg(unix)])));
+        assert!(!known_false(
+            parse_quote!(#[cfg(all(unix, feature = "std"))])
+        ));
+        assert!(!known_false(parse_quote!(#[allow(unused)])));
+
+        let all = ActiveFeatures {
+            all: true,
+            enabled: BTreeSet::new(),
+        };
+        assert!(!cfg_known_false(
+            &[parse_quote!(#[cfg(feature = "nightly")])],
+            &all
+        ));
+    }
+}
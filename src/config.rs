@@ -6,16 +6,21 @@
 //! and after finding the source tree, because these together
 //! determine its location.
 //!
+//! Config can also be given in a `[package.metadata.mutants]` table in the root
+//! package's `Cargo.toml`, in which case keys set in the config file take precedence.
+//!
 //! The config file is then merged in to the [Options].
 
+use std::collections::BTreeMap;
 use std::default::Default;
 use std::fs::read_to_string;
 use std::path::Path;
 use std::str::FromStr;
 
 use anyhow::Context;
-use camino::{Utf8Path, Utf8PathBuf};
+use camino::Utf8PathBuf;
 use serde::Deserialize;
+use serde_json::Value;
 
 use crate::options::TestTool;
 use crate::Result;
@@ -24,15 +29,30 @@ use crate::Result;
 ///
 /// This is similar to [Options], and eventually merged into it, but separate because it
 /// can be deserialized.
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Default, Clone, Deserialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct Config {
+    /// Always examine mutants matching these regexps, even if they would otherwise
+    /// be excluded by name, file, or diff filters.
+    pub always_examine_names: Vec<String>,
+    /// Also replace `Vec<u8>` return values with a non-empty byte string.
+    pub byte_strings: bool,
     /// Pass `--cap-lints` to rustc.
     pub cap_lints: bool,
+    /// Copy the baseline's `target` directory into additional build directories.
+    pub copy_target: Option<bool>,
     /// Copy `.git` and other VCS directories to the build directory.
     pub copy_vcs: Option<bool>,
+    /// Cache the mutants discovered in each source file, and reuse them if it's unchanged.
+    pub discovery_cache: bool,
     /// Generate these error values from functions returning Result.
     pub error_values: Vec<String>,
+    /// Generate these error values only from functions returning Result with an error
+    /// type matching the key, such as `io::Error`.
+    pub error_values_by_type: BTreeMap<String, Vec<String>>,
+    /// Also mutate example targets.
+    pub examine_examples: bool,
     /// Generate mutants from source files matching these globs.
     pub examine_globs: Vec<String>,
     /// Exclude mutants from source files matching these globs.
@@ -41,34 +61,82 @@ pub struct Config {
     pub exclude_re: Vec<String>,
     /// Examine only mutants matching these regexps.
     pub examine_re: Vec<String>,
+    /// Also replace float return values with NaN and the infinities.
+    pub float_specials: bool,
     /// Pass extra args to every cargo invocation.
     pub additional_cargo_args: Vec<String>,
     /// Pass extra args to cargo test.
     pub additional_cargo_test_args: Vec<String>,
     /// Minimum test timeout, in seconds, as a floor on the autoset value.
     pub minimum_test_timeout: Option<f64>,
+    /// Generate values for functions returning `&mut` by leaking memory; defaults to true.
+    pub leak_mut: Option<bool>,
+    /// Skip functions with fewer than this many statements.
+    pub min_statements: Option<usize>,
+    /// Count each arm of a `match` as a statement for `min_statements`.
+    pub count_match_arms: bool,
+    /// Generate off-by-one mutants of literal const generic defaults.
+    pub mutate_const_defaults: bool,
+    /// Replace reads of `self.field` with other values, for fields of simple types.
+    pub mutate_field_reads: bool,
+    /// Also mutate `unsafe fn`s.
+    pub mutate_unsafe: bool,
     /// Output directory.
     pub output: Option<Utf8PathBuf>,
+    /// Also replace the bodies of functions returning `Result<(), E>` with `panic!()`.
+    pub panic_mutants: bool,
+    /// Shell command to run after testing each mutant.
+    pub post_mutant_command: Option<String>,
+    /// Shell command to run before building each mutant.
+    pub pre_mutant_command: Option<String>,
     /// Cargo profile.
     pub profile: Option<String>,
+    /// Only mutate functions declared `pub` or `pub(...)`, and methods of trait impls.
+    pub pub_only: Option<bool>,
+    /// Delete statements that call `dbg!`, `println!`, or `eprintln!`.
+    pub remove_debug_output: bool,
+    /// Replace the only use of each function parameter with `Default::default()`.
+    pub replace_args_with_default: bool,
+    /// Run the tests for a caught mutant up to this many more times, in case they're flaky.
+    pub retry: Option<usize>,
     /// Skip calls to functions or methods with these names.
     ///
     /// This is combined with values from the --skip-calls argument.
     pub skip_calls: Vec<String>,
     /// Use built-in defaults for `skip_calls` in addition to any explicit values.
     pub skip_calls_defaults: Option<bool>,
+    /// Don't mutate impls of traits with these names.
+    ///
+    /// This is combined with values from the --skip-trait-impls argument.
+    pub skip_trait_impls: Vec<String>,
+    /// Use built-in defaults for `skip_trait_impls` in addition to any explicit values.
+    pub skip_trait_impls_defaults: Option<bool>,
+    /// Don't mutate trait methods with these names, in impls of any trait.
+    ///
+    /// This is combined with values from the --skip-trait-methods argument.
+    pub skip_trait_methods: Vec<String>,
+    /// Use built-in defaults for `skip_trait_methods` in addition to any explicit values.
+    pub skip_trait_methods_defaults: Option<bool>,
     /// Run tests from these packages for all mutants.
     pub test_package: Vec<String>,
     /// Choice of test tool: cargo or nextest.
     pub test_tool: Option<TestTool>,
+    /// Build and test with this rustup toolchain.
+    pub toolchain: Option<String>,
     /// Timeout multiplier, relative to the baseline 'cargo test'.
     pub timeout_multiplier: Option<f64>,
+    /// Test timeouts in seconds for mutants of particular genres, keyed by genre name.
+    pub timeout_per_genre: BTreeMap<String, f64>,
     /// Build timeout multiplier, relative to the baseline 'cargo build'.
     pub build_timeout_multiplier: Option<f64>,
     /// Run tests from all packages in the workspace, not just the mutated package.
     ///
     /// Overrides `test_package`.
     pub test_workspace: Option<bool>,
+    /// Also replace the bodies of functions returning `()` with `panic!()`.
+    pub unit_panic: bool,
+    /// Also replace values of unrecognized types by parsing `"0"` and `"1"`.
+    pub try_fromstr: bool,
 }
 
 impl Config {
@@ -77,18 +145,30 @@ impl Config {
         Config::from_str(&toml).with_context(|| format!("parse toml from {path:?}"))
     }
 
-    /// Read the config from a tree's `.cargo/mutants.toml`, and return a default (empty)
-    /// Config is the file does not exist.
-    pub fn read_tree_config(workspace_dir: &Utf8Path) -> Result<Config> {
-        let path = workspace_dir.join(".cargo").join("mutants.toml");
-        if path.exists() {
-            Config::read_file(path.as_ref())
-        } else {
-            Ok(Config::default())
+    /// Read config from `[package.metadata.mutants]` in `Cargo.toml`, as reported by
+    /// `cargo metadata`, overlaid by the config file if there is one.
+    ///
+    /// Top-level keys in the file replace the same keys from the package metadata.
+    pub fn read_merged(package_metadata: Option<&Value>, path: Option<&Path>) -> Result<Config> {
+        let Some(metadata) = package_metadata else {
+            return path.map_or_else(|| Ok(Config::default()), Config::read_file);
+        };
+        // Deserialize it directly first, so that errors are reported against the metadata.
+        Config::deserialize(metadata).context(METADATA_CONTEXT)?;
+        let mut table = toml::Table::deserialize(metadata).context(METADATA_CONTEXT)?;
+        if let Some(path) = path {
+            let toml = read_to_string(path).with_context(|| format!("read config {path:?}"))?;
+            Config::from_str(&toml).with_context(|| format!("parse toml from {path:?}"))?;
+            table.extend(toml::Table::from_str(&toml)?);
         }
+        toml::Value::Table(table)
+            .try_into()
+            .context("merge config from Cargo.toml metadata and config file")
     }
 }
 
+const METADATA_CONTEXT: &str = "parse [package.metadata.mutants] from Cargo.toml";
+
 impl FromStr for Config {
     type Err = anyhow::Error;
 
@@ -16,7 +16,7 @@ use tracing::Level;
 use tracing_subscriber::fmt::MakeWriter;
 use tracing_subscriber::prelude::*;
 
-use crate::options::Colors;
+use crate::options::{Colors, Verbosity};
 use crate::outcome::{LabOutcome, ScenarioOutcome, SummaryOutcome};
 use crate::scenario::Scenario;
 use crate::tail_file::TailFile;
@@ -77,7 +77,15 @@ impl Console {
             model.remove_scenario(dir);
         });
 
-        if (outcome.mutant_caught() && !options.print_caught)
+        if let Some(only) = &options.print_only {
+            if !scenario.is_mutant() || outcome.summary() != *only {
+                return;
+            }
+        } else if options.verbosity == Verbosity::Quiet {
+            if outcome.summary() != SummaryOutcome::MissedMutant && !outcome.should_show_logs() {
+                return;
+            }
+        } else if (outcome.mutant_caught() && !options.print_caught)
             || (outcome.scenario.is_mutant()
                 && outcome.check_or_build_failed()
                 && !options.print_unviable)
@@ -85,12 +93,22 @@ impl Console {
             return;
         }
 
-        let mut s = format!(
-            "{:8} {}",
-            style_outcome(outcome),
-            style_scenario(scenario, true),
-        );
-        if options.show_times {
+        // A templated line is exactly what the template asks for, with no times.
+        let templated = match (&options.format, scenario) {
+            (Some(format), Scenario::Mutant(mutant)) => {
+                Some(format.render(mutant, outcome_label(outcome)))
+            }
+            _ => None,
+        };
+        let is_templated = templated.is_some();
+        let mut s = templated.unwrap_or_else(|| {
+            format!(
+                "{:8} {}",
+                style_outcome(outcome),
+                style_scenario(scenario, true),
+            )
+        });
+        if options.show_times && !is_templated {
             let prs: Vec<String> = outcome
                 .phase_results()
                 .iter()
@@ -147,11 +165,23 @@ impl Console {
     }
 
     /// Update that we discovered some mutants to test.
-    pub fn discovered_mutants(&self, mutants: &[Mutant]) {
-        self.message(&format!(
-            "Found {} to test\n",
-            plural(mutants.len(), "mutant")
-        ));
+    ///
+    /// `chosen_seed` is the seed picked to shuffle them, if no seed was given.
+    pub fn discovered_mutants(
+        &self,
+        mutants: &[Mutant],
+        chosen_seed: Option<u64>,
+        options: &Options,
+    ) {
+        if options.verbosity > Verbosity::Quiet {
+            self.message(&format!(
+                "Found {} to test\n",
+                plural(mutants.len(), "mutant")
+            ));
+            if let Some(seed) = chosen_seed {
+                self.message(&format!("Shuffled with --shuffle-seed={seed}\n"));
+            }
+        }
         let n_mutants = mutants.len();
         self.view.update(|model| {
             model.n_mutants = n_mutants;
@@ -172,6 +202,17 @@ impl Console {
         });
     }
 
+    /// With `--verbose`, print a command that's about to run.
+    pub fn command_started(&self, phase: Phase, quoted_argv: &str, options: &Options) {
+        if options.verbosity >= Verbosity::Verbose {
+            self.message(&format!(
+                "{:8} {}\n",
+                style(phase.to_string()).dim(),
+                quoted_argv
+            ));
+        }
+    }
+
     pub fn scenario_phase_finished(&self, dir: &Utf8Path, phase: Phase) {
         self.view.update(|model| {
             model.find_scenario_mut(dir).phase_finished(phase);
@@ -534,7 +575,7 @@ impl nutmeg::Model for ScenarioModel {
         }
         parts.push(self.name.to_string());
         parts.push("...".to_string());
-        parts.push(style_secs(self.phase_start.elapsed()).to_string());
+        parts.push(style_secs(self.phase_start.elapsed()));
         // let mut prs = self
         //     .previous_phase_durations
         //     .iter()
@@ -599,13 +640,25 @@ fn nutmeg_options() -> nutmeg::Options {
 
 /// Return a styled string reflecting the moral value of this outcome.
 pub fn style_outcome(outcome: &ScenarioOutcome) -> StyledObject<&'static str> {
+    let label = outcome_label(outcome);
+    match outcome.summary() {
+        SummaryOutcome::CaughtMutant | SummaryOutcome::Success => style(label).green(),
+        SummaryOutcome::MissedMutant | SummaryOutcome::Failure | SummaryOutcome::Timeout => {
+            style(label).red().bold()
+        }
+        SummaryOutcome::Unviable => style(label).blue(),
+    }
+}
+
+/// The unstyled word describing an outcome, like `caught` or `MISSED`.
+fn outcome_label(outcome: &ScenarioOutcome) -> &'static str {
     match outcome.summary() {
-        SummaryOutcome::CaughtMutant => style("caught").green(),
-        SummaryOutcome::MissedMutant => style("MISSED").red().bold(),
-        SummaryOutcome::Failure => style("FAILED").red().bold(),
-        SummaryOutcome::Success => style("ok").green(),
-        SummaryOutcome::Unviable => style("unviable").blue(),
-        SummaryOutcome::Timeout => style("TIMEOUT").red().bold(),
+        SummaryOutcome::CaughtMutant => "caught",
+        SummaryOutcome::MissedMutant => "MISSED",
+        SummaryOutcome::Failure => "FAILED",
+        SummaryOutcome::Success => "ok",
+        SummaryOutcome::Unviable => "unviable",
+        SummaryOutcome::Timeout => "TIMEOUT",
     }
 }
 
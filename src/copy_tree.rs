@@ -2,6 +2,8 @@
 
 //! Copy a source tree, with some exclusions, to a new temporary directory.
 
+use std::fs::{create_dir_all, File};
+
 use anyhow::Context;
 use camino::{Utf8Path, Utf8PathBuf};
 use ignore::WalkBuilder;
@@ -25,19 +27,26 @@ use windows::copy_symlink;
 static VCS_DIRS: &[&str] = &[".git", ".hg", ".bzr", ".svn", "_darcs", ".jj", ".pijul"];
 
 /// Copy a source tree, with some exclusions, to a new temporary directory.
+///
+/// The directory is created inside `--scratch-dir` if that's set, or otherwise in the
+/// system temporary directory. Each call makes a new uniquely-named directory, so
+/// several build directories can share one scratch dir.
 pub fn copy_tree(
     from_path: &Utf8Path,
     name_base: &str,
     options: &Options,
     console: &Console,
 ) -> Result<TempDir> {
-    let mut total_bytes = 0;
-    let mut total_files = 0;
-    let temp_dir = tempfile::Builder::new()
-        .prefix(name_base)
-        .suffix(".tmp")
-        .tempdir()
-        .context("create temp dir")?;
+    let mut builder = tempfile::Builder::new();
+    builder.prefix(name_base).suffix(".tmp");
+    let temp_dir = if let Some(scratch_dir) = &options.scratch_dir {
+        create_dir_all(scratch_dir)
+            .with_context(|| format!("create scratch directory {scratch_dir:?}"))?;
+        builder.tempdir_in(scratch_dir)
+    } else {
+        builder.tempdir()
+    }
+    .context("create temp dir")?;
     let dest = temp_dir
         .path()
         .try_into()
@@ -56,9 +65,40 @@ pub fn copy_tree(
             let name = entry.file_name().to_string_lossy();
             name != "mutants.out"
                 && name != "mutants.out.old"
+                && name != "mutants.out.cache"
                 && (copy_vcs || !VCS_DIRS.contains(&name.as_ref()))
         });
     debug!(?walk_builder);
+    let (total_bytes, total_files) = copy_walk(&walk_builder, from_path, dest, console)?;
+    console.finish_copy(dest);
+    debug!(?total_bytes, ?total_files, temp_dir = ?temp_dir.path(), "Copied source tree");
+    Ok(temp_dir)
+}
+
+/// Copy a `target` directory from an already-built tree into another build directory.
+///
+/// This should be called after the source has been copied, so that the copied build
+/// outputs are newer than the sources and cargo will consider them fresh.
+pub fn copy_target_dir(from_path: &Utf8Path, dest: &Utf8Path, console: &Console) -> Result<()> {
+    console.start_copy(dest);
+    let mut walk_builder = WalkBuilder::new(from_path);
+    walk_builder.standard_filters(false); // copy everything, including hidden files
+    let (total_bytes, total_files) = copy_walk(&walk_builder, from_path, dest, console)?;
+    console.finish_copy(dest);
+    debug!(?total_bytes, ?total_files, ?dest, "Copied target directory");
+    Ok(())
+}
+
+/// Copy everything found by a walk of `from_path` into `dest`, returning the number
+/// of bytes and files copied.
+fn copy_walk(
+    walk_builder: &WalkBuilder,
+    from_path: &Utf8Path,
+    dest: &Utf8Path,
+    console: &Console,
+) -> Result<(u64, usize)> {
+    let mut total_bytes = 0;
+    let mut total_files = 0;
     for entry in walk_builder.build() {
         check_interrupted()?;
         let entry = entry?;
@@ -66,11 +106,8 @@ pub fn copy_tree(
             .path()
             .strip_prefix(from_path)
             .expect("entry path is in from_path");
-        let dest_path: Utf8PathBuf = temp_dir
-            .path()
-            .join(relative_path)
-            .try_into()
-            .context("Convert path to UTF-8")?;
+        let dest_path: Utf8PathBuf =
+            dest.join(Utf8Path::from_path(relative_path).context("Convert path to UTF-8")?);
         let ft = entry
             .file_type()
             .with_context(|| format!("Expected file to have a file type: {:?}", entry.path()))?;
@@ -81,6 +118,22 @@ pub fn copy_tree(
                     entry.path().to_slash_lossy(),
                 )
             })?;
+            // Keep the mtime so that cargo can tell that copied build products are
+            // still fresh relative to each other and to the sources.
+            let mtime = entry
+                .metadata()
+                .ok()
+                .and_then(|metadata| metadata.modified().ok());
+            if let Some(mtime) = mtime {
+                if let Err(err) = File::options()
+                    .write(true)
+                    .open(&dest_path)
+                    .and_then(|file| file.set_modified(mtime))
+                {
+                    // Perhaps the file is read-only; it's not important.
+                    debug!(?dest_path, ?err, "Failed to set mtime");
+                }
+            }
             total_bytes += bytes_copied;
             total_files += 1;
             console.copy_progress(dest, total_bytes);
@@ -100,16 +153,15 @@ pub fn copy_tree(
             warn!("Unexpected file type: {:?}", entry.path());
         }
     }
-    console.finish_copy(dest);
-    debug!(?total_bytes, ?total_files, temp_dir = ?temp_dir.path(), "Copied source tree");
-    Ok(temp_dir)
+    Ok((total_bytes, total_files))
 }
 
 #[cfg(test)]
 mod test {
     // TODO: Maybe run these with $HOME set to a temp dir so that global git config has no effect?
 
-    use std::fs::{create_dir, write};
+    use std::fs::{create_dir, read_to_string, write, File};
+    use std::time::{Duration, SystemTime};
 
     use camino::Utf8PathBuf;
     use tempfile::TempDir;
@@ -118,7 +170,30 @@ mod test {
     use crate::options::Options;
     use crate::Result;
 
-    use super::copy_tree;
+    use super::{copy_target_dir, copy_tree};
+
+    #[test]
+    fn copy_target_dir_preserves_mtimes() -> Result<()> {
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp = Utf8PathBuf::try_from(tmp_dir.path().to_owned()).unwrap();
+        let from = tmp.join("target");
+        create_dir(&from)?;
+        create_dir(from.join("debug"))?;
+        write(from.join("debug/.fingerprint"), "abc")?;
+        let old_mtime = SystemTime::now() - Duration::from_secs(3600);
+        File::options()
+            .write(true)
+            .open(from.join("debug/.fingerprint"))?
+            .set_modified(old_mtime)?;
+
+        let dest = tmp.join("copy");
+        copy_target_dir(&from, &dest, &Console::new())?;
+        let copied = dest.join("debug/.fingerprint");
+        assert_eq!(read_to_string(&copied)?, "abc");
+        assert_eq!(copied.metadata()?.modified()?, old_mtime);
+
+        Ok(())
+    }
 
     /// Test for regression of <https://github.com/sourcefrog/cargo-mutants/issues/450>
     #[test]
@@ -144,6 +219,27 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn copy_tree_into_scratch_dir() -> Result<()> {
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp = Utf8PathBuf::try_from(tmp_dir.path().to_owned()).unwrap();
+        let src_dir = tmp.join("a");
+        create_dir(&src_dir)?;
+        write(src_dir.join("Cargo.toml"), "[package]\nname = a")?;
+        let scratch_dir = tmp.join("scratch/nested");
+
+        let options = Options::from_arg_strs(["mutants", "--scratch-dir", scratch_dir.as_str()]);
+        let first = copy_tree(&src_dir, "a", &options, &Console::new())?;
+        let second = copy_tree(&src_dir, "a", &options, &Console::new())?;
+        assert!(scratch_dir.is_dir(), "scratch dir is created if missing");
+        assert_eq!(first.path().parent().unwrap(), scratch_dir);
+        assert_eq!(second.path().parent().unwrap(), scratch_dir);
+        assert_ne!(first.path(), second.path());
+        assert!(first.path().join("Cargo.toml").is_file());
+
+        Ok(())
+    }
+
     /// With `gitignore` set to `true`, but no `.git`, don't exclude anything.
     #[test]
     fn copy_with_gitignore_but_without_git_dir() -> Result<()> {
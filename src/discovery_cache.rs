@@ -0,0 +1,251 @@
+// Copyright 2025 Martin Pool
+
+//! Cache the mutants discovered in each source file, so that unchanged files don't need to
+//! be parsed and visited again on the next run.
+//!
+//! Entries are keyed by a hash of the file's content, its path and package, the error
+//! values, and all the options that affect which mutants are generated. Mutants are cached
+//! before they're filtered, so changing filters such as `--file` or `--re` doesn't
+//! invalidate the cache.
+//!
+//! The cache is stored in `mutants.out.cache/discovery.json` in the output directory, so
+//! that it's not rotated away along with `mutants.out`. Each run rewrites it with only
+//! the entries for the files that were seen, so it doesn't grow without limit.
+
+use std::collections::HashMap;
+use std::fs::{create_dir_all, read_to_string, write};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::Arc;
+
+use anyhow::Context;
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::{Deserialize, Serialize};
+use syn::Type;
+use tracing::{debug, warn};
+
+use crate::fnvalue::ErrorExprs;
+use crate::mutant::{Function, Genre, Mutant};
+use crate::pretty::ToPrettyString;
+use crate::source::SourceFile;
+use crate::span::Span;
+use crate::visit::{walk_file, ExternalModRef};
+use crate::{Options, Result};
+
+/// The directory within the output directory that holds the cache.
+const CACHE_DIR_NAME: &str = "mutants.out.cache";
+
+/// The name of the cache file within [`CACHE_DIR_NAME`].
+const DISCOVERY_CACHE_JSON: &str = "discovery.json";
+
+/// Mutants discovered in source files in previous runs, and in this run.
+pub(crate) struct DiscoveryCache {
+    /// The file the cache is read from and written to.
+    path: Utf8PathBuf,
+
+    /// A description of the options that affect discovery, included in every key.
+    fingerprint: String,
+
+    /// Entries read from the cache file.
+    previous: HashMap<String, CachedFile>,
+
+    /// Entries used or added in this run, which will be written back.
+    current: HashMap<String, CachedFile>,
+}
+
+/// The serialized form of the cache.
+#[derive(Serialize, Deserialize)]
+struct CacheFile {
+    /// The version of cargo-mutants that wrote the cache: entries from other versions are
+    /// ignored, because they might generate different mutants.
+    cargo_mutants_version: String,
+    entries: HashMap<String, CachedFile>,
+}
+
+/// The results of walking one source file.
+#[derive(Clone, Serialize, Deserialize)]
+struct CachedFile {
+    mutants: Vec<CachedMutant>,
+    external_mods: Vec<ExternalModRef>,
+    /// The error types of `Result` aliases defined in the file, as Rust syntax.
+    result_alias_errors: Vec<String>,
+}
+
+/// A mutant, without the source file that it's in.
+#[derive(Clone, Serialize, Deserialize)]
+struct CachedMutant {
+    function: Option<Function>,
+    span: Span,
+    replacement: String,
+    genre: Genre,
+}
+
+impl DiscoveryCache {
+    /// Load the cache from the output directory, or start an empty cache if there is
+    /// none, or it can't be read.
+    pub fn load(output_parent_dir: &Utf8Path, options: &Options) -> DiscoveryCache {
+        let path = output_parent_dir
+            .join(CACHE_DIR_NAME)
+            .join(DISCOVERY_CACHE_JSON);
+        let previous = if path.is_file() {
+            match read_to_string(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|json| serde_json::from_str::<CacheFile>(&json).map_err(Into::into))
+            {
+                Ok(cache_file) if cache_file.cargo_mutants_version == crate::VERSION => {
+                    cache_file.entries
+                }
+                Ok(_) => {
+                    debug!(
+                        ?path,
+                        "Discovery cache is from another version; ignoring it"
+                    );
+                    HashMap::new()
+                }
+                Err(err) => {
+                    warn!(?path, "Failed to read discovery cache; ignoring it: {err}");
+                    HashMap::new()
+                }
+            }
+        } else {
+            HashMap::new()
+        };
+        debug!(?path, entries = previous.len(), "Loaded discovery cache");
+        DiscoveryCache {
+            path,
+            fingerprint: options.discovery_fingerprint(),
+            previous,
+            current: HashMap::new(),
+        }
+    }
+
+    /// Find mutants in a source file, reusing the previous results if the file and options
+    /// haven't changed.
+    ///
+    /// Returns the same values as [`walk_file`].
+    pub fn walk_file(
+        &mut self,
+        source_file: &SourceFile,
+        error_exprs: &ErrorExprs,
+        options: &Options,
+    ) -> Result<(Vec<Mutant>, Vec<ExternalModRef>, Vec<Type>)> {
+        let key = self.key(source_file, error_exprs);
+        if let Some(cached) = self
+            .current
+            .get(&key)
+            .or_else(|| self.previous.get(&key))
+            .cloned()
+        {
+            debug!(
+                path = source_file.tree_relative_slashes(),
+                "Reusing cached mutants"
+            );
+            let result = cached.to_walk_result(source_file)?;
+            self.current.insert(key, cached);
+            return Ok(result);
+        }
+        debug!(
+            path = source_file.tree_relative_slashes(),
+            "Discovering mutants in changed file"
+        );
+        let (mutants, external_mods, result_alias_errors) =
+            walk_file(source_file, error_exprs, options)?;
+        self.current.insert(
+            key,
+            CachedFile {
+                mutants: mutants
+                    .iter()
+                    .map(|mutant| CachedMutant {
+                        function: mutant.function.as_deref().cloned(),
+                        span: mutant.span,
+                        replacement: mutant.replacement.clone(),
+                        genre: mutant.genre,
+                    })
+                    .collect(),
+                external_mods: external_mods.clone(),
+                result_alias_errors: result_alias_errors
+                    .iter()
+                    .map(ToPrettyString::to_pretty_string)
+                    .collect(),
+            },
+        );
+        Ok((mutants, external_mods, result_alias_errors))
+    }
+
+    /// Write the entries used in this run back to the cache file.
+    pub fn save(&self) -> Result<()> {
+        let dir = self.path.parent().expect("Cache file has a parent");
+        create_dir_all(dir).with_context(|| format!("Failed to create cache directory {dir:?}"))?;
+        let cache_file = CacheFile {
+            cargo_mutants_version: crate::VERSION.to_owned(),
+            entries: self.current.clone(),
+        };
+        write(
+            &self.path,
+            serde_json::to_string(&cache_file).expect("Serialize discovery cache"),
+        )
+        .with_context(|| format!("Failed to write discovery cache {:?}", self.path))?;
+        debug!(
+            path = ?self.path,
+            entries = self.current.len(),
+            "Saved discovery cache"
+        );
+        Ok(())
+    }
+
+    /// Make the key for a source file: a hash of everything that affects the mutants
+    /// generated from it.
+    ///
+    /// Besides the file itself and the options, this includes the package's dependencies,
+    /// which select default error values, and the error type of the crate's `Result` alias,
+    /// which can be defined in another file.
+    fn key(&self, source_file: &SourceFile, error_exprs: &ErrorExprs) -> String {
+        let mut hasher = DefaultHasher::new();
+        self.fingerprint.hash(&mut hasher);
+        for expr in &error_exprs.general {
+            expr.to_pretty_string().hash(&mut hasher);
+        }
+        for (type_path, expr) in &error_exprs.by_type {
+            (type_path.to_pretty_string(), expr.to_pretty_string()).hash(&mut hasher);
+        }
+        error_exprs
+            .result_alias_error
+            .as_ref()
+            .map(ToPrettyString::to_pretty_string)
+            .hash(&mut hasher);
+        // The whole package, including its dependencies and features.
+        source_file.package.hash(&mut hasher);
+        source_file.tree_relative_path.hash(&mut hasher);
+        source_file.is_top.hash(&mut hasher);
+        source_file.code().hash(&mut hasher);
+        format!("{:016x}", hasher.finish())
+    }
+}
+
+impl CachedFile {
+    /// Rebuild the results of walking a file from the cache.
+    fn to_walk_result(
+        &self,
+        source_file: &SourceFile,
+    ) -> Result<(Vec<Mutant>, Vec<ExternalModRef>, Vec<Type>)> {
+        let mutants = self
+            .mutants
+            .iter()
+            .map(|cached| Mutant {
+                source_file: source_file.clone(),
+                function: cached.function.clone().map(Arc::new),
+                span: cached.span,
+                replacement: cached.replacement.clone(),
+                genre: cached.genre,
+            })
+            .collect();
+        let result_alias_errors = self
+            .result_alias_errors
+            .iter()
+            .map(|type_str| {
+                syn::parse_str(type_str)
+                    .with_context(|| format!("Failed to parse cached error type {type_str:?}"))
+            })
+            .collect::<Result<Vec<Type>>>()?;
+        Ok((mutants, self.external_mods.clone(), result_alias_errors))
+    }
+}
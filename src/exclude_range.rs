@@ -0,0 +1,111 @@
+// Copyright 2024 Martin Pool
+
+//! Exclude mutants within a range of lines in a file, from `--exclude-range`.
+
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use anyhow::{anyhow, ensure, Context, Error};
+
+use crate::mutant::Mutant;
+
+/// A range of lines in one source file, like `src/parser.rs:100-200`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExcludeRange {
+    /// The path of the file relative to the tree root, with forward slashes.
+    pub path: String,
+    /// The 1-based line numbers, inclusive at both ends.
+    pub lines: RangeInclusive<usize>,
+}
+
+impl ExcludeRange {
+    /// True if any part of the mutant's span is within this range.
+    pub fn overlaps(&self, mutant: &Mutant) -> bool {
+        mutant.source_file.tree_relative_slashes() == self.path
+            && mutant.span.start.line <= *self.lines.end()
+            && mutant.span.end.line >= *self.lines.start()
+    }
+}
+
+impl FromStr for ExcludeRange {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (path, lines) = s
+            .rsplit_once(':')
+            .ok_or(anyhow!("exclude range must be like FILE:START-END"))?;
+        ensure!(!path.is_empty(), "exclude range has no file name");
+        let (start, end) = lines.split_once('-').unwrap_or((lines, lines));
+        let start: usize = start.parse().context("exclude range start line")?;
+        let end: usize = end.parse().context("exclude range end line")?;
+        ensure!(
+            start >= 1 && start <= end,
+            "exclude range lines must be 1 or more and in order"
+        );
+        Ok(ExcludeRange {
+            path: path.replace('\\', "/"),
+            lines: start..=end,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use indoc::indoc;
+
+    use super::*;
+    use crate::visit::mutate_source_str;
+    use crate::Options;
+
+    #[test]
+    fn parse_exclude_range() {
+        assert_eq!(
+            ExcludeRange::from_str("src/parser.rs:100-200").unwrap(),
+            ExcludeRange {
+                path: "src/parser.rs".to_owned(),
+                lines: 100..=200
+            }
+        );
+        assert_eq!(ExcludeRange::from_str("src/lib.rs:7").unwrap().lines, 7..=7);
+        for bad in [
+            "src/lib.rs",
+            ":1-2",
+            "src/lib.rs:a-2",
+            "src/lib.rs:5-2",
+            "src/lib.rs:0-2",
+        ] {
+            assert!(ExcludeRange::from_str(bad).is_err(), "{bad:?}");
+        }
+    }
+
+    #[test]
+    fn overlapping_mutants() {
+        let mutants = mutate_source_str(
+            indoc! {"
+                fn one() -> u32 {
+                    10
+                }
+
+                fn two() -> u32 {
+                    20
+                }
+            "},
+            &Options::default(),
+        )
+        .unwrap();
+        let range = ExcludeRange::from_str("src/main.rs:5-7").unwrap();
+        assert_eq!(
+            mutants
+                .iter()
+                .filter(|mutant| !range.overlaps(mutant))
+                .map(|mutant| mutant.name(true))
+                .collect::<Vec<_>>(),
+            [
+                "src/main.rs:2:5: replace one -> u32 with 0",
+                "src/main.rs:2:5: replace one -> u32 with 1",
+            ]
+        );
+        let other_file = ExcludeRange::from_str("src/lib.rs:1-100").unwrap();
+        assert!(!mutants.iter().any(|mutant| other_file.overlaps(mutant)));
+    }
+}
@@ -5,19 +5,22 @@
 //! These are assigned so that different cases that CI or other automation (or
 //! cargo-mutants' own test suite) might want to distinguish are distinct.
 //!
-//! These are also described in README.md.
+//! These are a stable contract: the meaning of each code won't change. They're also
+//! described in `book/src/exit-codes.md` and at the end of `--help`.
 
 // TODO: Maybe merge this with outcome::Status, and maybe merge with sysexit.
 
 /// Everything worked and all the mutants were caught.
 pub const SUCCESS: i32 = 0;
 
-/// The wrong arguments, etc.
+/// The wrong arguments, an invalid config file, a tree that can't be read, etc.
 ///
-/// (1 is also the value returned by Clap.)
+/// (1 is also the value returned by Clap, and by Rust when `main` returns an error,
+/// which happens for errors before testing begins.)
 pub const USAGE: i32 = 1;
 
-/// Found one or mutants that were not caught by tests.
+/// Found one or more mutants that were not caught by tests, or that failed because the
+/// pre-mutant command failed.
 pub const FOUND_PROBLEMS: i32 = 2;
 
 /// One or more tests timed out: probably the mutant caused an infinite loop, or the timeout is too low.
@@ -26,5 +29,38 @@ pub const TIMEOUT: i32 = 3;
 /// The tests are already failing in an unmutated tree.
 pub const BASELINE_FAILED: i32 = 4;
 
-/// An internal software error, from sysexit.
+/// The `--total-timeout` was reached before all the mutants were tested.
+pub const UNFINISHED: i32 = 5;
+
+/// An error while testing mutants, after setup succeeded, such as failing to copy the
+/// tree or to run cargo. The value is from sysexit.
 pub const SOFTWARE: i32 = 70;
+
+/// The run was interrupted, for example by Ctrl-C, before all the mutants were tested.
+/// The value is the conventional 128 plus the number of SIGINT.
+pub const INTERRUPTED: i32 = 130;
+
+// If cargo-mutants panics, Rust exits with code 101.
+
+#[cfg(test)]
+mod test {
+    use itertools::Itertools;
+
+    use super::*;
+
+    #[test]
+    fn exit_codes_are_distinct() {
+        let codes = [
+            SUCCESS,
+            USAGE,
+            FOUND_PROBLEMS,
+            TIMEOUT,
+            BASELINE_FAILED,
+            UNFINISHED,
+            SOFTWARE,
+            INTERRUPTED,
+            101,
+        ];
+        assert!(codes.iter().all_unique());
+    }
+}
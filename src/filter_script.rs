@@ -0,0 +1,62 @@
+// Copyright 2024 Martin Pool
+
+//! Filter discovered mutants by running an external program for each one.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use anyhow::{bail, Context};
+use camino::Utf8Path;
+use tracing::trace;
+
+use crate::mutant::Mutant;
+use crate::Result;
+
+/// Keep only the mutants accepted by the filter script, in their original order.
+///
+/// This is applied after all other filters, so that the script is only run for mutants
+/// that would otherwise be tested.
+pub fn retain_by_filter_script(mutants: Vec<Mutant>, script: &Utf8Path) -> Result<Vec<Mutant>> {
+    let mut kept = Vec::with_capacity(mutants.len());
+    for mutant in mutants {
+        if filter_script_keeps(script, &mutant)? {
+            kept.push(mutant);
+        }
+    }
+    Ok(kept)
+}
+
+/// Run the filter script for one mutant, and return true if the mutant should be kept.
+///
+/// The mutant is written to the script's stdin as JSON, in the same format as
+/// `--list --json`. The mutant is kept if the script exits successfully, and dropped
+/// if it exits with code 1. Any other exit is an error.
+pub fn filter_script_keeps(script: &Utf8Path, mutant: &Mutant) -> Result<bool> {
+    let json = serde_json::to_string(mutant).expect("Serialize mutant");
+    let mut child = Command::new(script)
+        .stdin(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run filter script {script:?}"))?;
+    child
+        .stdin
+        .take()
+        .expect("Child has stdin")
+        .write_all(json.as_bytes())
+        .with_context(|| format!("Failed to write mutant to filter script {script:?}"))?;
+    let status = child
+        .wait()
+        .with_context(|| format!("Failed to wait for filter script {script:?}"))?;
+    trace!(
+        ?status,
+        mutant = mutant.name(true),
+        "filter script finished"
+    );
+    match status.code() {
+        Some(0) => Ok(true),
+        Some(1) => Ok(false),
+        _ => bail!(
+            "Filter script {script:?} failed with {status} for {}",
+            mutant.name(true)
+        ),
+    }
+}
@@ -6,61 +6,370 @@
 
 use std::iter;
 
+use anyhow::{Context, Result};
 use itertools::Itertools;
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{quote, ToTokens};
+use syn::parse_quote;
+use syn::visit_mut::{self, VisitMut};
 use syn::{
-    AngleBracketedGenericArguments, AssocType, Expr, GenericArgument, Ident, Path, PathArguments,
-    ReturnType, TraitBound, Type, TypeArray, TypeImplTrait, TypeParamBound, TypeSlice, TypeTuple,
+    AngleBracketedGenericArguments, AssocType, Expr, ExprArray, ExprCall, ExprParen, ExprPath,
+    ExprRepeat, ExprTuple, ExprUnary, GenericArgument, Ident, Path, PathArguments, ReturnType,
+    TraitBound, Type, TypeArray, TypeGroup, TypeImplTrait, TypeParamBound, TypeParen, TypePath,
+    TypeSlice, TypeTraitObject, TypeTuple, UnOp,
 };
-use tracing::trace;
+use tracing::{debug, trace};
 
+use crate::package::Package;
 use crate::pretty::ToPrettyString;
+use crate::Options;
+
+/// Error values to return as `Err(...)` from functions returning `Result`.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ErrorExprs {
+    /// Error values used for any error type that has no specific values in `by_type`.
+    pub general: Vec<Expr>,
+
+    /// Error values used only for error types whose path ends with the given path.
+    ///
+    /// For example `io::Error` matches both `io::Error` and `std::io::Error`.
+    pub by_type: Vec<(Path, Expr)>,
+
+    /// The error type of the crate's own `Result` alias, like
+    /// `type Result<T> = std::result::Result<T, MyError>;`, used to select values from
+    /// `by_type` for `Result`s that don't name their error type.
+    pub result_alias_error: Option<Type>,
+}
+
+impl ErrorExprs {
+    /// Fill in default error values suited to a package, if none are configured.
+    ///
+    /// If the package depends on `anyhow`, its `Result`s are likely to be `anyhow::Result`,
+    /// whose errors can be made with `anyhow!`.
+    pub fn with_package_defaults(&self, package: &Package) -> ErrorExprs {
+        let mut error_exprs = self.clone();
+        if error_exprs.general.is_empty() && package.dependencies.contains("anyhow") {
+            debug!(
+                package = package.name,
+                "Using anyhow! for default error values"
+            );
+            error_exprs
+                .general
+                .push(parse_quote! { anyhow::anyhow!("mutated") });
+        }
+        error_exprs
+    }
+
+    /// Use the error type of the crate's `Result` alias, given the error types of all the
+    /// aliases found in the crate.
+    ///
+    /// Returns `None` if this wouldn't change the generated values: if there are no
+    /// aliases, if they have different error types, or if no type-specific values match
+    /// the error type.
+    pub fn with_result_alias(&self, alias_error_types: &[Type]) -> Option<ErrorExprs> {
+        let (error_type, others) = alias_error_types.split_first()?;
+        let error_type_str = error_type.to_pretty_string();
+        if others
+            .iter()
+            .any(|other| other.to_pretty_string() != error_type_str)
+        {
+            debug!("Result aliases have different error types; not using them");
+            return None;
+        }
+        let Type::Path(syn::TypePath { path, .. }) = error_type else {
+            return None;
+        };
+        if self.result_alias_error.is_some()
+            || !self
+                .by_type
+                .iter()
+                .any(|(type_path, _)| path_ends_with_path(path, type_path))
+        {
+            return None;
+        }
+        debug!(
+            error_type = error_type_str,
+            "Using error type of Result alias"
+        );
+        Some(ErrorExprs {
+            result_alias_error: Some(error_type.clone()),
+            ..self.clone()
+        })
+    }
+
+    /// The error type of a `Result` path that doesn't name one, if it refers to the crate's
+    /// own alias: either a bare `Result<T>` or a path within the crate like
+    /// `crate::Result<T>`, but not `io::Result<T>`.
+    fn alias_error_type(&self, path: &Path) -> Option<&Type> {
+        let first = &path.segments.first()?.ident;
+        if path.segments.len() == 1 || first == "crate" || first == "self" || first == "super" {
+            self.result_alias_error.as_ref()
+        } else {
+            None
+        }
+    }
+
+    /// The error type of a `Result` type, if it names one or refers to the crate's own
+    /// alias.
+    pub(crate) fn result_error_type<'a>(&'a self, type_: &'a Type) -> Option<&'a Type> {
+        let Type::Path(syn::TypePath { path, .. }) = type_ else {
+            return None;
+        };
+        if !path_ends_with(path, "Result") {
+            return None;
+        }
+        match_second_type_arg(path, "Result").or_else(|| self.alias_error_type(path))
+    }
+
+    /// Select the error values for a `Result` with the given error type, if it's known.
+    ///
+    /// If any of the type-specific values match the error type, only they are used;
+    /// otherwise the general values are used.
+    pub(crate) fn for_error_type(&self, error_type: Option<&Type>) -> Vec<&Expr> {
+        if let Some(Type::Path(syn::TypePath { path, .. })) = error_type {
+            let specific = self
+                .by_type
+                .iter()
+                .filter(|(type_path, _)| path_ends_with_path(path, type_path))
+                .map(|(_, expr)| expr)
+                .collect_vec();
+            if !specific.is_empty() {
+                return specific;
+            }
+        }
+        self.general.iter().collect()
+    }
+}
+
+/// Options that control which values are generated for a type.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct ValueOptions {
+    /// Don't generate values for `&mut` references, because they can only be made by
+    /// leaking memory.
+    pub no_leak_mut: bool,
+
+    /// Also replace `Vec<u8>` by a recognizable non-empty byte string.
+    pub byte_strings: bool,
+
+    /// Also replace types we don't otherwise recognize by parsing `"0"` and `"1"`, which
+    /// suits newtypes that implement `FromStr` but not `Default`.
+    pub try_fromstr: bool,
+
+    /// Also replace floats with NaN and the infinities.
+    pub float_specials: bool,
+}
 
 /// Generate replacement text for a function based on its return type.
+///
+/// At most [`MAX_REPLACEMENTS`] values are returned, so that nested types like
+/// `Result<Option<Vec<(A, B)>>>` can't generate an unbounded number of mutants for one
+/// function. For a `Result`, fewer `Ok` values are kept so that the `Err` values still fit.
 pub(crate) fn return_type_replacements(
     return_type: &ReturnType,
-    error_exprs: &[Expr],
+    error_exprs: &ErrorExprs,
+    value_options: ValueOptions,
 ) -> Vec<TokenStream> {
     match return_type {
         ReturnType::Default => vec![quote! { () }],
-        ReturnType::Type(_rarrow, type_) => type_replacements(type_, error_exprs).collect_vec(),
+        ReturnType::Type(_rarrow, type_) => {
+            capped_type_replacements(type_, error_exprs, value_options).1
+        }
+    }
+}
+
+/// Generate at most [`MAX_REPLACEMENTS`] values for a type, and the name of the rule
+/// that chose them.
+fn capped_type_replacements(
+    type_: &Type,
+    error_exprs: &ErrorExprs,
+    value_options: ValueOptions,
+) -> (&'static str, Vec<TokenStream>) {
+    let (rule, mut reps) = type_replacements_with_rule(type_, error_exprs, value_options, 0);
+    if reps.len() > MAX_REPLACEMENTS {
+        debug!(
+            type_ = type_.to_pretty_string(),
+            count = reps.len(),
+            "Too many replacements for return type, keeping the first {MAX_REPLACEMENTS}"
+        );
+        reps.truncate(MAX_REPLACEMENTS);
+    }
+    (rule, reps)
+}
+
+/// The maximum number of replacement values generated for one function's return type.
+const MAX_REPLACEMENTS: usize = 32;
+
+/// Generate replacement values for the initializer of a `static` of this type.
+///
+/// Replacements that call `Default::default()` are left out, including all of those for
+/// unrecognized types. Unless the static is `lazy`, so that the value is computed by a
+/// closure at runtime, it must be initialized by a constant expression, so only
+/// replacements made of literals, `None`, and tuples or arrays of those are kept.
+///
+/// A non-lazy static whose `value` is computed, like `3 == (2 + 1)`, gets no
+/// replacements: the operators in the expression are already mutated, and replacing the
+/// whole expression with a constant often just reproduces its value.
+pub(crate) fn static_value_replacements(
+    type_: &Type,
+    value: &Expr,
+    error_exprs: &ErrorExprs,
+    value_options: ValueOptions,
+    lazy: bool,
+) -> Vec<TokenStream> {
+    if !lazy && !is_promotable(&value.to_token_stream()) {
+        return Vec::new();
+    }
+    type_replacements(type_, error_exprs, value_options, 0)
+        .filter(|rep| !rep.to_string().contains("Default :: default"))
+        .filter(|rep| lazy || is_promotable(rep))
+        .take(MAX_REPLACEMENTS)
+        .collect()
+}
+
+/// Generate replacement values for a read of a struct field of this type.
+///
+/// Only fields of simple types are mutated: bools, numbers, and `String`. Values of other
+/// types are more often moved, borrowed mutably, or used as method receivers, where a
+/// replacement would rarely build.
+pub(crate) fn field_value_replacements(
+    type_: &Type,
+    error_exprs: &ErrorExprs,
+    value_options: ValueOptions,
+) -> Vec<TokenStream> {
+    let Type::Path(TypePath { qself: None, path }) = type_ else {
+        return Vec::new();
+    };
+    if path.is_ident("bool")
+        || path.is_ident("String")
+        || path_is_unsigned(path)
+        || path_is_signed(path)
+        || path_is_float(path)
+    {
+        type_replacements(type_, error_exprs, value_options, 0).collect()
+    } else {
+        Vec::new()
+    }
+}
+
+/// Replace `Self` in a return type with the type of the enclosing `impl` block.
+///
+/// This lets us generate replacements suited to the concrete type: for example
+/// `fn parse(s: &str) -> Option<Self>` in `impl Parse for u32` can return `Some(0)`.
+/// `Self` in a type we don't recognize still becomes `Default::default()`.
+pub(crate) fn replace_self_type(return_type: &ReturnType, self_ty: &Type) -> ReturnType {
+    struct ReplaceSelf<'t>(&'t Type);
+
+    impl VisitMut for ReplaceSelf<'_> {
+        fn visit_type_mut(&mut self, type_: &mut Type) {
+            if matches!(type_, Type::Path(TypePath { qself: None, path }) if path.is_ident("Self"))
+            {
+                *type_ = self.0.clone();
+            } else {
+                visit_mut::visit_type_mut(self, type_);
+            }
+        }
     }
+
+    let mut return_type = return_type.clone();
+    ReplaceSelf(self_ty).visit_return_type_mut(&mut return_type);
+    return_type
 }
 
+/// The maximum depth of nested types, like `Mutex<Vec<Option<T>>>`, for which we
+/// generate specific replacements.
+///
+/// Beyond this depth the innermost type is replaced with `Default::default()`, so that
+/// deeply nested types don't generate very many mutants.
+const MAX_TYPE_DEPTH: usize = 4;
+
 /// Generate some values that we hope are reasonable replacements for a type.
+///
+/// `depth` is the number of types this one is nested inside.
+fn type_replacements(
+    type_: &Type,
+    error_exprs: &ErrorExprs,
+    value_options: ValueOptions,
+    depth: usize,
+) -> impl Iterator<Item = TokenStream> {
+    type_replacements_with_rule(type_, error_exprs, value_options, depth)
+        .1
+        .into_iter()
+}
+
+/// Generate replacement values for a type, along with the name of the rule that chose
+/// them, as shown by `--explain-type`.
 #[allow(clippy::too_many_lines)]
-fn type_replacements(type_: &Type, error_exprs: &[Expr]) -> impl Iterator<Item = TokenStream> {
+fn type_replacements_with_rule(
+    type_: &Type,
+    error_exprs: &ErrorExprs,
+    value_options: ValueOptions,
+    depth: usize,
+) -> (&'static str, Vec<TokenStream>) {
     // This could probably change to run from some configuration rather than
     // hardcoding various types, which would make it easier to support tree-specific
     // mutation values, and perhaps reduce duplication. However, it seems better
     // to support all the core cases with direct code first to learn what generalizations
     // are needed.
+    if depth > MAX_TYPE_DEPTH {
+        trace!(
+            type_ = type_.to_pretty_string(),
+            "Type is nested too deeply, using Default"
+        );
+        return ("nested too deeply", vec![quote! { Default::default() }]);
+    }
     match type_ {
         Type::Path(syn::TypePath { path, .. }) => {
             // dbg!(&path);
             if path.is_ident("bool") {
-                vec![quote! { true }, quote! { false }]
+                ("bool", vec![quote! { true }, quote! { false }])
             } else if path.is_ident("String") {
-                vec![quote! { String::new() }, quote! { "xyzzy".into() }]
+                (
+                    "String",
+                    vec![quote! { String::new() }, quote! { "xyzzy".into() }],
+                )
             } else if path.is_ident("str") {
-                vec![quote! { "" }, quote! { "xyzzy" }]
+                ("str", vec![quote! { "" }, quote! { "xyzzy" }])
             } else if path_is_unsigned(path) {
-                vec![quote! { 0 }, quote! { 1 }]
+                ("unsigned integer", vec![quote! { 0 }, quote! { 1 }])
             } else if path_is_signed(path) {
-                vec![quote! { 0 }, quote! { 1 }, quote! { -1 }]
+                (
+                    "signed integer",
+                    vec![quote! { 0 }, quote! { 1 }, quote! { -1 }],
+                )
             } else if path_is_nonzero_signed(path) {
-                vec![quote! { 1 }, quote! { -1 }]
+                ("nonzero signed integer", vec![quote! { 1 }, quote! { -1 }])
             } else if path_is_nonzero_unsigned(path) {
-                vec![quote! { 1 }]
+                ("nonzero unsigned integer", vec![quote! { 1 }])
             } else if path_is_float(path) {
-                vec![quote! { 0.0 }, quote! { 1.0 }, quote! { -1.0 }]
+                let mut reps = vec![quote! { 0.0 }, quote! { 1.0 }, quote! { -1.0 }];
+                if value_options.float_specials {
+                    reps.extend([
+                        quote! { #path::NAN },
+                        quote! { #path::INFINITY },
+                        quote! { #path::NEG_INFINITY },
+                    ]);
+                }
+                ("float", reps)
             } else if path_ends_with(path, "Result") {
-                if let Some(ok_type) = match_first_type_arg(path, "Result") {
-                    type_replacements(ok_type, error_exprs)
+                let err_reps = error_exprs
+                    .for_error_type(
+                        match_second_type_arg(path, "Result")
+                            .or_else(|| error_exprs.alias_error_type(path)),
+                    )
+                    .into_iter()
+                    .map(|error_expr| {
+                        quote! { Err(#error_expr) }
+                    })
+                    .collect_vec();
+                let reps = if let Some(ok_type) = match_first_type_arg(path, "Result") {
+                    // Leave room for the errors within the overall limit, so that they're
+                    // not all dropped when the Ok type has many values.
+                    type_replacements(ok_type, error_exprs, value_options, depth + 1)
                         .map(|rep| {
                             quote! { Ok(#rep) }
                         })
+                        .take(MAX_REPLACEMENTS.saturating_sub(err_reps.len()).max(1))
                         .collect_vec()
                 } else {
                     // A result with no type arguments, like `fmt::Result`; hopefully
@@ -68,86 +377,196 @@ fn type_replacements(type_: &Type, error_exprs: &[Expr]) -> impl Iterator<Item =
                     vec![quote! { Ok(Default::default()) }]
                 }
                 .into_iter()
-                .chain(error_exprs.iter().map(|error_expr| {
-                    quote! { Err(#error_expr) }
-                }))
-                .collect_vec()
+                .chain(err_reps)
+                .collect_vec();
+                ("Result", reps)
+            } else if let Some((zero, one)) =
+                match_first_type_arg(path, "Range").and_then(range_bound_literals)
+            {
+                (
+                    "Range",
+                    vec![quote! { #zero..#zero }, quote! { #zero..#one }],
+                )
+            } else if let Some((zero, one)) =
+                match_first_type_arg(path, "RangeInclusive").and_then(range_bound_literals)
+            {
+                (
+                    "RangeInclusive",
+                    vec![quote! { #zero..=#zero }, quote! { #zero..=#one }],
+                )
+            } else if path_ends_with(path, "TokenStream") {
+                // Proc macros and their helpers: an empty expansion.
+                ("TokenStream", vec![quote! { #path::new() }])
+            } else if path_ends_with(path, "Instant") {
+                // There's no default Instant; the only way to make one is to ask the clock.
+                ("Instant", vec![quote! { #path::now() }])
+            } else if path_ends_with(path, "SystemTime") {
+                (
+                    "SystemTime",
+                    vec![quote! { #path::now() }, quote! { #path::UNIX_EPOCH }],
+                )
             } else if path_ends_with(path, "HttpResponse") {
-                vec![quote! { HttpResponse::Ok().finish() }]
+                ("HttpResponse", vec![quote! { HttpResponse::Ok().finish() }])
+            } else if let Some(pinned_type) = match_first_type_arg(path, "Pin") {
+                (
+                    "Pin",
+                    pin_replacements(pinned_type, error_exprs, value_options, depth),
+                )
+            } else if let Some(output_type) = match_first_type_arg(path, "BoxFuture")
+                .or_else(|| match_first_type_arg(path, "LocalBoxFuture"))
+            {
+                // The boxed future aliases from the `futures` crate.
+                (
+                    "BoxFuture",
+                    type_replacements(output_type, error_exprs, value_options, depth + 1)
+                        .map(|rep| {
+                            let block = async_block(&rep);
+                            quote! { Box::pin(#block) }
+                        })
+                        .collect_vec(),
+                )
             } else if let Some(some_type) = match_first_type_arg(path, "Option") {
-                iter::once(quote! { None })
-                    .chain(type_replacements(some_type, error_exprs).map(|rep| {
-                        quote! { Some(#rep) }
-                    }))
-                    .collect_vec()
+                (
+                    "Option",
+                    iter::once(quote! { None })
+                        .chain(
+                            type_replacements(some_type, error_exprs, value_options, depth + 1)
+                                .map(|rep| quote! { Some(#rep) }),
+                        )
+                        .collect_vec(),
+                )
             } else if let Some(element_type) = match_first_type_arg(path, "Vec") {
                 // Generate an empty Vec, and then a one-element vec for every recursive
-                // value.
-                iter::once(quote! { vec![] })
-                    .chain(type_replacements(element_type, error_exprs).map(|rep| {
-                        quote! { vec![#rep] }
-                    }))
-                    .collect_vec()
+                // value, and optionally a byte string for `Vec<u8>`.
+                let is_bytes = matches!(
+                    element_type,
+                    Type::Path(TypePath { path, .. }) if path.is_ident("u8")
+                );
+                (
+                    "Vec",
+                    iter::once(quote! { vec![] })
+                        .chain(
+                            type_replacements(element_type, error_exprs, value_options, depth + 1)
+                                .map(|rep| quote! { vec![#rep] }),
+                        )
+                        .chain(
+                            (value_options.byte_strings && is_bytes)
+                                .then(|| quote! { b"xyzzy".to_vec() }),
+                        )
+                        .collect_vec(),
+                )
+            } else if let Some(Type::Slice(TypeSlice { elem, .. })) =
+                match_first_type_arg(path, "Cow")
+            {
+                // A borrowed empty slice is promoted to a static, and anything else
+                // can be owned in a Vec, rather than leaked.
+                (
+                    "Cow",
+                    iter::once(quote! { Cow::Borrowed(&[]) })
+                        .chain(
+                            type_replacements(elem, error_exprs, value_options, depth + 1)
+                                .map(|rep| quote! { Cow::Owned(vec![#rep]) }),
+                        )
+                        .collect_vec(),
+                )
             } else if let Some(borrowed_type) = match_first_type_arg(path, "Cow") {
-                // TODO: We could specialize Cows for cases like Vec and Box where
+                // TODO: We could specialize Cows for cases like Box where
                 // we would have to leak to make the reference; perhaps it would only
                 // look better...
-                type_replacements(borrowed_type, error_exprs)
-                    .flat_map(|rep| {
-                        [
-                            quote! { Cow::Borrowed(#rep) },
-                            quote! { Cow::Owned(#rep.to_owned()) },
-                        ]
-                    })
-                    .collect_vec()
-            } else if let Some((container_type, inner_type)) = known_container(path) {
-                // Something like Arc, Mutex, etc.
-                // TODO: Ideally we should use the path without relying on it being
-                // imported, but we must strip or rewrite the arguments, so that
-                // `std::sync::Arc<String>` becomes either `std::sync::Arc::<String>::new`
-                // or at least `std::sync::Arc::new`. Similarly for other types.
-                type_replacements(inner_type, error_exprs)
-                    .map(|rep| {
-                        quote! { #container_type::new(#rep) }
-                    })
-                    .collect_vec()
+                (
+                    "Cow",
+                    type_replacements(borrowed_type, error_exprs, value_options, depth + 1)
+                        .flat_map(|rep| {
+                            [
+                                quote! { Cow::Borrowed(#rep) },
+                                quote! { Cow::Owned(#rep.to_owned()) },
+                            ]
+                        })
+                        .collect_vec(),
+                )
+            } else if let Some((container_path, inner_type)) = known_container(path) {
+                // Something like Arc, Mutex, etc. Use the path as written, without its
+                // type arguments, so that `std::sync::Arc<String>` becomes
+                // `std::sync::Arc::new(...)` even if `Arc` is not imported.
+                (
+                    "known container",
+                    match inner_type {
+                        // An unsized value can't be passed to `new`, but a `Box<str>` or
+                        // `Rc<[T]>` can be made `from` a `&str` or a `Vec`.
+                        Type::Path(TypePath { qself: None, path }) if path.is_ident("str") => vec![
+                            quote! { #container_path::from("") },
+                            quote! { #container_path::from("xyzzy") },
+                        ],
+                        Type::Slice(TypeSlice { elem, .. }) => {
+                            iter::once(quote! { #container_path::from(vec![]) })
+                                .chain(
+                                    type_replacements(elem, error_exprs, value_options, depth + 1)
+                                        .map(|rep| quote! { #container_path::from(vec![#rep]) }),
+                                )
+                                .collect_vec()
+                        }
+                        _ => type_replacements(inner_type, error_exprs, value_options, depth + 1)
+                            .map(|rep| {
+                                quote! { #container_path::new(#rep) }
+                            })
+                            .collect_vec(),
+                    },
+                )
             } else if let Some((collection_type, inner_type)) = known_collection(path) {
-                iter::once(quote! { #collection_type::new() })
-                    .chain(type_replacements(inner_type, error_exprs).map(|rep| {
-                        quote! { #collection_type::from_iter([#rep]) }
-                    }))
-                    .collect_vec()
+                (
+                    "known collection",
+                    iter::once(quote! { #collection_type::new() })
+                        .chain(
+                            type_replacements(inner_type, error_exprs, value_options, depth + 1)
+                                .map(|rep| quote! { #collection_type::from_iter([#rep]) }),
+                        )
+                        .collect_vec(),
+                )
             } else if let Some((collection_type, key_type, value_type)) = known_map(path) {
-                let key_reps = type_replacements(key_type, error_exprs).collect_vec();
-                let val_reps = type_replacements(value_type, error_exprs).collect_vec();
-                iter::once(quote! { #collection_type::new() })
-                    .chain(
-                        key_reps
-                            .iter()
-                            .cartesian_product(val_reps)
-                            .map(|(k, v)| quote! { #collection_type::from_iter([(#k, #v)]) }),
-                    )
-                    .collect_vec()
+                let key_reps = type_replacements(key_type, error_exprs, value_options, depth + 1)
+                    .collect_vec();
+                let val_reps = type_replacements(value_type, error_exprs, value_options, depth + 1)
+                    .collect_vec();
+                (
+                    "known map",
+                    iter::once(quote! { #collection_type::new() })
+                        .chain(
+                            key_reps
+                                .iter()
+                                .cartesian_product(val_reps)
+                                .map(|(k, v)| quote! { #collection_type::from_iter([(#k, #v)]) }),
+                        )
+                        .collect_vec(),
+                )
             } else if let Some((collection_type, inner_type)) = maybe_collection_or_container(path)
             {
                 // Something like `T<A>` or `T<'a, A>`, when we don't know exactly how
                 // to call it, but we strongly suspect that you could construct it from
                 // an `A`.
-                iter::once(quote! { #collection_type::new() })
-                    .chain(type_replacements(inner_type, error_exprs).flat_map(|rep| {
-                        [
-                            quote! { #collection_type::from_iter([#rep]) },
-                            quote! { #collection_type::new(#rep) },
-                            quote! { #collection_type::from(#rep) },
-                        ]
-                    }))
-                    .collect_vec()
+                (
+                    "probable collection or container",
+                    iter::once(quote! { #collection_type::new() })
+                        .chain(
+                            type_replacements(inner_type, error_exprs, value_options, depth + 1)
+                                .flat_map(|rep| {
+                                    [
+                                        quote! { #collection_type::from_iter([#rep]) },
+                                        quote! { #collection_type::new(#rep) },
+                                        quote! { #collection_type::from(#rep) },
+                                    ]
+                                }),
+                        )
+                        .collect_vec(),
+                )
             } else {
                 trace!(
                     type_ = type_.to_pretty_string(),
                     "Return type is not recognized, trying Default"
                 );
-                vec![quote! { Default::default() }]
+                (
+                    "unrecognized type",
+                    unrecognized_type_replacements(value_options.try_fromstr),
+                )
             }
         }
         Type::Array(TypeArray { elem, len, .. }) =>
@@ -156,107 +575,240 @@ fn type_replacements(type_: &Type, error_exprs: &[Expr]) -> impl Iterator<Item =
         // large, and values like "all zeros" and "all ones" seem likely to catch
         // lots of things.
         {
-            type_replacements(elem, error_exprs)
-                .map(|r| quote! { [ #r; #len ] })
-                .collect_vec()
+            (
+                "array",
+                type_replacements(elem, error_exprs, value_options, depth + 1)
+                    .map(|r| quote! { [ #r; #len ] })
+                    .collect_vec(),
+            )
         }
-        Type::Slice(TypeSlice { elem, .. }) => iter::once(quote! { Vec::leak(Vec::new()) })
-            .chain(type_replacements(elem, error_exprs).map(|r| quote! { Vec::leak(vec![ #r ]) }))
-            .collect_vec(),
-        Type::Reference(syn::TypeReference {
-            mutability: None,
-            elem,
-            ..
-        }) => match &**elem {
-            // You can't currently match box patterns in Rust
-            Type::Path(path) if path.path.is_ident("str") => {
-                vec![quote! { "" }, quote! { "xyzzy" }]
-            }
-            Type::Slice(TypeSlice { elem, .. }) => iter::once(quote! { Vec::leak(Vec::new()) })
+        Type::Slice(TypeSlice { elem, .. }) => (
+            "slice",
+            iter::once(quote! { Vec::leak(Vec::new()) })
                 .chain(
-                    type_replacements(elem, error_exprs).map(|r| quote! { Vec::leak(vec![ #r ]) }),
+                    type_replacements(elem, error_exprs, value_options, depth + 1)
+                        .map(|r| quote! { Vec::leak(vec![ #r ]) }),
                 )
                 .collect_vec(),
-            _ => type_replacements(elem, error_exprs)
-                .map(|rep| {
-                    quote! { &#rep }
-                })
-                .collect_vec(),
-        },
+        ),
         Type::Reference(syn::TypeReference {
-            mutability: Some(_),
+            mutability: None,
             elem,
             ..
-        }) => match &**elem {
-            Type::Slice(TypeSlice { elem, .. }) => iter::once(quote! { Vec::leak(Vec::new()) })
-                .chain(
-                    type_replacements(elem, error_exprs).map(|r| quote! { Vec::leak(vec![ #r ]) }),
-                )
-                .collect_vec(),
-            _ => {
-                // Make &mut with static lifetime by leaking them on the heap.
-                type_replacements(elem, error_exprs)
+        }) => (
+            "shared reference",
+            match &**elem {
+                // You can't currently match box patterns in Rust
+                Type::Path(path) if path.path.is_ident("str") => {
+                    vec![quote! { "" }, quote! { "xyzzy" }]
+                }
+                // Unsized path types that borrow from a static string.
+                Type::Path(TypePath { path, .. })
+                    if path_ends_with(path, "Path") || path_ends_with(path, "OsStr") =>
+                {
+                    vec![quote! { #path::new("") }, quote! { #path::new("xyzzy") }]
+                }
+                Type::TraitObject(_) => {
+                    // A `dyn Trait` can't be boxed from `Default::default()`, and we don't
+                    // know any concrete type that implements it.
+                    trace!(
+                        type_ = type_.to_pretty_string(),
+                        "Not generating references to trait objects"
+                    );
+                    vec![]
+                }
+                Type::Slice(TypeSlice { elem, .. }) => iter::once(quote! { Vec::leak(Vec::new()) })
+                    .chain(
+                        type_replacements(elem, error_exprs, value_options, depth + 1)
+                            .map(|r| quote! { Vec::leak(vec![ #r ]) }),
+                    )
+                    .collect_vec(),
+                _ => type_replacements(elem, error_exprs, value_options, depth + 1)
                     .map(|rep| {
-                        quote! { Box::leak(Box::new(#rep)) }
+                        // `&0` or `&None` is promoted to a static, but a reference to
+                        // anything built by a function call, like `&vec![]`, would be a
+                        // reference to a temporary, so leak it instead.
+                        if is_promotable(&rep) {
+                            quote! { &#rep }
+                        } else {
+                            quote! { Box::leak(Box::new(#rep)) }
+                        }
                     })
-                    .collect_vec()
-            }
-        },
+                    .collect_vec(),
+            },
+        ),
+        Type::Reference(syn::TypeReference {
+            mutability: Some(_),
+            ..
+        }) if value_options.no_leak_mut => {
+            trace!(
+                type_ = type_.to_pretty_string(),
+                "Not generating &mut values that would leak"
+            );
+            ("mutable reference, not leaked", vec![])
+        }
+        Type::Reference(syn::TypeReference {
+            mutability: Some(_),
+            elem,
+            ..
+        }) => (
+            "mutable reference",
+            match &**elem {
+                Type::Slice(TypeSlice { elem, .. }) => iter::once(quote! { Vec::leak(Vec::new()) })
+                    .chain(
+                        type_replacements(elem, error_exprs, value_options, depth + 1)
+                            .map(|r| quote! { Vec::leak(vec![ #r ]) }),
+                    )
+                    .collect_vec(),
+                Type::TraitObject(_) => vec![],
+                _ => {
+                    // Make &mut with static lifetime by leaking them on the heap.
+                    type_replacements(elem, error_exprs, value_options, depth + 1)
+                        .map(|rep| {
+                            quote! { Box::leak(Box::new(#rep)) }
+                        })
+                        .collect_vec()
+                }
+            },
+        ),
         Type::Tuple(TypeTuple { elems, .. }) => {
             // Generate the cartesian product of replacements of every type within the tuple.
-            elems
-                .iter()
-                .map(|elem| type_replacements(elem, error_exprs).collect_vec())
-                .multi_cartesian_product()
-                .map(|reps| {
-                    quote! { ( #( #reps ),* ) }
-                })
-                .collect_vec()
+            (
+                "tuple",
+                elems
+                    .iter()
+                    .map(|elem| {
+                        type_replacements(elem, error_exprs, value_options, depth + 1).collect_vec()
+                    })
+                    .multi_cartesian_product()
+                    .map(|reps| {
+                        quote! { ( #( #reps ),* ) }
+                    })
+                    .collect_vec(),
+            )
         }
         // -> impl Iterator<Item = T>
         Type::ImplTrait(impl_trait) => {
             if let Some(item_type) = match_impl_iterator(impl_trait) {
-                iter::once(quote! { ::std::iter::empty() })
-                    .chain(
-                        type_replacements(item_type, error_exprs)
-                            .map(|r| quote! { ::std::iter::once(#r) }),
-                    )
-                    .collect_vec()
+                (
+                    "impl Iterator",
+                    iter::once(quote! { ::std::iter::empty() })
+                        .chain(
+                            type_replacements(item_type, error_exprs, value_options, depth + 1)
+                                .map(|r| quote! { ::std::iter::once(#r) }),
+                        )
+                        .collect_vec(),
+                )
+            } else if let Some(output_type) = match_future_output(&impl_trait.bounds) {
+                (
+                    "impl Future",
+                    type_replacements(output_type, error_exprs, value_options, depth + 1)
+                        .map(|r| async_block(&r))
+                        .collect_vec(),
+                )
             } else {
                 // TODO: Can we do anything with other impl traits?
-                vec![]
+                ("other impl trait", vec![])
             }
         }
-        Type::Never(_) => {
-            vec![]
+        Type::Never(_) => ("never type", vec![]),
+        // -> (T), or a type wrapped in an invisible group by a macro
+        Type::Paren(TypeParen { elem, .. }) | Type::Group(TypeGroup { elem, .. }) => {
+            type_replacements_with_rule(elem, error_exprs, value_options, depth)
         }
         _ => {
             trace!(?type_, "Return type is not recognized, trying Default");
-            vec![quote! { Default::default() }]
+            (
+                "unrecognized type",
+                unrecognized_type_replacements(value_options.try_fromstr),
+            )
         }
     }
-    .into_iter()
+}
+
+/// Explain the replacement values generated for a return type.
+fn explain_type(type_: &Type, error_exprs: &ErrorExprs, value_options: ValueOptions) -> String {
+    let (rule, reps) = capped_type_replacements(type_, error_exprs, value_options);
+    let mut s = format!("{} matches rule: {rule}\n", type_.to_pretty_string());
+    if reps.is_empty() {
+        s.push_str("No replacements are generated\n");
+    }
+    for rep in reps {
+        s.push_str(&rep.to_pretty_string());
+        s.push('\n');
+    }
+    s
+}
+
+/// Parse a type and explain its replacements according to the options, for `--explain-type`.
+pub(crate) fn explain_type_str(type_str: &str, options: &Options) -> Result<String> {
+    let type_: Type =
+        syn::parse_str(type_str).with_context(|| format!("Failed to parse type {type_str:?}"))?;
+    Ok(explain_type(
+        &type_,
+        &options.parsed_error_exprs()?,
+        options.value_options(),
+    ))
+}
+
+/// Replacements for a type we don't recognize: its default, and optionally some parsed values.
+fn unrecognized_type_replacements(try_fromstr: bool) -> Vec<TokenStream> {
+    let mut reps = vec![quote! { Default::default() }];
+    if try_fromstr {
+        reps.push(quote! { "0".parse().unwrap() });
+        reps.push(quote! { "1".parse().unwrap() });
+    }
+    reps
 }
 
 fn path_ends_with(path: &Path, ident: &str) -> bool {
     path.segments.last().is_some_and(|s| s.ident == ident)
 }
 
+/// True if the identifiers in `suffix` match the last identifiers in `path`, ignoring
+/// any generic arguments.
+fn path_ends_with_path(path: &Path, suffix: &Path) -> bool {
+    path.segments.len() >= suffix.segments.len()
+        && path
+            .segments
+            .iter()
+            .rev()
+            .zip(suffix.segments.iter().rev())
+            .all(|(a, b)| a.ident == b.ident)
+}
+
 fn match_impl_iterator(TypeImplTrait { bounds, .. }: &TypeImplTrait) -> Option<&Type> {
+    match_bound_assoc_type(bounds, "Iterator", "Item")
+}
+
+/// Match `impl Future<Output = T>` or `dyn Future<Output = T>`, returning `T`.
+fn match_future_output<'t>(
+    bounds: impl IntoIterator<Item = &'t TypeParamBound>,
+) -> Option<&'t Type> {
+    match_bound_assoc_type(bounds, "Future", "Output")
+}
+
+/// If one of the bounds is a trait with the given name that binds an associated type,
+/// like `Iterator<Item = T>`, return the associated type.
+fn match_bound_assoc_type<'t>(
+    bounds: impl IntoIterator<Item = &'t TypeParamBound>,
+    trait_name: &str,
+    assoc_name: &str,
+) -> Option<&'t Type> {
     for bound in bounds {
         if let TypeParamBound::Trait(TraitBound { path, .. }) = bound {
             if let Some(last_segment) = path.segments.last() {
-                if last_segment.ident == "Iterator" {
+                if last_segment.ident == trait_name {
                     if let PathArguments::AngleBracketed(AngleBracketedGenericArguments {
                         args,
                         ..
                     }) = &last_segment.arguments
                     {
-                        if let Some(GenericArgument::AssocType(AssocType { ident, ty, .. })) =
-                            args.first()
-                        {
-                            if ident == "Item" {
-                                return Some(ty);
+                        for arg in args {
+                            if let GenericArgument::AssocType(AssocType { ident, ty, .. }) = arg {
+                                if ident == assoc_name {
+                                    return Some(ty);
+                                }
                             }
                         }
                     }
@@ -267,13 +819,87 @@ fn match_impl_iterator(TypeImplTrait { bounds, .. }: &TypeImplTrait) -> Option<&
     None
 }
 
+/// An `async` block that returns a value: `async {}` for `()`, or else `async { value }`.
+fn async_block(value: &TokenStream) -> TokenStream {
+    if value.to_string() == "()" {
+        quote! { async {} }
+    } else {
+        quote! { async { #value } }
+    }
+}
+
+/// Replacements for a pinned type, like `Pin<Box<dyn Future<Output = T>>>`.
+///
+/// A boxed future is replaced with a future that immediately returns each replacement
+/// of its output type. Any other boxed value is pinned with `Box::pin`. There's no
+/// general way to make other pinned pointers, or boxes of other trait objects, so
+/// they get no replacements rather than an unviable `Default::default()`.
+fn pin_replacements(
+    pinned_type: &Type,
+    error_exprs: &ErrorExprs,
+    value_options: ValueOptions,
+    depth: usize,
+) -> Vec<TokenStream> {
+    let Type::Path(TypePath { path, .. }) = pinned_type else {
+        return vec![];
+    };
+    match match_first_type_arg(path, "Box") {
+        Some(Type::TraitObject(TypeTraitObject { bounds, .. })) => match_future_output(bounds)
+            .map(|output_type| {
+                type_replacements(output_type, error_exprs, value_options, depth + 1)
+                    .map(|rep| {
+                        let block = async_block(&rep);
+                        quote! { Box::pin(#block) }
+                    })
+                    .collect_vec()
+            })
+            .unwrap_or_default(),
+        Some(boxed_type) => type_replacements(boxed_type, error_exprs, value_options, depth + 1)
+            .map(|rep| quote! { Box::pin(#rep) })
+            .collect_vec(),
+        None => vec![],
+    }
+}
+
+/// True if a replacement expression is a constant that Rust will promote to a static
+/// when it's borrowed, so that `&expr` can be returned from a function.
+///
+/// This covers literals, paths like `None`, and tuples, arrays, and `Some`, `Ok`, or
+/// `Err` of those; anything else, like a function call, is assumed to make a temporary.
+/// The same expressions can initialize a non-lazy `static`.
+fn is_promotable(expr: &TokenStream) -> bool {
+    fn promotable(expr: &Expr) -> bool {
+        match expr {
+            Expr::Lit(_) | Expr::Path(_) => true,
+            Expr::Unary(ExprUnary {
+                op: UnOp::Neg(_),
+                expr,
+                ..
+            })
+            | Expr::Paren(ExprParen { expr, .. })
+            | Expr::Repeat(ExprRepeat { expr, .. }) => promotable(expr),
+            Expr::Tuple(ExprTuple { elems, .. }) | Expr::Array(ExprArray { elems, .. }) => {
+                elems.iter().all(promotable)
+            }
+            Expr::Call(ExprCall { func, args, .. }) => {
+                matches!(func.as_ref(), Expr::Path(ExprPath { path, .. })
+                    if ["Some", "Ok", "Err"].iter().any(|name| path.is_ident(name)))
+                    && args.iter().all(promotable)
+            }
+            _ => false,
+        }
+    }
+    syn::parse2::<Expr>(expr.clone()).is_ok_and(|expr| promotable(&expr))
+}
+
 /// If the type has a single type argument then, perhaps it's a simple container
 /// like Box, Cell, Mutex, etc, that can be constructed with `T::new(inner_val)`.
 ///
-/// If so, return the short name (like "Box") and the inner type.
-fn known_container(path: &Path) -> Option<(&Ident, &Type)> {
+/// If so, return the path of the container with its type arguments stripped (like
+/// `std::sync::Arc`), and the inner type.
+fn known_container(path: &Path) -> Option<(Path, &Type)> {
     let last = path.segments.last()?;
-    if ["Box", "Cell", "RefCell", "Arc", "Rc", "Mutex"]
+    if ["Box", "Cell", "RefCell", "Arc", "Rc", "Mutex", "RwLock"]
         .iter()
         .any(|v| last.ident == v)
     {
@@ -281,10 +907,15 @@ fn known_container(path: &Path) -> Option<(&Ident, &Type)> {
             &last.arguments
         {
             // TODO: Skip lifetime args.
-            // TODO: Return the path with args stripped out.
             if args.len() == 1 {
                 if let Some(GenericArgument::Type(inner_type)) = args.first() {
-                    return Some((&last.ident, inner_type));
+                    let mut container_path = path.clone();
+                    container_path
+                        .segments
+                        .last_mut()
+                        .expect("Path has a last segment")
+                        .arguments = PathArguments::None;
+                    return Some((container_path, inner_type));
                 }
             }
         }
@@ -369,6 +1000,22 @@ fn path_is_float(path: &Path) -> bool {
     ["f32", "f64"].iter().any(|s| path.is_ident(s))
 }
 
+/// Literals for the start and end of a range over a numeric type: an empty range
+/// from zero to zero, and a one-element range from zero to one.
+fn range_bound_literals(type_: &Type) -> Option<(TokenStream, TokenStream)> {
+    match type_ {
+        Type::Path(syn::TypePath { path, .. }) if path_is_float(path) => {
+            Some((quote! { 0.0 }, quote! { 1.0 }))
+        }
+        Type::Path(syn::TypePath { path, .. })
+            if path_is_unsigned(path) || path_is_signed(path) =>
+        {
+            Some((quote! { 0 }, quote! { 1 }))
+        }
+        _ => None,
+    }
+}
+
 fn path_is_unsigned(path: &Path) -> bool {
     ["u8", "u16", "u32", "u64", "u128", "usize"]
         .iter()
@@ -434,17 +1081,44 @@ fn match_first_type_arg<'p>(path: &'p Path, expected_ident: &str) -> Option<&'p
     None
 }
 
+/// Return the second type argument of a path like `Result<T, E>`, if the last
+/// segment has the expected name.
+pub(crate) fn match_second_type_arg<'p>(path: &'p Path, expected_ident: &str) -> Option<&'p Type> {
+    let last = path.segments.last()?;
+    if last.ident != expected_ident {
+        return None;
+    }
+    if let PathArguments::AngleBracketed(AngleBracketedGenericArguments { args, .. }) =
+        &last.arguments
+    {
+        args.iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(arg_type) => Some(arg_type),
+                _ => None,
+            })
+            .nth(1)
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod test {
     use itertools::Itertools;
     use pretty_assertions::assert_eq;
-    use syn::{parse_quote, Expr, ReturnType};
+    use syn::token::RArrow;
+    use syn::{parse_quote, Expr, ReturnType, Type, TypeGroup};
+
+    use indoc::indoc;
 
+    use super::{
+        explain_type, known_map, replace_self_type, return_type_replacements,
+        type_replacements_with_rule, ErrorExprs, ValueOptions, MAX_REPLACEMENTS,
+    };
     use crate::fnvalue::match_impl_iterator;
+    use crate::package::Package;
     use crate::pretty::ToPrettyString;
 
-    use super::{known_map, return_type_replacements};
-
     #[test]
     fn recurse_into_result_bool() {
         check_replacements(
@@ -468,6 +1142,130 @@ mod test {
         );
     }
 
+    #[test]
+    fn error_values_selected_by_error_type() {
+        let error_exprs = ErrorExprs {
+            general: vec![parse_quote! { anyhow!("mutated") }],
+            by_type: vec![(
+                parse_quote! { io::Error },
+                parse_quote! { io::Error::new(io::ErrorKind::Other, "mutated") },
+            )],
+            result_alias_error: None,
+        };
+        let names = |return_type: ReturnType| {
+            return_type_replacements(&return_type, &error_exprs, ValueOptions::default())
+                .into_iter()
+                .map(|t| t.to_pretty_string())
+                .collect_vec()
+        };
+        assert_eq!(
+            names(parse_quote! { -> Result<(), std::io::Error> }),
+            [
+                "Ok(())",
+                r#"Err(io::Error::new(io::ErrorKind::Other, "mutated"))"#
+            ]
+        );
+        assert_eq!(
+            names(parse_quote! { -> Result<(), MyError> }),
+            ["Ok(())", r#"Err(anyhow!("mutated"))"#]
+        );
+        assert_eq!(
+            names(parse_quote! { -> anyhow::Result<()> }),
+            ["Ok(())", r#"Err(anyhow!("mutated"))"#]
+        );
+    }
+
+    #[test]
+    fn error_values_selected_by_result_alias_error_type() {
+        let error_exprs = ErrorExprs {
+            general: vec![parse_quote! { anyhow!("mutated") }],
+            by_type: vec![(parse_quote! { MyError }, parse_quote! { MyError::Mutated })],
+            result_alias_error: None,
+        };
+        assert!(error_exprs.with_result_alias(&[]).is_none());
+        assert!(
+            error_exprs
+                .with_result_alias(&[parse_quote! { OtherError }])
+                .is_none(),
+            "no type-specific values for the alias's error type"
+        );
+        assert!(
+            error_exprs
+                .with_result_alias(&[parse_quote! { MyError }, parse_quote! { OtherError }])
+                .is_none(),
+            "aliases disagree about the error type"
+        );
+        let error_exprs = error_exprs
+            .with_result_alias(&[
+                parse_quote! { crate::MyError },
+                parse_quote! { crate::MyError },
+            ])
+            .unwrap();
+        let names = |return_type: ReturnType| {
+            return_type_replacements(&return_type, &error_exprs, ValueOptions::default())
+                .into_iter()
+                .map(|t| t.to_pretty_string())
+                .collect_vec()
+        };
+        assert_eq!(
+            names(parse_quote! { -> Result<()> }),
+            ["Ok(())", "Err(MyError::Mutated)"]
+        );
+        assert_eq!(
+            names(parse_quote! { -> crate::error::Result<()> }),
+            ["Ok(())", "Err(MyError::Mutated)"]
+        );
+        assert_eq!(
+            names(parse_quote! { -> io::Result<()> }),
+            ["Ok(())", r#"Err(anyhow!("mutated"))"#]
+        );
+        assert_eq!(
+            names(parse_quote! { -> Result<(), io::Error> }),
+            ["Ok(())", r#"Err(anyhow!("mutated"))"#]
+        );
+    }
+
+    #[test]
+    fn anyhow_dependency_gives_default_error_values() {
+        let package = |dependencies: &[&str]| Package {
+            name: "demo".to_owned(),
+            version: "0.1.0".to_owned(),
+            relative_dir: "".into(),
+            top_sources: vec!["src/lib.rs".into()],
+            example_sources: Vec::new(),
+            features: std::collections::BTreeMap::new(),
+            dependencies: dependencies.iter().map(|s| (*s).to_owned()).collect(),
+        };
+        let names = |error_exprs: &ErrorExprs| {
+            return_type_replacements(
+                &parse_quote! { -> anyhow::Result<u32> },
+                error_exprs,
+                ValueOptions::default(),
+            )
+            .into_iter()
+            .map(|t| t.to_pretty_string())
+            .collect_vec()
+        };
+        assert_eq!(
+            names(&ErrorExprs::default().with_package_defaults(&package(&["anyhow", "serde"]))),
+            ["Ok(0)", "Ok(1)", r#"Err(anyhow::anyhow!("mutated"))"#]
+        );
+        assert_eq!(
+            names(&ErrorExprs::default().with_package_defaults(&package(&["serde"]))),
+            ["Ok(0)", "Ok(1)"]
+        );
+        // Configured error values are used instead of the default.
+        let configured = ErrorExprs {
+            general: vec![parse_quote! { Error::Mutated }],
+            by_type: Vec::new(),
+            result_alias_error: None,
+        };
+        assert_eq!(
+            names(&configured.with_package_defaults(&package(&["anyhow"]))),
+            ["Ok(0)", "Ok(1)", "Err(Error::Mutated)"]
+        );
+    }
+
     #[test]
     fn u16_replacements() {
         check_replacements(&parse_quote! { -> u16 }, &[], &["0", "1"]);
@@ -492,10 +1290,39 @@ mod test {
     }
 
     #[test]
-    fn unit_replacement() {
-        check_replacements(&parse_quote! { -> () }, &[], &["()"]);
-    }
-
+    fn parenthesized_and_grouped_types_are_unwrapped() {
+        check_replacements(&parse_quote! { -> (bool) }, &[], &["true", "false"]);
+        check_replacements(
+            &parse_quote! { -> Option<(u32)> },
+            &[],
+            &["None", "Some(0)", "Some(1)"],
+        );
+        let grouped = Type::Group(TypeGroup {
+            group_token: syn::token::Group::default(),
+            elem: Box::new(parse_quote! { bool }),
+        });
+        check_replacements(
+            &ReturnType::Type(RArrow::default(), Box::new(grouped.clone())),
+            &[],
+            &["true", "false"],
+        );
+        assert_eq!(
+            type_replacements_with_rule(
+                &grouped,
+                &ErrorExprs::default(),
+                ValueOptions::default(),
+                0
+            )
+            .0,
+            "bool"
+        );
+    }
+
+    #[test]
+    fn unit_replacement() {
+        check_replacements(&parse_quote! { -> () }, &[], &["()"]);
+    }
+
     #[test]
     fn result_unit_replacement() {
         check_replacements(&parse_quote! { -> Result<(), Error> }, &[], &["Ok(())"]);
@@ -521,6 +1348,300 @@ mod test {
         );
     }
 
+    #[test]
+    fn range_usize_replacement() {
+        check_replacements(
+            &parse_quote! { -> std::ops::Range<usize> },
+            &[],
+            &["0..0", "0..1"],
+        );
+    }
+
+    #[test]
+    fn range_inclusive_u8_replacement() {
+        check_replacements(
+            &parse_quote! { -> RangeInclusive<u8> },
+            &[],
+            &["0..=0", "0..=1"],
+        );
+    }
+
+    #[test]
+    fn mutex_vec_u8_replacement() {
+        check_replacements(
+            &parse_quote! { -> Mutex<Vec<u8>> },
+            &[],
+            &[
+                "Mutex::new(vec![])",
+                "Mutex::new(vec![0])",
+                "Mutex::new(vec![1])",
+            ],
+        );
+    }
+
+    #[test]
+    fn arc_mutex_replacement() {
+        check_replacements(
+            &parse_quote! { -> Arc<Mutex<u32>> },
+            &[],
+            &["Arc::new(Mutex::new(0))", "Arc::new(Mutex::new(1))"],
+        );
+        check_replacements(
+            &parse_quote! { -> Arc<RwLock<bool>> },
+            &[],
+            &[
+                "Arc::new(RwLock::new(true))",
+                "Arc::new(RwLock::new(false))",
+            ],
+        );
+    }
+
+    #[test]
+    fn qualified_container_paths_are_kept() {
+        check_replacements(
+            &parse_quote! { -> std::sync::Arc<std::sync::Mutex<u32>> },
+            &[],
+            &[
+                "std::sync::Arc::new(std::sync::Mutex::new(0))",
+                "std::sync::Arc::new(std::sync::Mutex::new(1))",
+            ],
+        );
+    }
+
+    #[test]
+    fn try_fromstr_replacement_for_unrecognized_type() {
+        let return_type = parse_quote! { -> UserId };
+        let reps = |try_fromstr| {
+            return_type_replacements(
+                &return_type,
+                &ErrorExprs::default(),
+                ValueOptions {
+                    try_fromstr,
+                    ..Default::default()
+                },
+            )
+            .into_iter()
+            .map(|t| t.to_pretty_string())
+            .collect_vec()
+        };
+        assert_eq!(reps(false), ["Default::default()"]);
+        assert_eq!(
+            reps(true),
+            [
+                "Default::default()",
+                "\"0\".parse().unwrap()",
+                "\"1\".parse().unwrap()"
+            ]
+        );
+        assert_eq!(
+            return_type_replacements(
+                &parse_quote! { -> Option<UserId> },
+                &ErrorExprs::default(),
+                ValueOptions {
+                    try_fromstr: true,
+                    ..Default::default()
+                }
+            )
+            .into_iter()
+            .map(|t| t.to_pretty_string())
+            .collect_vec(),
+            [
+                "None",
+                "Some(Default::default())",
+                "Some(\"0\".parse().unwrap())",
+                "Some(\"1\".parse().unwrap())"
+            ]
+        );
+    }
+
+    #[test]
+    fn explain_type_shows_rule_and_replacements() {
+        let error_exprs = ErrorExprs {
+            general: vec![parse_quote! { anyhow!("mutated") }],
+            by_type: Vec::new(),
+            result_alias_error: None,
+        };
+        assert_eq!(
+            explain_type(
+                &parse_quote! { Result<Vec<u8>, Error> },
+                &error_exprs,
+                ValueOptions::default()
+            ),
+            indoc! {r#"
+                Result<Vec<u8>, Error> matches rule: Result
+                Ok(vec![])
+                Ok(vec![0])
+                Ok(vec![1])
+                Err(anyhow!("mutated"))
+            "#}
+        );
+        assert_eq!(
+            explain_type(
+                &parse_quote! { UserId },
+                &ErrorExprs::default(),
+                ValueOptions::default()
+            ),
+            "UserId matches rule: unrecognized type\nDefault::default()\n"
+        );
+        assert_eq!(
+            explain_type(
+                &parse_quote! { &mut Vec<u8> },
+                &ErrorExprs::default(),
+                ValueOptions {
+                    no_leak_mut: true,
+                    ..Default::default()
+                }
+            ),
+            "&mut Vec<u8> matches rule: mutable reference, not leaked\n\
+             No replacements are generated\n"
+        );
+    }
+
+    #[test]
+    fn float_specials_replacements() {
+        let reps = |return_type: ReturnType, float_specials| {
+            return_type_replacements(
+                &return_type,
+                &ErrorExprs::default(),
+                ValueOptions {
+                    float_specials,
+                    ..Default::default()
+                },
+            )
+            .into_iter()
+            .map(|t| t.to_pretty_string())
+            .collect_vec()
+        };
+        assert_eq!(reps(parse_quote! { -> f64 }, false), ["0.0", "1.0", "-1.0"]);
+        assert_eq!(
+            reps(parse_quote! { -> f64 }, true),
+            [
+                "0.0",
+                "1.0",
+                "-1.0",
+                "f64::NAN",
+                "f64::INFINITY",
+                "f64::NEG_INFINITY"
+            ]
+        );
+        assert_eq!(
+            reps(parse_quote! { -> Option<f32> }, true),
+            [
+                "None",
+                "Some(0.0)",
+                "Some(1.0)",
+                "Some(-1.0)",
+                "Some(f32::NAN)",
+                "Some(f32::INFINITY)",
+                "Some(f32::NEG_INFINITY)"
+            ]
+        );
+    }
+
+    #[test]
+    fn result_vec_u8_replacement() {
+        check_replacements(
+            &parse_quote! { -> Result<Vec<u8>, Error> },
+            &[parse_quote! { anyhow!("mutated") }],
+            &[
+                "Ok(vec![])",
+                "Ok(vec![0])",
+                "Ok(vec![1])",
+                "Err(anyhow!(\"mutated\"))",
+            ],
+        );
+    }
+
+    #[test]
+    fn deeply_nested_replacements_are_capped() {
+        let return_type = parse_quote! { -> Result<Option<Vec<(u8, u8, u8, u8, u8)>>, Error> };
+        let reps = return_type_replacements(
+            &return_type,
+            &ErrorExprs::default(),
+            ValueOptions::default(),
+        );
+        assert_eq!(reps.len(), MAX_REPLACEMENTS);
+        assert_eq!(reps[0].to_pretty_string(), "Ok(None)");
+        assert_eq!(reps[1].to_pretty_string(), "Ok(Some(vec![]))");
+    }
+
+    #[test]
+    fn capped_result_replacements_keep_errors() {
+        let return_type = parse_quote! { -> Result<(u8, u8, u8, u8, u8, u8), Error> };
+        let error_exprs = ErrorExprs {
+            general: vec![parse_quote! { Error::Parse }, parse_quote! { Error::Io }],
+            ..Default::default()
+        };
+        let reps = return_type_replacements(&return_type, &error_exprs, ValueOptions::default())
+            .into_iter()
+            .map(|rep| rep.to_pretty_string())
+            .collect_vec();
+        assert_eq!(reps.len(), MAX_REPLACEMENTS);
+        assert_eq!(reps[0], "Ok((0, 0, 0, 0, 0, 0))");
+        assert_eq!(
+            reps[MAX_REPLACEMENTS - 2..],
+            ["Err(Error::Parse)", "Err(Error::Io)"]
+        );
+    }
+
+    #[test]
+    fn vec_u8_byte_string_replacement() {
+        let return_type = parse_quote! { -> Vec<u8> };
+        assert_eq!(
+            return_type_replacements(
+                &return_type,
+                &ErrorExprs::default(),
+                ValueOptions {
+                    byte_strings: true,
+                    ..Default::default()
+                }
+            )
+            .into_iter()
+            .map(|t| t.to_pretty_string())
+            .collect_vec(),
+            ["vec![]", "vec![0]", "vec![1]", "b\"xyzzy\".to_vec()"]
+        );
+        assert_eq!(
+            return_type_replacements(
+                &parse_quote! { -> Result<Vec<u8>, Error> },
+                &ErrorExprs::default(),
+                ValueOptions {
+                    byte_strings: true,
+                    ..Default::default()
+                }
+            )
+            .into_iter()
+            .map(|t| t.to_pretty_string())
+            .collect_vec(),
+            [
+                "Ok(vec![])",
+                "Ok(vec![0])",
+                "Ok(vec![1])",
+                "Ok(b\"xyzzy\".to_vec())"
+            ]
+        );
+        // Only for bytes, and only when enabled.
+        check_replacements(
+            &parse_quote! { -> Vec<u16> },
+            &[],
+            &["vec![]", "vec![0]", "vec![1]"],
+        );
+        check_replacements(&return_type, &[], &["vec![]", "vec![0]", "vec![1]"]);
+    }
+
+    #[test]
+    fn deeply_nested_containers_are_capped() {
+        check_replacements(
+            &parse_quote! { -> Mutex<Option<Box<Option<Box<Option<u8>>>>>> },
+            &[],
+            &[
+                "Mutex::new(None)",
+                "Mutex::new(Some(Box::new(None)))",
+                "Mutex::new(Some(Box::new(Some(Box::new(Default::default())))))",
+            ],
+        );
+    }
+
     #[test]
     fn box_usize_replacement() {
         check_replacements(
@@ -530,6 +1651,45 @@ mod test {
         );
     }
 
+    #[test]
+    fn box_str_replacement() {
+        check_replacements(
+            &parse_quote! { -> Box<str> },
+            &[],
+            &[r#"Box::from("")"#, r#"Box::from("xyzzy")"#],
+        );
+        check_replacements(
+            &parse_quote! { -> std::sync::Arc<str> },
+            &[],
+            &[
+                r#"std::sync::Arc::from("")"#,
+                r#"std::sync::Arc::from("xyzzy")"#,
+            ],
+        );
+    }
+
+    #[test]
+    fn box_slice_replacement() {
+        check_replacements(
+            &parse_quote! { -> Box<[u8]> },
+            &[],
+            &[
+                "Box::from(vec![])",
+                "Box::from(vec![0])",
+                "Box::from(vec![1])",
+            ],
+        );
+        check_replacements(
+            &parse_quote! { -> Rc<[String]> },
+            &[],
+            &[
+                "Rc::from(vec![])",
+                "Rc::from(vec![String::new()])",
+                r#"Rc::from(vec!["xyzzy".into()])"#,
+            ],
+        );
+    }
+
     #[test]
     fn box_unrecognized_type_replacement() {
         check_replacements(
@@ -539,6 +1699,50 @@ mod test {
         );
     }
 
+    #[test]
+    fn pin_box_future_replacement() {
+        check_replacements(
+            &parse_quote! { -> Pin<Box<dyn Future<Output = u8>>> },
+            &[],
+            &["Box::pin(async { 0 })", "Box::pin(async { 1 })"],
+        );
+        check_replacements(
+            &parse_quote! { -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> },
+            &[parse_quote! { String::new() }],
+            &[
+                "Box::pin(async { Ok(()) })",
+                "Box::pin(async { Err(String::new()) })",
+            ],
+        );
+        check_replacements(
+            &parse_quote! { -> futures::future::BoxFuture<'a, bool> },
+            &[],
+            &["Box::pin(async { true })", "Box::pin(async { false })"],
+        );
+        check_replacements(
+            &parse_quote! { -> impl Future<Output = u8> + Send },
+            &[],
+            &["async { 0 }", "async { 1 }"],
+        );
+        check_replacements(
+            &parse_quote! { -> Pin<Box<dyn Future<Output = ()>>> },
+            &[],
+            &["Box::pin(async {})"],
+        );
+    }
+
+    #[test]
+    fn pin_other_replacement() {
+        check_replacements(
+            &parse_quote! { -> Pin<Box<u8>> },
+            &[],
+            &["Box::pin(0)", "Box::pin(1)"],
+        );
+        // There's no way to make these, but at least don't generate unviable defaults.
+        check_replacements(&parse_quote! { -> Pin<Box<dyn Display>> }, &[], &[]);
+        check_replacements(&parse_quote! { -> Pin<&mut u8> }, &[], &[]);
+    }
+
     #[test]
     fn vec_string_replacement() {
         check_replacements(
@@ -558,6 +1762,53 @@ mod test {
         check_replacements(&parse_quote! { -> &bool }, &[], &["&true", "&false"]);
     }
 
+    #[test]
+    fn ref_to_temporary_is_leaked() {
+        check_replacements(
+            &parse_quote! { -> &Vec<u8> },
+            &[],
+            &[
+                "Box::leak(Box::new(vec![]))",
+                "Box::leak(Box::new(vec![0]))",
+                "Box::leak(Box::new(vec![1]))",
+            ],
+        );
+        check_replacements(
+            &parse_quote! { -> &HashMap<u32, bool> },
+            &[],
+            &[
+                "Box::leak(Box::new(HashMap::new()))",
+                "Box::leak(Box::new(HashMap::from_iter([(0, true)])))",
+                "Box::leak(Box::new(HashMap::from_iter([(0, false)])))",
+                "Box::leak(Box::new(HashMap::from_iter([(1, true)])))",
+                "Box::leak(Box::new(HashMap::from_iter([(1, false)])))",
+            ],
+        );
+    }
+
+    #[test]
+    fn ref_to_promotable_constant_is_borrowed() {
+        check_replacements(
+            &parse_quote! { -> &Option<i32> },
+            &[],
+            &["&None", "&Some(0)", "&Some(1)", "&Some(-1)"],
+        );
+        check_replacements(
+            &parse_quote! { -> &(u8, bool) },
+            &[],
+            &["&(0, true)", "&(0, false)", "&(1, true)", "&(1, false)"],
+        );
+        check_replacements(
+            &parse_quote! { -> &Option<String> },
+            &[],
+            &[
+                "&None",
+                "Box::leak(Box::new(Some(String::new())))",
+                r#"Box::leak(Box::new(Some("xyzzy".into())))"#,
+            ],
+        );
+    }
+
     #[test]
     fn array_replacement() {
         check_replacements(
@@ -569,23 +1820,29 @@ mod test {
 
     #[test]
     fn arc_replacement() {
-        // Also checks that it matches the path, even using an atypical path.
-        // TODO: Ideally this would be fully qualified like `alloc::sync::Arc::new(String::new())`.
+        // Also checks that it matches the path, even using an atypical path, and
+        // keeps the path qualified.
         check_replacements(
             &parse_quote! { -> alloc::sync::Arc<String> },
             &[],
-            &["Arc::new(String::new())", r#"Arc::new("xyzzy".into())"#],
+            &[
+                "alloc::sync::Arc::new(String::new())",
+                r#"alloc::sync::Arc::new("xyzzy".into())"#,
+            ],
         );
     }
 
     #[test]
     fn rc_replacement() {
-        // Also checks that it matches the path, even using an atypical path.
-        // TODO: Ideally this would be fully qualified like `alloc::sync::Rc::new(String::new())`.
+        // Also checks that it matches the path, even using an atypical path, and
+        // keeps the path qualified.
         check_replacements(
             &parse_quote! { -> alloc::sync::Rc<String> },
             &[],
-            &["Rc::new(String::new())", r#"Rc::new("xyzzy".into())"#],
+            &[
+                "alloc::sync::Rc::new(String::new())",
+                r#"alloc::sync::Rc::new("xyzzy".into())"#,
+            ],
         );
     }
 
@@ -679,6 +1936,28 @@ mod test {
         );
     }
 
+    #[test]
+    fn cow_slice_generates_borrowed_empty_and_owned_vec() {
+        check_replacements(
+            &parse_quote! { -> Cow<'static, [u8]> },
+            &[],
+            &[
+                "Cow::Borrowed(&[])",
+                "Cow::Owned(vec![0])",
+                "Cow::Owned(vec![1])",
+            ],
+        );
+        check_replacements(
+            &parse_quote! { -> std::borrow::Cow<'a, [String]> },
+            &[],
+            &[
+                "Cow::Borrowed(&[])",
+                "Cow::Owned(vec![String::new()])",
+                r#"Cow::Owned(vec!["xyzzy".into()])"#,
+            ],
+        );
+    }
+
     #[test]
     fn unknown_container_replacement() {
         // This looks like something that holds a &str, and maybe can be constructed
@@ -783,9 +2062,199 @@ mod test {
         );
     }
 
+    #[test]
+    fn result_of_map_replacement() {
+        // The Ok values come from the map rule, and the Err values from the error exprs.
+        check_replacements(
+            &parse_quote! { -> Result<HashMap<String, u32>, Error> },
+            &[parse_quote! { anyhow!("mutated") }],
+            &[
+                "Ok(HashMap::new())",
+                "Ok(HashMap::from_iter([(String::new(), 0)]))",
+                "Ok(HashMap::from_iter([(String::new(), 1)]))",
+                "Ok(HashMap::from_iter([(\"xyzzy\".into(), 0)]))",
+                "Ok(HashMap::from_iter([(\"xyzzy\".into(), 1)]))",
+                "Err(anyhow!(\"mutated\"))",
+            ],
+        );
+    }
+
+    #[test]
+    fn mut_ref_replacements_leak_by_default() {
+        check_replacements(
+            &parse_quote! { -> &mut u32 },
+            &[],
+            &["Box::leak(Box::new(0))", "Box::leak(Box::new(1))"],
+        );
+        check_replacements(
+            &parse_quote! { -> &mut [u8] },
+            &[],
+            &[
+                "Vec::leak(Vec::new())",
+                "Vec::leak(vec![0])",
+                "Vec::leak(vec![1])",
+            ],
+        );
+    }
+
+    #[test]
+    fn option_of_shared_ref_replacements_are_viable() {
+        // Promotable constants are borrowed; anything else is leaked rather than
+        // returning a reference to a temporary.
+        check_replacements(
+            &parse_quote! { -> Option<&u32> },
+            &[],
+            &["None", "Some(&0)", "Some(&1)"],
+        );
+        check_replacements(
+            &parse_quote! { -> Option<&Vec<u8>> },
+            &[],
+            &[
+                "None",
+                "Some(Box::leak(Box::new(vec![])))",
+                "Some(Box::leak(Box::new(vec![0])))",
+                "Some(Box::leak(Box::new(vec![1])))",
+            ],
+        );
+        check_replacements(
+            &parse_quote! { -> Option<&Option<String>> },
+            &[],
+            &[
+                "None",
+                "Some(&None)",
+                "Some(Box::leak(Box::new(Some(String::new()))))",
+                "Some(Box::leak(Box::new(Some(\"xyzzy\".into()))))",
+            ],
+        );
+        check_replacements(
+            &parse_quote! { -> Result<&String, Error> },
+            &[],
+            &[
+                "Ok(Box::leak(Box::new(String::new())))",
+                "Ok(Box::leak(Box::new(\"xyzzy\".into())))",
+            ],
+        );
+        // Unsized types can't be boxed from a default value.
+        check_replacements(
+            &parse_quote! { -> Option<&std::path::Path> },
+            &[],
+            &[
+                "None",
+                "Some(std::path::Path::new(\"\"))",
+                "Some(std::path::Path::new(\"xyzzy\"))",
+            ],
+        );
+        check_replacements(
+            &parse_quote! { -> &OsStr },
+            &[],
+            &["OsStr::new(\"\")", "OsStr::new(\"xyzzy\")"],
+        );
+        check_replacements(&parse_quote! { -> Option<&dyn Display> }, &[], &["None"]);
+        check_replacements(&parse_quote! { -> Option<&mut dyn Write> }, &[], &["None"]);
+    }
+
+    #[test]
+    fn no_mut_ref_replacements_without_leak_mut() {
+        let names = |return_type: ReturnType| {
+            return_type_replacements(
+                &return_type,
+                &ErrorExprs::default(),
+                ValueOptions {
+                    no_leak_mut: true,
+                    ..Default::default()
+                },
+            )
+            .into_iter()
+            .map(|t| t.to_pretty_string())
+            .collect_vec()
+        };
+        assert_eq!(names(parse_quote! { -> &mut u32 }), Vec::<String>::new());
+        assert_eq!(names(parse_quote! { -> &mut [u8] }), Vec::<String>::new());
+        assert_eq!(names(parse_quote! { -> Option<&mut u32> }), ["None"]);
+        assert_eq!(names(parse_quote! { -> &u32 }), ["&0", "&1"]);
+    }
+
+    #[test]
+    fn token_stream_replacement() {
+        check_replacements(
+            &parse_quote! { -> TokenStream },
+            &[],
+            &["TokenStream::new()"],
+        );
+        check_replacements(
+            &parse_quote! { -> proc_macro2::TokenStream },
+            &[],
+            &["proc_macro2::TokenStream::new()"],
+        );
+        check_replacements(
+            &parse_quote! { -> syn::Result<proc_macro::TokenStream> },
+            &[],
+            &["Ok(proc_macro::TokenStream::new())"],
+        );
+    }
+
+    #[test]
+    fn time_replacements() {
+        check_replacements(&parse_quote! { -> Instant }, &[], &["Instant::now()"]);
+        check_replacements(
+            &parse_quote! { -> std::time::Instant },
+            &[],
+            &["std::time::Instant::now()"],
+        );
+        check_replacements(
+            &parse_quote! { -> SystemTime },
+            &[],
+            &["SystemTime::now()", "SystemTime::UNIX_EPOCH"],
+        );
+        check_replacements(
+            &parse_quote! { -> Option<std::time::SystemTime> },
+            &[],
+            &[
+                "None",
+                "Some(std::time::SystemTime::now())",
+                "Some(std::time::SystemTime::UNIX_EPOCH)",
+            ],
+        );
+    }
+
+    #[test]
+    fn self_is_replaced_by_impl_type() {
+        let self_ty = parse_quote! { u32 };
+        check_replacements(
+            &replace_self_type(&parse_quote! { -> Self }, &self_ty),
+            &[],
+            &["0", "1"],
+        );
+        check_replacements(
+            &replace_self_type(&parse_quote! { -> Option<Self> }, &self_ty),
+            &[],
+            &["None", "Some(0)", "Some(1)"],
+        );
+        // `Self::Err` is an associated type, not the impl type.
+        check_replacements(
+            &replace_self_type(&parse_quote! { -> Result<Self, Self::Err> }, &self_ty),
+            &[],
+            &["Ok(0)", "Ok(1)"],
+        );
+    }
+
+    #[test]
+    fn self_of_unknown_type_is_default() {
+        check_replacements(
+            &replace_self_type(&parse_quote! { -> Self }, &parse_quote! { Builder }),
+            &[],
+            &["Default::default()"],
+        );
+    }
+
     fn check_replacements(return_type: &ReturnType, error_exprs: &[Expr], expected: &[&str]) {
+        let error_exprs = ErrorExprs {
+            general: error_exprs.to_vec(),
+            by_type: Vec::new(),
+            result_alias_error: None,
+        };
         assert_eq!(
-            return_type_replacements(return_type, error_exprs)
+            return_type_replacements(return_type, &error_exprs, ValueOptions::default())
                 .into_iter()
                 .map(|t| t.to_pretty_string())
                 .collect_vec(),
@@ -5,13 +5,14 @@
 
 use std::collections::HashMap;
 use std::iter::once;
+use std::process::Command;
 
-use anyhow::{anyhow, bail};
+use anyhow::{anyhow, bail, Context};
 use camino::Utf8Path;
 use indoc::formatdoc;
 use itertools::Itertools;
 use patch::{Line, Patch};
-use tracing::{info, trace, warn};
+use tracing::{debug, info, trace, warn};
 
 use crate::mutant::Mutant;
 use crate::source::SourceFile;
@@ -78,6 +79,43 @@ pub fn diff_filter(mutants: Vec<Mutant>, diff_text: &str) -> Result<Vec<Mutant>>
     Ok(matched)
 }
 
+/// Run `git diff` from a ref to the working tree, and return the text of the diff.
+///
+/// Because the diff is against the working tree, it includes any uncommitted changes
+/// to tracked files. Untracked files are not included.
+///
+/// Paths in the diff are relative to `dir`, which should be the workspace root, so that
+/// they match the paths of source files.
+///
+/// Refs starting with `-` are rejected, so that they can't be taken as options to git.
+pub fn git_diff_since(dir: &Utf8Path, git_ref: &str) -> Result<String> {
+    if git_ref.starts_with('-') {
+        bail!("Git ref for --since must not start with '-': {git_ref:?}");
+    }
+    let argv = [
+        "diff",
+        "--relative",
+        "--no-color",
+        "--no-ext-diff",
+        "--no-renames",
+        git_ref,
+        "--",
+    ];
+    debug!(?argv, %dir, "run git diff");
+    let output = Command::new("git")
+        .args(argv)
+        .current_dir(dir)
+        .output()
+        .context("Failed to run git diff")?;
+    if !output.status.success() {
+        bail!(
+            "git diff {git_ref:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    String::from_utf8(output.stdout).context("git diff output is not UTF-8")
+}
+
 /// Error if the new text from the diffs doesn't match the source files.
 fn check_diff_new_text_matches(patches: &[Patch], mutants: &[Mutant]) -> Result<()> {
     let mut source_by_name: HashMap<&Utf8Path, &SourceFile> = HashMap::new();
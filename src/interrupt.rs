@@ -17,10 +17,16 @@ pub fn install_handler() {
         .expect("install ctrl-c handler");
 }
 
+/// True if the user interrupted the program.
+#[mutants::skip] // Like `check_interrupted`, mutating this makes tests hang.
+pub fn was_interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
 /// Return an error if the program was interrupted and should exit.
 #[mutants::skip] // With this mutated too many of the tests will hang.
 pub fn check_interrupted() -> Result<()> {
-    if INTERRUPTED.load(Ordering::SeqCst) {
+    if was_interrupted() {
         error!("interrupted");
         Err(anyhow!("interrupted"))
     } else {
@@ -8,15 +8,25 @@
 use std::cmp::{max, min};
 use std::panic::resume_unwind;
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use std::{thread, vec};
 
 use itertools::Itertools;
 use tracing::{debug, debug_span, error, trace, warn};
 
 use crate::{
-    cargo::run_cargo, options::TestPackages, outcome::LabOutcome, output::OutputDir,
-    package::Package, package::PackageSelection, timeouts::Timeouts, workspace::Workspace,
+    cargo::run_cargo,
+    console::plural,
+    interrupt::check_interrupted,
+    options::{MutantOrder, TestPackages},
+    outcome::{LabOutcome, PhaseResult},
+    output::{OutputDir, ScenarioOutput},
+    package::Package,
+    package::PackageSelection,
+    process::{Exit, Process},
+    sarif::write_sarif,
+    timeouts::Timeouts,
+    workspace::Workspace,
     BaselineStrategy, BuildDir, Console, Context, Mutant, Options, Phase, Result, Scenario,
     ScenarioOutcome,
 };
@@ -37,11 +47,9 @@ pub fn test_mutants(
 ) -> Result<LabOutcome> {
     let start_time = Instant::now();
     console.set_debug_log(output_dir.open_debug_log()?);
-    if options.shuffle {
-        fastrand::shuffle(&mut mutants);
-    }
+    let chosen_seed = order_mutants(&mut mutants, options);
     output_dir.write_mutants_list(&mutants)?;
-    console.discovered_mutants(&mutants);
+    console.discovered_mutants(&mutants, chosen_seed, options);
     if mutants.is_empty() {
         warn!("No mutants found under the active filters");
         return Ok(LabOutcome::default());
@@ -62,6 +70,7 @@ pub fn test_mutants(
         output_mutex,
         jobserver,
         tests_for_mutant,
+        deadline: options.total_timeout.map(|total| start_time + total),
         options,
         console,
     };
@@ -86,21 +95,35 @@ pub fn test_mutants(
     };
     debug!(?timeouts);
 
-    let build_dir_0 = Mutex::new(Some(baseline_build_dir));
+    let n_threads = max(1, min(options.jobs.unwrap_or(1), mutants.len()));
+    let mut build_dirs = Vec::with_capacity(n_threads);
+    if options.copy_target && options.baseline == BaselineStrategy::Run {
+        // Start the other build dirs from the baseline's build products, so that
+        // they don't each need to build the whole tree from scratch.
+        for _ in 1..n_threads {
+            build_dirs.push(BuildDir::copy_from_baseline(
+                &baseline_build_dir,
+                workspace.root(),
+                options,
+                console,
+            )?);
+        }
+    }
+    build_dirs.push(baseline_build_dir);
+    let build_dirs = Mutex::new(build_dirs);
     // Create n threads, each dedicated to one build directory. Each of them tries to take a
     // scenario to test off the queue, and then exits when there are no more left.
     console.start_testing_mutants(mutants.len());
-    let n_threads = max(1, min(options.jobs.unwrap_or(1), mutants.len()));
     let work_queue = &Mutex::new(mutants.into_iter());
     thread::scope(|scope| -> crate::Result<()> {
         let mut threads = Vec::new();
         for _i_thread in 0..n_threads {
             threads.push(scope.spawn(|| -> crate::Result<()> {
                 trace!(thread_id = ?thread::current().id(), "start thread");
-                // First thread to start can use the baseline's build dir;
+                // Threads take the baseline's build dir and any copies made from it;
                 // others need to copy a new one
-                let build_dir_0 = build_dir_0.lock().expect("lock build dir 0").take(); // separate for lock
-                let build_dir = &if let Some(d) = build_dir_0 {
+                let prepared_dir = build_dirs.lock().expect("lock build dirs").pop(); // separate for lock
+                let build_dir = &if let Some(d) = prepared_dir {
                     d
                 } else {
                     BuildDir::copy_from(workspace.root(), options, console)?
@@ -111,10 +134,11 @@ pub fn test_mutants(
         join_threads(threads)
     })?;
 
-    let output_dir = lab
+    let mut output_dir = lab
         .output_mutex
         .into_inner()
         .expect("final unlock mutants queue");
+    record_unfinished(work_queue, &mut output_dir)?;
     console.lab_finished(&output_dir.lab_outcome, start_time, options);
     let lab_outcome = output_dir.take_lab_outcome();
     if lab_outcome.total_mutants == 0 {
@@ -124,9 +148,54 @@ pub fn test_mutants(
     } else if lab_outcome.unviable == lab_outcome.total_mutants {
         warn!("No mutants were viable: perhaps there is a problem with building in a scratch directory. Look in mutants.out/log/* for more information.");
     }
+    if let Some(sarif_path) = &options.sarif {
+        write_sarif(sarif_path, &lab_outcome, workspace.root())?;
+    }
     Ok(lab_outcome)
 }
 
+/// Record any mutants left in the queue because the total timeout was reached.
+fn record_unfinished(
+    work_queue: &Mutex<vec::IntoIter<Mutant>>,
+    output_dir: &mut OutputDir,
+) -> Result<()> {
+    let unfinished = work_queue
+        .lock()
+        .expect("lock work queue")
+        .as_slice()
+        .to_vec();
+    if !unfinished.is_empty() {
+        warn!(
+            "Total timeout reached: {} not tested",
+            plural(unfinished.len(), "mutant")
+        );
+        output_dir.add_unfinished(&unfinished)?;
+    }
+    Ok(())
+}
+
+/// Put mutants into the order in which they'll be tested.
+///
+/// This is done after sharding, so that every shard sees the same list.
+///
+/// Returns the seed for a random order if none was given, so that it can be shown.
+fn order_mutants(mutants: &mut [Mutant], options: &Options) -> Option<u64> {
+    match options.order {
+        MutantOrder::Source => None,
+        MutantOrder::Random => {
+            let seed = options.shuffle_seed.unwrap_or_else(|| fastrand::u64(..));
+            // Recorded in the debug log so that the order can be reproduced later.
+            debug!("Shuffling mutants with --shuffle-seed={seed}");
+            fastrand::Rng::with_seed(seed).shuffle(mutants);
+            options.shuffle_seed.is_none().then_some(seed)
+        }
+        MutantOrder::ByGenre => {
+            mutants.sort_by_key(|mutant| mutant.genre);
+            None
+        }
+    }
+}
+
 #[mutants::skip] // it's a little hard to observe that the threads were collected?
 fn join_threads(threads: Vec<thread::ScopedJoinHandle<'_, Result<()>>>) -> Result<()> {
     // The errors potentially returned from `join` are a special `std::thread::Result`
@@ -162,6 +231,8 @@ struct Lab<'a> {
     output_mutex: Mutex<OutputDir>,
     jobserver: Option<jobserver::Client>,
     tests_for_mutant: TestsForMutant,
+    /// Don't start testing any more mutants after this time.
+    deadline: Option<Instant>,
     options: &'a Options,
     console: &'a Console,
 }
@@ -205,6 +276,7 @@ impl Lab<'_> {
             output_mutex: &self.output_mutex,
             jobserver: self.jobserver.as_ref(),
             tests_for_mutant: &self.tests_for_mutant,
+            deadline: self.deadline,
             options: self.options,
             console: self.console,
         }
@@ -220,12 +292,13 @@ struct Worker<'a> {
     output_mutex: &'a Mutex<OutputDir>,
     jobserver: Option<&'a jobserver::Client>,
     tests_for_mutant: &'a TestsForMutant,
+    deadline: Option<Instant>,
     options: &'a Options,
     console: &'a Console,
 }
 
 impl Worker<'_> {
-    /// Run until the input queue is empty.
+    /// Run until the input queue is empty, or the deadline has passed.
     fn run_queue(
         mut self,
         work_queue: &Mutex<vec::IntoIter<Mutant>>,
@@ -233,6 +306,13 @@ impl Worker<'_> {
     ) -> Result<()> {
         let _span = debug_span!("worker thread", build_dir = ?self.build_dir.path()).entered();
         loop {
+            if self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+            {
+                debug!("Total timeout reached; not starting any more mutants");
+                return Ok(());
+            }
             // Not a `for` statement so that we don't hold the lock
             // for the whole iteration.
             let Some(mutant) = work_queue.lock().expect("Lock pending work queue").next() else {
@@ -246,6 +326,7 @@ impl Worker<'_> {
                 }
                 TestsForMutant::Explicit(packages) => PackageSelection::Explicit(packages.clone()),
             };
+            let timeouts = timeouts.for_genre(mutant.genre, self.options);
             self.run_one_scenario(&Scenario::Mutant(mutant), &test_packages, timeouts)?;
         }
     }
@@ -274,43 +355,17 @@ impl Worker<'_> {
         }
 
         let mut outcome = ScenarioOutcome::new(&scenario_output, scenario.clone());
-        for &phase in self.options.phases() {
-            self.console.scenario_phase_started(dir, phase);
-            let timeout = match phase {
-                Phase::Test => timeouts.test,
-                Phase::Build | Phase::Check => timeouts.build,
-            };
-            match run_cargo(
-                self.build_dir,
-                self.jobserver,
-                test_packages,
-                phase,
-                timeout,
-                &mut scenario_output,
-                self.options,
-                self.console,
-            ) {
-                Ok(phase_result) => {
-                    let success = phase_result.is_success(); // so we can move it away
-                    outcome.add_phase_result(phase_result);
-                    self.console.scenario_phase_finished(dir, phase);
-                    if !success {
-                        break;
-                    }
-                }
-                Err(err) => {
-                    error!(?err, ?phase, "scenario execution internal error");
-                    // Some unexpected internal error that stops the program.
-                    if let Some(mutant) = scenario.mutant() {
-                        mutant.revert(self.build_dir)?;
-                    }
-                    return Err(err);
-                }
-            }
-        }
+        let result = self.run_mutant_hooks_and_phases(
+            scenario,
+            test_packages,
+            timeouts,
+            &mut scenario_output,
+            &mut outcome,
+        );
         if let Some(mutant) = scenario.mutant() {
             mutant.revert(self.build_dir)?;
         }
+        result?;
         self.output_mutex
             .lock()
             .expect("lock output dir to add outcome")
@@ -321,6 +376,162 @@ impl Worker<'_> {
 
         Ok(outcome)
     }
+
+    /// Run the phases, preceded and followed by the `--pre-mutant-command` and
+    /// `--post-mutant-command`, if this is a mutant scenario.
+    ///
+    /// If the pre-mutant command fails, the mutant is not built or tested.
+    fn run_mutant_hooks_and_phases(
+        &mut self,
+        scenario: &Scenario,
+        test_packages: &PackageSelection,
+        timeouts: Timeouts,
+        scenario_output: &mut ScenarioOutput,
+        outcome: &mut ScenarioOutcome,
+    ) -> Result<()> {
+        let Some(mutant) = scenario.mutant() else {
+            return self.run_phases(scenario, test_packages, timeouts, scenario_output, outcome);
+        };
+        if let Some(command) = &self.options.pre_mutant_command {
+            let start = Instant::now();
+            let argv = shell_argv(command);
+            let process_status = self.run_hook(&argv, timeouts.build, scenario_output)?;
+            outcome.add_phase_result(PhaseResult {
+                phase: Phase::PreMutant,
+                duration: start.elapsed(),
+                process_status,
+                argv,
+            });
+            if !process_status.is_success() {
+                warn!(
+                    "Pre-mutant command failed for {}, so it was not tested",
+                    mutant.name(true)
+                );
+                return Ok(());
+            }
+        }
+        self.run_phases(scenario, test_packages, timeouts, scenario_output, outcome)?;
+        if let Some(command) = &self.options.post_mutant_command {
+            // The tests have already run, so a failure here doesn't change the outcome.
+            let process_status =
+                self.run_hook(&shell_argv(command), timeouts.build, scenario_output)?;
+            if !process_status.is_success() {
+                warn!(
+                    "Post-mutant command failed for {}: {process_status:?}",
+                    mutant.name(true)
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Run a pre- or post-mutant command in the build directory.
+    fn run_hook(
+        &self,
+        argv: &[String],
+        timeout: Option<Duration>,
+        scenario_output: &mut ScenarioOutput,
+    ) -> Result<Exit> {
+        let process_status = Process::run(
+            argv,
+            &[],
+            self.build_dir.path(),
+            timeout,
+            self.jobserver,
+            scenario_output,
+            self.console,
+        )?;
+        check_interrupted()?;
+        debug!(?process_status, "hook finished");
+        Ok(process_status)
+    }
+
+    /// Run each phase in turn until one fails, adding their results to the outcome.
+    ///
+    /// If the tests fail for a mutant, they're run up to `--retry` more times, in case the
+    /// failure was caused by a flaky test rather than the mutation.
+    fn run_phases(
+        &mut self,
+        scenario: &Scenario,
+        test_packages: &PackageSelection,
+        timeouts: Timeouts,
+        scenario_output: &mut ScenarioOutput,
+        outcome: &mut ScenarioOutcome,
+    ) -> Result<()> {
+        for &phase in self.options.phases() {
+            let timeout = match phase {
+                Phase::Test => timeouts.test,
+                Phase::PreMutant | Phase::Build | Phase::Check => timeouts.build,
+            };
+            let success =
+                self.run_phase(phase, timeout, test_packages, scenario_output, outcome)?;
+            if !success {
+                break;
+            }
+        }
+        if let Some(mutant) = scenario.mutant() {
+            for retry in 1..=self.options.retry {
+                if !outcome.mutant_caught() || outcome.has_timeout() {
+                    break;
+                }
+                debug!(retry, "Retrying tests for caught mutant");
+                if self.run_phase(
+                    Phase::Test,
+                    timeouts.test,
+                    test_packages,
+                    scenario_output,
+                    outcome,
+                )? {
+                    warn!(
+                        "{} was caught on the first run, but the tests passed on retry {retry}: \
+                        the tests may be flaky",
+                        mutant.name(true)
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Run one phase and add its result to the outcome, returning true if it succeeded.
+    fn run_phase(
+        &mut self,
+        phase: Phase,
+        timeout: Option<Duration>,
+        test_packages: &PackageSelection,
+        scenario_output: &mut ScenarioOutput,
+        outcome: &mut ScenarioOutcome,
+    ) -> Result<bool> {
+        let dir = self.build_dir.path();
+        self.console.scenario_phase_started(dir, phase);
+        let phase_result = run_cargo(
+            self.build_dir,
+            self.jobserver,
+            test_packages,
+            phase,
+            timeout,
+            scenario_output,
+            self.options,
+            self.console,
+        )
+        .inspect_err(|err| {
+            // Some unexpected internal error that stops the program.
+            error!(?err, ?phase, "scenario execution internal error");
+        })?;
+        let success = phase_result.is_success(); // so we can move it away
+        outcome.add_phase_result(phase_result);
+        self.console.scenario_phase_finished(dir, phase);
+        Ok(success)
+    }
+}
+
+/// The argv to run a command string through the platform's shell.
+fn shell_argv(command: &str) -> Vec<String> {
+    if cfg!(windows) {
+        vec!["cmd".to_owned(), "/C".to_owned(), command.to_owned()]
+    } else {
+        vec!["sh".to_owned(), "-c".to_owned(), command.to_owned()]
+    }
 }
 
 /// Which packages to test
@@ -345,3 +556,79 @@ impl TestsForMutant {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use indoc::indoc;
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::mutant::Genre;
+    use crate::visit::mutate_source_str;
+
+    fn mutants() -> Vec<Mutant> {
+        mutate_source_str(
+            indoc! {"
+                fn double(a: u32) -> u32 {
+                    a * 2
+                }
+
+                fn is_zero(a: u32) -> bool {
+                    a == 0
+                }
+            "},
+            &Options::default(),
+        )
+        .unwrap()
+    }
+
+    fn names(mutants: &[Mutant]) -> Vec<String> {
+        mutants.iter().map(|m| m.name(true)).collect_vec()
+    }
+
+    #[test]
+    fn order_by_genre_keeps_source_order_within_genre() {
+        let mut mutants = mutants();
+        order_mutants(
+            &mut mutants,
+            &Options {
+                order: MutantOrder::ByGenre,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            mutants.iter().map(|m| m.genre).dedup().collect_vec(),
+            [Genre::FnValue, Genre::BinaryOperator]
+        );
+        assert_eq!(
+            names(&mutants),
+            [
+                "src/main.rs:2:5: replace double -> u32 with 0",
+                "src/main.rs:2:5: replace double -> u32 with 1",
+                "src/main.rs:6:5: replace is_zero -> bool with true",
+                "src/main.rs:6:5: replace is_zero -> bool with false",
+                "src/main.rs:2:7: replace * with + in double",
+                "src/main.rs:2:7: replace * with / in double",
+                "src/main.rs:6:7: replace == with != in is_zero",
+            ]
+        );
+    }
+
+    #[test]
+    fn random_order_is_reproducible_with_seed() {
+        let options = Options {
+            order: MutantOrder::Random,
+            shuffle_seed: Some(1234),
+            ..Default::default()
+        };
+        let mut first = mutants();
+        order_mutants(&mut first, &options);
+        let mut second = mutants();
+        order_mutants(&mut second, &options);
+        assert_eq!(names(&first), names(&second));
+        assert_eq!(
+            names(&first).into_iter().sorted().collect_vec(),
+            names(&mutants()).into_iter().sorted().collect_vec()
+        );
+    }
+}
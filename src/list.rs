@@ -5,19 +5,31 @@
 #![warn(clippy::pedantic)]
 #![allow(clippy::module_name_repetitions)]
 
+use std::fmt::Write;
+use std::fs::read_to_string;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use camino::Utf8Path;
 use itertools::Itertools;
 use serde_json::{json, Value};
+use strum::IntoEnumIterator;
 
-use crate::mutant::Mutant;
+use crate::console::plural;
+use crate::mutant::{Genre, Mutant};
 use crate::path::Utf8PathSlashes;
 use crate::source::SourceFile;
-use crate::Options;
+use crate::visit::Discovered;
+use crate::{Options, Result};
 
 /// Return a string representation of a list of mutants.
 ///
-/// The format is controlled by the `emit_json`, `emit_diffs`, `show_line_col`, and `colors` options.
+/// The format is controlled by the `emit_json`, `emit_markdown`, `emit_diffs`, `list_context`,
+/// `show_line_col`, and `colors` options.
 pub fn list_mutants(mutants: &[Mutant], options: &Options) -> String {
-    if options.emit_json {
+    if options.emit_markdown {
+        list_mutants_markdown(mutants)
+    } else if options.emit_json {
         // Panic: only if we created illegal json, which would be a bug.
         let mut list: Vec<serde_json::Value> = Vec::new();
         for mutant in mutants {
@@ -28,6 +40,11 @@ pub fn list_mutants(mutants: &[Mutant], options: &Options) -> String {
                     json!(mutant.diff(&mutant.mutated_code())),
                 );
             }
+            if let Some(radius) = options.list_context {
+                obj.as_object_mut()
+                    .unwrap()
+                    .insert("context".to_owned(), json!(mutant.source_context(radius)));
+            }
             list.push(obj);
         }
         serde_json::to_string_pretty(&list).expect("Serialize mutants")
@@ -43,6 +60,9 @@ pub fn list_mutants(mutants: &[Mutant], options: &Options) -> String {
                 out.push_str(&mutant.name(options.show_line_col));
             }
             out.push('\n');
+            if let Some(radius) = options.list_context {
+                out.push_str(&mutant.source_context(radius));
+            }
             if options.emit_diffs {
                 out.push_str(&mutant.diff(&mutant.mutated_code()));
                 out.push('\n');
@@ -52,6 +72,42 @@ pub fn list_mutants(mutants: &[Mutant], options: &Options) -> String {
     }
 }
 
+/// Return a Markdown table of mutants, for pasting into bug reports and PR descriptions.
+fn list_mutants_markdown(mutants: &[Mutant]) -> String {
+    let mut out = String::with_capacity(100 * (mutants.len() + 2));
+    out.push_str("| File | Line | Function | Genre | Replacement |\n");
+    out.push_str("| ---- | ---: | -------- | ----- | ----------- |\n");
+    for mutant in mutants {
+        let function = mutant
+            .function
+            .as_ref()
+            .map(|f| markdown_code(&f.function_name))
+            .unwrap_or_default();
+        writeln!(
+            out,
+            "| {file} | {line} | {function} | {genre:?} | {replacement} |",
+            file = markdown_code(&mutant.source_file.tree_relative_slashes()),
+            line = mutant.span.start.line,
+            genre = mutant.genre,
+            replacement = markdown_code(mutant.replacement_text()),
+        )
+        .unwrap();
+    }
+    out
+}
+
+/// Format a string as inline code within a Markdown table cell.
+///
+/// Pipes are escaped so that they don't end the cell, and newlines are collapsed so that they
+/// don't end the row. Empty strings produce an empty cell.
+fn markdown_code(s: &str) -> String {
+    if s.is_empty() {
+        String::new()
+    } else {
+        format!("`{}`", s.replace('\n', " ").replace('|', "\\|"))
+    }
+}
+
 /// List the source files as json or text.
 pub fn list_files(source_files: &[SourceFile], options: &Options) -> String {
     if options.emit_json {
@@ -74,3 +130,131 @@ pub fn list_files(source_files: &[SourceFile], options: &Options) -> String {
             .join("")
     }
 }
+
+/// List the genres of mutation as json or text.
+///
+/// Each text line has the genre name, `default` or `optional`, and a description,
+/// separated by tabs.
+pub fn list_genres(emit_json: bool) -> String {
+    if emit_json {
+        let json_list = Value::Array(
+            Genre::iter()
+                .map(|genre| {
+                    json!({
+                        "genre": genre,
+                        "description": genre.description(),
+                        "default": genre.enabled_by_default(),
+                    })
+                })
+                .collect(),
+        );
+        serde_json::to_string_pretty(&json_list).expect("Serialize genres")
+    } else {
+        Genre::iter()
+            .map(|genre| {
+                format!(
+                    "{genre:?}\t{}\t{}\n",
+                    if genre.enabled_by_default() {
+                        "default"
+                    } else {
+                        "optional"
+                    },
+                    genre.description()
+                )
+            })
+            .join("")
+    }
+}
+
+/// Count mutants in total, per file, and per genre, for `--list --count`, as json or text.
+///
+/// Files and genres are listed with the most mutants first, so that any file generating
+/// an unexpectedly large number stands out. Only files that have some of these mutants are
+/// counted.
+pub fn list_counts(mutants: &[Mutant], options: &Options) -> String {
+    let by_file = mutants
+        .iter()
+        .map(|mutant| mutant.source_file.tree_relative_slashes())
+        .counts()
+        .into_iter()
+        .sorted_by(|(a_name, a_count), (b_name, b_count)| {
+            b_count.cmp(a_count).then(a_name.cmp(b_name))
+        })
+        .collect_vec();
+    let by_genre = mutants
+        .iter()
+        .map(|mutant| mutant.genre)
+        .counts()
+        .into_iter()
+        .sorted_by(|(a_genre, a_count), (b_genre, b_count)| {
+            b_count.cmp(a_count).then(a_genre.cmp(b_genre))
+        })
+        .collect_vec();
+    if options.emit_json {
+        let json = json!({
+            "total_mutants": mutants.len(),
+            "total_files": by_file.len(),
+            "files": by_file.iter().map(|(file, count)| json!({"file": file, "count": count})).collect_vec(),
+            "genres": by_genre.iter().map(|(genre, count)| json!({"genre": genre, "count": count})).collect_vec(),
+        });
+        serde_json::to_string_pretty(&json).expect("Serialize counts") + "\n"
+    } else {
+        let mut s = format!(
+            "{} in {}\n",
+            plural(mutants.len(), "mutant"),
+            plural(by_file.len(), "source file")
+        );
+        s.push_str("\nBy file:\n");
+        for (file, count) in &by_file {
+            writeln!(s, "{count:>8}  {file}").unwrap();
+        }
+        s.push_str("\nBy genre:\n");
+        for (genre, count) in &by_genre {
+            writeln!(s, "{count:>8}  {genre:?}").unwrap();
+        }
+        s
+    }
+}
+
+/// Summarize what was found by `--discovery-only`, and how long it took unless times are
+/// turned off.
+pub fn discovery_summary(discovered: &Discovered, elapsed: Duration, options: &Options) -> String {
+    let mut s = format!(
+        "Found {} in {}",
+        plural(discovered.mutants.len(), "mutant"),
+        plural(discovered.files.len(), "source file"),
+    );
+    if options.show_times {
+        write!(s, " in {:.3}s", elapsed.as_secs_f64()).unwrap();
+    }
+    s.push('\n');
+    s
+}
+
+/// Read the names of mutants from a file, for `--mutants-from-file`.
+///
+/// The file can either have one name per line, as in `missed.txt` or the output of `--list`,
+/// or be a JSON array of mutants with `name` fields, as written by `--list --json`.
+pub fn read_mutant_names(path: &Utf8Path) -> Result<Vec<String>> {
+    let content =
+        read_to_string(path).with_context(|| format!("Failed to read mutants from {path:?}"))?;
+    if content.trim_start().starts_with('[') {
+        let json: Vec<Value> = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse mutants json from {path:?}"))?;
+        json.iter()
+            .map(|mutant| {
+                mutant["name"]
+                    .as_str()
+                    .map(ToOwned::to_owned)
+                    .ok_or_else(|| anyhow!("Mutant in {path:?} has no name: {mutant}"))
+            })
+            .collect()
+    } else {
+        Ok(content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(ToOwned::to_owned)
+            .collect())
+    }
+}
@@ -5,14 +5,23 @@
 //! See <https://mutants.rs> for the manual and more information.
 
 #![warn(clippy::pedantic)]
-#![allow(clippy::module_name_repetitions, clippy::needless_raw_string_hashes)]
+#![allow(
+    clippy::format_push_string,
+    clippy::module_name_repetitions,
+    clippy::needless_raw_string_hashes,
+    clippy::unnecessary_debug_formatting
+)]
 
 mod build_dir;
 mod cargo;
+mod cfg;
 mod config;
 mod console;
 mod copy_tree;
+mod discovery_cache;
+mod exclude_range;
 mod exit_code;
+mod filter_script;
 mod fnvalue;
 mod glob;
 mod in_diff;
@@ -28,6 +37,8 @@ mod package;
 mod path;
 mod pretty;
 mod process;
+mod result_format;
+mod sarif;
 mod scenario;
 mod shard;
 mod source;
@@ -40,10 +51,12 @@ mod timeouts;
 mod visit;
 mod workspace;
 
+use std::collections::HashSet;
 use std::env;
 use std::fs::read_to_string;
 use std::io;
 use std::process::exit;
+use std::time::Instant;
 
 use anyhow::{anyhow, ensure, Context, Result};
 use camino::{Utf8Path, Utf8PathBuf};
@@ -53,18 +66,24 @@ use clap::{ArgAction, CommandFactory, Parser, ValueEnum};
 use clap_complete::{generate, Shell};
 use color_print::cstr;
 use console::enable_console_colors;
-use output::{load_previously_caught, OutputDir};
+use output::{load_previously_caught, write_patches, OutputDir};
 use tracing::{debug, info};
 
 use crate::build_dir::BuildDir;
 use crate::console::Console;
-use crate::in_diff::diff_filter;
+use crate::exclude_range::ExcludeRange;
+use crate::filter_script::retain_by_filter_script;
+use crate::fnvalue::explain_type_str;
+use crate::in_diff::{diff_filter, git_diff_since};
 use crate::interrupt::check_interrupted;
 use crate::lab::test_mutants;
-use crate::list::{list_files, list_mutants};
+use crate::list::{
+    discovery_summary, list_counts, list_files, list_genres, list_mutants, read_mutant_names,
+};
 use crate::mutant::{Genre, Mutant};
-use crate::options::{Colors, Options, TestTool};
+use crate::options::{Colors, MutantOrder, Options, TestTool};
 use crate::outcome::{Phase, ScenarioOutcome};
+use crate::result_format::ResultFormat;
 use crate::scenario::Scenario;
 use crate::shard::Shard;
 use crate::workspace::{PackageFilter, Workspace};
@@ -77,6 +96,22 @@ static MUTATION_MARKER_COMMENT: &str = "/* ~ changed by cargo-mutants ~ */";
 
 static SPONSOR_MESSAGE: &str = cstr!("<magenta><bold>Support and accelerate cargo-mutants at <<https://github.com/sponsors/sourcefrog>></></>");
 
+/// Shown at the end of `--help`: this should match the values in [`exit_code`].
+static EXIT_CODES_HELP: &str = cstr!(
+    "<green><bold>Exit codes:</></>
+  0    Success: every viable mutant that was tested was caught
+  1    Usage or setup error: bad arguments, an invalid config file, or a tree that can't be read
+  2    Some mutants were missed by the tests
+  3    Some tests timed out
+  4    The tests already fail or hang in the unmutated baseline tree, so no mutants were tested
+  5    The --total-timeout was reached before all mutants were tested
+  70   An error while testing mutants, such as failing to copy the tree or run cargo
+  101  cargo-mutants panicked: please report a bug
+  130  The run was interrupted, for example by Ctrl-C
+
+<magenta><bold>Support and accelerate cargo-mutants at <<https://github.com/sponsors/sourcefrog>></></>"
+);
+
 #[mutants::skip] // only visual effects, not worth testing
 fn clap_styles() -> Styles {
     styling::Styles::styled()
@@ -112,6 +147,7 @@ pub enum BaselineStrategy {
     author,
     about,
     after_help = SPONSOR_MESSAGE,
+    after_long_help = EXIT_CODES_HELP,
 )]
 pub struct Args {
     /// Show cargo output for all invocations (very verbose).
@@ -122,6 +158,10 @@ pub struct Args {
     #[arg(long, value_enum, default_value_t = BaselineStrategy::Run, help_heading = "Execution")]
     baseline: BaselineStrategy,
 
+    /// Also replace `Vec<u8>` return values with a non-empty byte string.
+    #[arg(long, help_heading = "Generate")]
+    byte_strings: bool,
+
     /// Turn off all rustc lints, so that denied warnings won't make mutants unviable.
     #[arg(long, action = ArgAction::Set, help_heading = "Build")]
     cap_lints: Option<bool>,
@@ -130,6 +170,10 @@ pub struct Args {
     #[arg(long, short = 'v', help_heading = "Output")]
     caught: bool,
 
+    /// Print results only for mutants that were caught by tests.
+    #[arg(long, help_heading = "Output", conflicts_with = "missed_only")]
+    caught_only: bool,
+
     /// Cargo check generated mutants, but don't run tests.
     #[arg(long, help_heading = "Execution")]
     check: bool,
@@ -144,6 +188,11 @@ pub struct Args {
     )]
     colors: Colors,
 
+    /// Start each additional build directory from a copy of the baseline's `target`
+    /// directory, so that it only needs an incremental build.
+    #[arg(long, help_heading = "Copying")]
+    copy_target: Option<bool>,
+
     /// Copy `.git` and other VCS directories to the build directory.
     ///
     /// This is useful if you have tests that depend on the presence of these directories.
@@ -153,10 +202,37 @@ pub struct Args {
     #[arg(long, help_heading = "Copying", visible_alias = "copy_git")]
     copy_vcs: Option<bool>,
 
+    /// With --list, print only the number of mutants: in total, per file, and per genre.
+    #[arg(
+        long,
+        requires = "list",
+        conflicts_with = "markdown",
+        help_heading = "Execution"
+    )]
+    count: bool,
+
+    /// With --list, show this many lines of source before and after each mutant, with the replacement applied.
+    #[arg(
+        long,
+        value_name = "N",
+        requires = "list",
+        conflicts_with_all = ["markdown", "count"],
+        help_heading = "Execution"
+    )]
+    context: Option<usize>,
+
     /// Show the mutation diffs.
     #[arg(long, help_heading = "Filters")]
     diff: bool,
 
+    /// Cache the mutants discovered in each source file in `mutants.out.cache`, and reuse them on later runs if the file is unchanged.
+    #[arg(long, help_heading = "Execution")]
+    discovery_cache: bool,
+
+    /// Only find mutants, without building or testing, and report how many were found and how long it took.
+    #[arg(long, help_heading = "Execution")]
+    discovery_only: bool,
+
     /// Rust crate directory to examine.
     #[arg(
         long,
@@ -166,15 +242,27 @@ pub struct Args {
     )]
     dir: Option<Utf8PathBuf>,
 
+    /// Read configuration from this file, instead of .cargo/mutants.toml in the tree.
+    #[arg(long, help_heading = "Input", conflicts_with = "no_config")]
+    config: Option<Utf8PathBuf>,
+
     /// Generate autocompletions for the given shell.
     #[arg(long)]
     completions: Option<Shell>,
 
+    /// Also replace float return values with NaN and the infinities.
+    #[arg(long, help_heading = "Generate")]
+    float_specials: bool,
+
     /// Return this error values from functions returning Result:
     /// for example, `::anyhow::anyhow!("mutated")`.
     #[arg(long, help_heading = "Generate")]
     error: Vec<String>,
 
+    /// Write a patch for each mutant into this directory, instead of testing them.
+    #[arg(long, help_heading = "Output")]
+    emit_patches: Option<Utf8PathBuf>,
+
     /// Regex for mutations to examine, matched against the names shown by `--list`.
     #[arg(
         long = "re",
@@ -191,15 +279,43 @@ pub struct Args {
     #[arg(long, short = 'e', help_heading = "Filters")]
     exclude: Vec<String>,
 
+    /// Exclude mutants overlapping a range of lines in a file, like `src/parser.rs:100-200`.
+    #[arg(long, help_heading = "Filters")]
+    exclude_range: Vec<ExcludeRange>,
+
     /// Regex for mutations to exclude, matched against the names shown by `--list`.
     #[arg(long, short = 'E', alias = "exclude-regex", help_heading = "Filters")]
     exclude_re: Vec<String>,
 
+    /// Print the rule and replacement values that would be generated for a return type, like `Result<Vec<u8>, Error>`, and exit.
+    #[arg(long, help_heading = "Generate")]
+    explain_type: Option<String>,
+
+    /// Also mutate the source of example targets, such as `examples/*.rs`.
+    #[arg(long, help_heading = "Filters")]
+    examine_examples: bool,
+
+    /// Only mutate the files listed in this file, one tree-relative path per line; lines starting with `#` are comments.
+    #[arg(long, help_heading = "Filters")]
+    examine_from_file: Option<Utf8PathBuf>,
+
     /// Glob for files to examine; with no glob, all files are examined; globs containing
     /// slash match the entire path. If used together with `--exclude` argument, then the files to be examined are matched before the files to be excluded.
     #[arg(long, short = 'f', help_heading = "Filters")]
     file: Vec<String>,
 
+    /// Program run for each mutant with its JSON description on stdin, which exits 0 to keep the mutant or 1 to skip it.
+    #[arg(long, help_heading = "Filters")]
+    filter_script: Option<Utf8PathBuf>,
+
+    /// Test only mutants in the function with exactly this name, like `Counter::get` or `outer::inner::name`, as shown in `--list` output; may be repeated.
+    #[arg(long, value_name = "NAME", help_heading = "Filters")]
+    function: Vec<String>,
+
+    /// Print each mutant's result using this template, with placeholders {file}, {line}, {function}, {genre}, {replacement}, and {outcome}.
+    #[arg(long, help_heading = "Output")]
+    format: Option<ResultFormat>,
+
     /// Don't copy files matching gitignore patterns.
     #[arg(long, action = ArgAction::Set, default_value = "true", help_heading = "Copying", group = "copy_opts")]
     gitignore: bool,
@@ -236,12 +352,12 @@ pub struct Args {
     #[arg(long, help_heading = "Execution")]
     jobserver_tasks: Option<usize>,
 
-    /// Output json (only for --list).
+    /// Output json (only for --list, --list-files, and --list-genres).
     #[arg(long, help_heading = "Output")]
     json: bool,
 
     /// Don't delete the scratch directories, for debugging.
-    #[arg(long, help_heading = "Debug")]
+    #[arg(long, help_heading = "Debug", visible_alias = "leak-scratch")]
     leak_dirs: bool,
 
     /// Log level for stdout (trace, debug, info, warn, error).
@@ -262,18 +378,35 @@ pub struct Args {
     #[arg(long, help_heading = "Execution")]
     list_files: bool,
 
+    /// List the genres of mutation, with descriptions and whether they're enabled by default.
+    #[arg(long, help_heading = "Execution")]
+    list_genres: bool,
+
+    /// Stop discovering mutants after this many source files have produced some, for
+    /// quick experiments.
+    #[arg(long, help_heading = "Filters")]
+    max_files: Option<usize>,
+
     /// Path to Cargo.toml for the package to mutate.
     #[arg(long, help_heading = "Input")]
     manifest_path: Option<Utf8PathBuf>,
 
+    /// Output a Markdown table (only for --list).
+    #[arg(long, help_heading = "Output", conflicts_with = "json")]
+    markdown: bool,
+
     /// Don't read .cargo/mutants.toml.
     #[arg(long, help_heading = "Input")]
     no_config: bool,
 
-    /// Don't copy the /target directory, and don't build the source tree first.
+    /// Deprecated: use `--copy-target=false`.
     #[arg(long, help_heading = "Copying", group = "copy_opts")]
     no_copy_target: bool,
 
+    /// Don't generate values for functions returning `&mut`, which leak memory.
+    #[arg(long, help_heading = "Generate")]
+    no_leak_mut: bool,
+
     /// Don't print times or tree sizes, to make output deterministic.
     #[arg(long, help_heading = "Output")]
     no_times: bool,
@@ -295,34 +428,116 @@ pub struct Args {
     #[arg(long, short = 'D', help_heading = "Filters")]
     in_diff: Option<Utf8PathBuf>,
 
-    /// Minimum timeout for tests, in seconds, as a lower bound on the auto-set time.
+    /// Minimum timeout for tests, in seconds or as a duration like "20s", as a lower bound on the auto-set time.
     #[arg(
         long,
         env = "CARGO_MUTANTS_MINIMUM_TEST_TIMEOUT",
+        value_parser = parse_seconds,
         help_heading = "Execution"
     )]
     minimum_test_timeout: Option<f64>,
 
+    /// Print results only for mutants that were missed by tests.
+    #[arg(long, help_heading = "Output")]
+    missed_only: bool,
+
+    /// Test only the mutants named in this file, one per line as in `missed.txt`, or as the output of `--list --json`.
+    #[arg(long, help_heading = "Filters")]
+    mutants_from_file: Option<Utf8PathBuf>,
+
     /// Only test mutants from these packages.
     #[arg(id = "package", long, short = 'p', help_heading = "Filters")]
     mutate_packages: Vec<String>,
 
-    /// Run mutants in random order.
+    /// Skip functions with fewer than this many statements.
+    #[arg(long, help_heading = "Filters")]
+    min_statements: Option<usize>,
+
+    /// Count each arm of a `match` as a statement for --min-statements.
+    #[arg(long, help_heading = "Filters")]
+    count_match_arms: bool,
+
+    /// Generate off-by-one mutants of literal const generic defaults, like `const N: usize = 8`.
+    #[arg(long, help_heading = "Generate")]
+    mutate_const_defaults: bool,
+
+    /// Replace reads of `self.field` with other values, for fields of simple types.
+    #[arg(long, help_heading = "Generate")]
+    mutate_field_reads: bool,
+
+    /// Also mutate `unsafe fn`s, which may produce mutants with undefined behavior.
+    #[arg(long, help_heading = "Filters")]
+    mutate_unsafe: bool,
+
+    /// Delete statements that call `dbg!`, `println!`, or `eprintln!`, to find debug output that tests don't depend on.
+    #[arg(long, help_heading = "Generate")]
+    remove_debug_output: bool,
+
+    /// Replace the only use of each function parameter with `Default::default()`.
+    #[arg(long, help_heading = "Generate")]
+    replace_args_with_default: bool,
+
+    /// Run the tests for a caught mutant up to this many more times, and report it as missed if they ever pass, in case they're flaky.
     #[arg(long, help_heading = "Execution")]
-    shuffle: bool,
+    retry: Option<usize>,
+
+    /// Run this shell command in the build directory before building each mutant; if it fails, the mutant is reported as failed.
+    #[arg(long, help_heading = "Execution")]
+    pre_mutant_command: Option<String>,
 
-    /// Run mutants in the fixed order they occur in the source tree.
+    /// Run this shell command in the build directory after testing each mutant.
     #[arg(long, help_heading = "Execution")]
+    post_mutant_command: Option<String>,
+
+    /// Only mutate functions declared `pub` or `pub(...)`, and methods of trait impls.
+    #[arg(long, help_heading = "Filters")]
+    pub_only: bool,
+
+    /// Print only missed mutants, problems, and the final summary.
+    #[arg(long, short = 'q', help_heading = "Output", conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Run mutants in random order: the same as `--order=random`.
+    #[arg(long, help_heading = "Execution", conflicts_with = "order")]
+    shuffle: bool,
+
+    /// Run mutants in the fixed order they occur in the source tree: the same as `--order=source`.
+    #[arg(long, help_heading = "Execution", conflicts_with = "order")]
     no_shuffle: bool,
 
+    /// Order in which to test mutants; the default is random.
+    #[arg(long, value_enum, help_heading = "Execution")]
+    order: Option<MutantOrder>,
+
+    /// Seed for the random order, to reproduce the order of an earlier run.
+    #[arg(long, help_heading = "Execution")]
+    shuffle_seed: Option<u64>,
+
     /// Build with this cargo profile.
     #[arg(long, help_heading = "Build")]
     profile: Option<String>,
 
+    /// Build and test with this rustup toolchain, by running `cargo +TOOLCHAIN`.
+    #[arg(long, help_heading = "Build")]
+    toolchain: Option<String>,
+
+    /// Write missed mutants to this file as a SARIF 2.1.0 report, for code-scanning tools.
+    #[arg(long, help_heading = "Output")]
+    sarif: Option<Utf8PathBuf>,
+
+    /// Create scratch build directories inside this directory, rather than the system
+    /// temporary directory.
+    #[arg(long, help_heading = "Copying", conflicts_with = "in_place")]
+    scratch_dir: Option<Utf8PathBuf>,
+
     /// Run only one shard of all generated mutants: specify as e.g. 1/4.
     #[arg(long, help_heading = "Execution")]
     shard: Option<Shard>,
 
+    /// Include only mutants in code changed since this git ref, including uncommitted changes.
+    #[arg(long, help_heading = "Filters", conflicts_with = "in_diff")]
+    since: Option<String>,
+
     /// Skip calls to functions and methods named in this list.
     ///
     /// The list may contain comma-separated names and may be repeated.
@@ -340,6 +555,30 @@ pub struct Args {
     #[arg(long)]
     skip_calls_defaults: Option<bool>,
 
+    /// Don't mutate impls of traits with these names, like `Debug` or `fmt::Debug`, as a comma-separated list.
+    ///
+    /// This value is combined with the names from the config `skip_trait_impls` key.
+    #[arg(long, help_heading = "Filters")]
+    skip_trait_impls: Vec<String>,
+
+    /// Use built-in defaults for `skip_trait_impls`, in addition to any explicit values.
+    ///
+    /// The defaults are `Debug`, `Display`, and `Hash`.
+    #[arg(long)]
+    skip_trait_impls_defaults: Option<bool>,
+
+    /// Don't mutate trait methods with these names, like `fmt`, in any trait, as a comma-separated list.
+    ///
+    /// This value is combined with the names from the config `skip_trait_methods` key.
+    #[arg(long, help_heading = "Filters")]
+    skip_trait_methods: Vec<String>,
+
+    /// Use built-in defaults for `skip_trait_methods`, in addition to any explicit values.
+    ///
+    /// The defaults are `fmt`, `eq`, `partial_cmp`, and `hash`.
+    #[arg(long)]
+    skip_trait_methods_defaults: Option<bool>,
+
     /// Run tests from these packages for all mutants.
     #[arg(long, help_heading = "Tests")]
     test_package: Vec<String>,
@@ -364,6 +603,14 @@ pub struct Args {
     #[arg(long, help_heading = "Execution", conflicts_with = "timeout")]
     timeout_multiplier: Option<f64>,
 
+    /// Test timeout for mutants of one genre, in seconds: for example `BinaryOperator=10`.
+    #[arg(long, help_heading = "Execution")]
+    timeout_per_genre: Vec<String>,
+
+    /// Stop starting new mutants after this much time in total, like `20m` or `1h30m`.
+    #[arg(long, help_heading = "Execution")]
+    total_timeout: Option<humantime::Duration>,
+
     /// Maximum run time for cargo build command, in seconds.
     #[arg(long, help_heading = "Execution")]
     build_timeout: Option<f64>,
@@ -372,10 +619,26 @@ pub struct Args {
     #[arg(long, help_heading = "Execution", conflicts_with = "build_timeout")]
     build_timeout_multiplier: Option<f64>,
 
+    /// Also replace values of unrecognized types with `"0".parse().unwrap()` and `"1".parse().unwrap()`.
+    #[arg(long, help_heading = "Generate")]
+    try_fromstr: bool,
+
+    /// Also replace the bodies of functions returning `()` with `panic!()`.
+    #[arg(long, help_heading = "Generate")]
+    unit_panic: bool,
+
+    /// Also replace the bodies of functions returning `Result<(), E>` with `panic!()`.
+    #[arg(long, help_heading = "Generate")]
+    panic_mutants: bool,
+
     /// Print mutations that failed to check or build.
     #[arg(long, short = 'V', help_heading = "Output")]
     unviable: bool,
 
+    /// Print the commands run for each mutant; repeat to also print all their output.
+    #[arg(long, action = ArgAction::Count, help_heading = "Output")]
+    verbose: u8,
+
     /// Show version and quit.
     #[arg(long, action = clap::ArgAction::SetTrue)]
     version: bool,
@@ -395,6 +658,7 @@ pub struct Args {
 
     /// Pass remaining arguments to cargo test after all options and after `--`.
     #[arg(last = true, help_heading = "Execution")]
+    #[allow(clippy::struct_field_names)]
     cargo_test_args: Vec<String>,
 
     #[command(flatten)]
@@ -420,6 +684,72 @@ pub struct Features {
     pub all_features: bool,
 }
 
+/// Parse a number of seconds, either as a plain number like "20" or "2.5",
+/// or as a human-readable duration like "20s" or "1m".
+fn parse_seconds(s: &str) -> Result<f64, String> {
+    if let Ok(secs) = s.parse::<f64>() {
+        Ok(secs)
+    } else {
+        s.parse::<humantime::Duration>()
+            .map(|d| d.as_secs_f64())
+            .map_err(|err| format!("Invalid duration {s:?}: {err}"))
+    }
+}
+
+/// The level of trace messages shown on the console.
+///
+/// Quiet mode hides info messages, unless some other level was chosen.
+fn console_trace_level(args: &Args) -> tracing::Level {
+    if args.quiet && args.level == tracing::Level::INFO {
+        tracing::Level::WARN
+    } else {
+        args.level
+    }
+}
+
+/// Read the config file named on the command line, or in the tree, merged over any
+/// `[package.metadata.mutants]` in `Cargo.toml`, unless turned off.
+fn load_config(args: &Args, workspace: &Workspace) -> Result<config::Config> {
+    if args.no_config {
+        return Ok(config::Config::default());
+    }
+    let config_path = if let Some(config_path) = &args.config {
+        Some(config_path.clone())
+    } else {
+        Some(workspace.root().join(".cargo").join("mutants.toml")).filter(|path| path.exists())
+    };
+    config::Config::read_merged(
+        workspace.mutants_metadata(),
+        config_path.as_ref().map(|path| path.as_std_path()),
+    )
+}
+
+/// Find the directory to start looking for the workspace, from `--manifest-path` or `--dir`.
+fn start_dir(args: &Args) -> Result<&Utf8Path> {
+    if let Some(manifest_path) = &args.manifest_path {
+        ensure!(manifest_path.is_file(), "Manifest path is not a file");
+        manifest_path
+            .parent()
+            .ok_or(anyhow!("Manifest path has no parent"))
+    } else if let Some(dir) = &args.dir {
+        Ok(dir)
+    } else {
+        Ok(Utf8Path::new("."))
+    }
+}
+
+/// Choose which packages to mutate, from `--package` and `--workspace`.
+fn package_filter(args: &Args, start_dir: &Utf8Path) -> PackageFilter {
+    if !args.mutate_packages.is_empty() {
+        PackageFilter::explicit(&args.mutate_packages)
+    } else if args.workspace {
+        PackageFilter::All
+    } else {
+        PackageFilter::Auto(start_dir.to_owned())
+    }
+}
+
+#[allow(clippy::too_many_lines)]
 fn main() -> Result<()> {
     let args = match Cargo::try_parse() {
         Ok(Cargo::Mutants(args)) => args,
@@ -441,47 +771,44 @@ fn main() -> Result<()> {
     } else if let Some(shell) = args.completions {
         generate(shell, &mut Cargo::command(), "cargo", &mut io::stdout());
         return Ok(());
+    } else if args.list_genres {
+        print!("{}", list_genres(args.json));
+        return Ok(());
     }
 
     let console = Console::new();
-    console.setup_global_trace(args.level, args.colors); // We don't have Options yet.
+    console.setup_global_trace(console_trace_level(&args), args.colors); // We don't have Options yet.
     enable_console_colors(args.colors);
     interrupt::install_handler();
 
-    let start_dir: &Utf8Path = if let Some(manifest_path) = &args.manifest_path {
-        ensure!(manifest_path.is_file(), "Manifest path is not a file");
-        manifest_path
-            .parent()
-            .ok_or(anyhow!("Manifest path has no parent"))?
-    } else if let Some(dir) = &args.dir {
-        dir
-    } else {
-        Utf8Path::new(".")
-    };
+    let start_dir = start_dir(&args)?;
     let workspace = Workspace::open(start_dir)?;
-    let config = if args.no_config {
-        config::Config::default()
-    } else {
-        config::Config::read_tree_config(workspace.root())?
-    };
+    let config = load_config(&args, &workspace)?;
     debug!(?config);
     debug!(?args.features);
     let options = Options::new(&args, &config)?;
     debug!(?options);
-    let package_filter = if !args.mutate_packages.is_empty() {
-        PackageFilter::explicit(&args.mutate_packages)
-    } else if args.workspace {
-        PackageFilter::All
-    } else {
-        PackageFilter::Auto(start_dir.to_owned())
-    };
+    if let Some(type_str) = &args.explain_type {
+        print!("{}", explain_type_str(type_str, &options)?);
+        return Ok(());
+    }
+    let package_filter = package_filter(&args, start_dir);
 
     let output_parent_dir = options
         .output_in_dir
         .clone()
         .unwrap_or_else(|| workspace.root().to_owned());
 
+    let discovery_start = Instant::now();
     let mut discovered = workspace.discover(&package_filter, &options, &console)?;
+    if args.discovery_only {
+        console.clear();
+        print!(
+            "{}",
+            discovery_summary(&discovered, discovery_start.elapsed(), &options)
+        );
+        return Ok(());
+    }
 
     let previously_caught = if args.iterate {
         let previously_caught = load_previously_caught(&output_parent_dir)?;
@@ -494,6 +821,15 @@ fn main() -> Result<()> {
     } else {
         None
     };
+    if let Some(path) = &args.mutants_from_file {
+        discovered.retain_named(&read_mutant_names(path)?, path);
+    }
+    if !args.function.is_empty() {
+        discovered.retain_functions(&args.function);
+    }
+    if let (Some(path), Some(files)) = (&args.examine_from_file, &options.examine_files) {
+        discovered.warn_undiscovered_files(files, path);
+    }
 
     console.clear();
     if args.list_files {
@@ -501,29 +837,73 @@ fn main() -> Result<()> {
         return Ok(());
     }
     let mut mutants = discovered.mutants;
-    if let Some(in_diff) = &args.in_diff {
-        mutants = diff_filter(
-            mutants,
-            &read_to_string(in_diff).context("Failed to read filter diff")?,
-        )?;
+    let diff_text = if let Some(in_diff) = &args.in_diff {
+        Some(read_to_string(in_diff).context("Failed to read filter diff")?)
+    } else if let Some(since) = &args.since {
+        Some(git_diff_since(workspace.root(), since)?)
+    } else {
+        None
+    };
+    if let Some(diff_text) = diff_text {
+        if options.always_examine_names.is_empty() {
+            mutants = diff_filter(mutants, &diff_text)?;
+        } else {
+            // Keep mutants matching `always_examine_names` even if the diff doesn't touch them,
+            // while preserving the discovery order.
+            let in_diff: HashSet<String> = diff_filter(mutants.clone(), &diff_text)?
+                .iter()
+                .map(|m| m.name(true))
+                .collect();
+            mutants
+                .retain(|m| options.always_examines_mutant(m) || in_diff.contains(&m.name(true)));
+        }
     }
     if let Some(shard) = &args.shard {
         mutants = shard.select(mutants);
     }
-    if args.list {
+    if let Some(script) = &options.filter_script {
+        mutants = retain_by_filter_script(mutants, script)?;
+    }
+    if let Some(patch_dir) = &args.emit_patches {
+        write_patches(&mutants, patch_dir)?;
+    } else if args.list && args.count {
+        print!("{}", list_counts(&mutants, &options));
+    } else if args.list {
         print!("{}", list_mutants(&mutants, &options));
     } else {
-        let output_dir = OutputDir::new(&output_parent_dir)?;
-        if let Some(previously_caught) = previously_caught {
-            output_dir.write_previously_caught(&previously_caught)?;
-        }
-        console.set_debug_log(output_dir.open_debug_log()?);
-        let lab_outcome = test_mutants(mutants, &workspace, output_dir, &options, &console)?;
+        // Failing to set up the output directory is a setup error, returned from main.
+        let output_dir = create_output_dir(&output_parent_dir, previously_caught, &console)?;
+        let lab_outcome = test_mutants(mutants, &workspace, output_dir, &options, &console)
+            .unwrap_or_else(|err| {
+                // Errors while testing are internal errors, unless the user interrupted
+                // the run.
+                eprintln!("Error: {err:?}");
+                exit(if interrupt::was_interrupted() {
+                    exit_code::INTERRUPTED
+                } else {
+                    exit_code::SOFTWARE
+                })
+            });
         exit(lab_outcome.exit_code());
     }
     Ok(())
 }
 
+/// Create a new output directory for the results of testing mutants, and start writing
+/// the debug log into it.
+fn create_output_dir(
+    output_parent_dir: &Utf8Path,
+    previously_caught: Option<Vec<String>>,
+    console: &Console,
+) -> Result<OutputDir> {
+    let output_dir = OutputDir::new(output_parent_dir)?;
+    if let Some(previously_caught) = previously_caught {
+        output_dir.write_previously_caught(&previously_caught)?;
+    }
+    console.set_debug_log(output_dir.open_debug_log()?);
+    Ok(output_dir)
+}
+
 #[cfg(test)]
 mod test {
     use clap::CommandFactory;
@@ -7,9 +7,11 @@ use std::sync::Arc;
 
 use anyhow::Result;
 use console::{style, StyledObject};
+use itertools::Itertools;
 use serde::ser::{SerializeStruct, Serializer};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use similar::TextDiff;
+use strum::{EnumIter, EnumString};
 use tracing::trace;
 
 use crate::build_dir::BuildDir;
@@ -19,7 +21,20 @@ use crate::span::Span;
 use crate::MUTATION_MARKER_COMMENT;
 
 /// Various broad categories of mutants.
-#[derive(Clone, Eq, PartialEq, Debug, Serialize)]
+#[derive(
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Hash,
+    Ord,
+    PartialOrd,
+    Debug,
+    Serialize,
+    Deserialize,
+    EnumString,
+    EnumIter,
+)]
 pub enum Genre {
     /// Replace the body of a function with a fixed value.
     FnValue,
@@ -30,6 +45,112 @@ pub enum Genre {
     MatchArm,
     /// Replace the expression of a match arm guard with a fixed value.
     MatchArmGuard,
+    /// Swap the bodies of two adjacent match arms.
+    MatchArmSwap,
+    /// Replace a call to `.min(x)` with `.max(x)` and vice versa.
+    MinMaxSwap,
+    /// Remove a call to `.clone()`, leaving just the receiver.
+    CloneRemoval,
+    /// Swap the `if` and `else` branches of an `if` expression.
+    IfSwap,
+    /// Replace a literal const generic default, like `const N: usize = 8`, with an
+    /// adjacent value.
+    ConstDefault,
+    /// Replace an `assert!` or similar macro in non-test code with `()`, so that the
+    /// invariant is never checked.
+    AssertNeutralize,
+    /// Replace a float literal with `0.0`, `1.0`, or its negation.
+    LiteralFloat,
+    /// Replace a `true` literal with `false` and vice versa.
+    LiteralBool,
+    /// Replace the closure passed to `.map` with one that does nothing, or delete a call
+    /// to `.and_then`.
+    CombinatorTweak,
+    /// Replace the only use of a function parameter with `Default::default()`.
+    ReplaceArgWithDefault,
+    /// Swap `saturating_*` and `wrapping_*` integer arithmetic methods.
+    OverflowPolicy,
+    /// Delete a statement that calls `push`, `insert`, or `extend`.
+    CollectionMutation,
+    /// Replace the predicate passed to `.filter`, `.retain`, and similar with `|_| true`
+    /// or `|_| false`.
+    PredicateConst,
+    /// Change the end of a range iterated by a `for` loop by one, or make it empty.
+    LoopBound,
+    /// Replace the value of a `static`, or the value returned by its lazy initializer.
+    StaticValue,
+    /// Replace the format string and arguments of `write!` or `writeln!` with `""`.
+    FormatMutation,
+    /// Replace `a && b` or `a || b` with just one of its operands.
+    ConditionPrune,
+    /// Replace `break` with `continue` in a loop, or vice versa.
+    LoopControl,
+    /// Remove an iterator adapter like `.rev()` or `.skip(n)`.
+    IterAdapter,
+    /// Replace the default value passed to `.unwrap_or()`.
+    UnwrapOrDefault,
+    /// Replace a read of `self.field` with a value of the field's type.
+    FieldRead,
+    /// Delete a `return` statement at the end of an `if` block.
+    EarlyReturn,
+    /// Swap calls to the functions `min` and `max`, like `std::cmp::min(a, b)`.
+    CmpFnSwap,
+    /// Force the other branch of `if let Ok(x) = ...` or `if let Err(e) = ...`.
+    ResultBranch,
+    /// Delete a statement that calls `dbg!`, `println!`, or `eprintln!`.
+    DebugRemoval,
+}
+
+impl Genre {
+    /// A short lowercase description of this kind of mutation, for listings and reports.
+    pub fn description(self) -> &'static str {
+        match self {
+            Genre::FnValue => "replace function body with a value",
+            Genre::BinaryOperator => "replace binary operator",
+            Genre::UnaryOperator => "delete unary operator",
+            Genre::MatchArm => "delete match arm",
+            Genre::MatchArmGuard => "replace match arm guard",
+            Genre::MatchArmSwap => "swap match arm bodies",
+            Genre::MinMaxSwap => "swap min and max",
+            Genre::CloneRemoval => "remove clone call",
+            Genre::IfSwap => "swap if and else branches",
+            Genre::ConstDefault => "replace const generic default",
+            Genre::AssertNeutralize => "neutralize assertion",
+            Genre::LiteralFloat => "replace float literal",
+            Genre::LiteralBool => "flip boolean literal",
+            Genre::CombinatorTweak => "delete combinator call",
+            Genre::ReplaceArgWithDefault => "replace argument with default",
+            Genre::OverflowPolicy => "swap integer overflow policy",
+            Genre::CollectionMutation => "delete collection update",
+            Genre::PredicateConst => "replace predicate with constant",
+            Genre::LoopBound => "change loop range bound",
+            Genre::StaticValue => "replace static value",
+            Genre::FormatMutation => "write nothing",
+            Genre::ConditionPrune => "drop condition operand",
+            Genre::LoopControl => "swap break and continue",
+            Genre::IterAdapter => "remove iterator adapter",
+            Genre::UnwrapOrDefault => "replace unwrap_or default",
+            Genre::FieldRead => "replace field read",
+            Genre::EarlyReturn => "delete early return",
+            Genre::CmpFnSwap => "swap min and max functions",
+            Genre::ResultBranch => "force Ok or Err branch",
+            Genre::DebugRemoval => "delete debug output",
+        }
+    }
+
+    /// True if mutants of this genre are generated without any options.
+    ///
+    /// The others are enabled by `--mutate-const-defaults`, `--replace-args-with-default`,
+    /// `--mutate-field-reads`, and `--remove-debug-output`.
+    pub fn enabled_by_default(self) -> bool {
+        !matches!(
+            self,
+            Genre::ConstDefault
+                | Genre::ReplaceArgWithDefault
+                | Genre::FieldRead
+                | Genre::DebugRemoval
+        )
+    }
 }
 
 /// A mutation applied to source code.
@@ -58,12 +179,20 @@ pub struct Mutant {
 /// The function containing a mutant.
 ///
 /// This is used for both mutations of the whole function, and smaller mutations within it.
-#[derive(Eq, PartialEq, Debug, Serialize)]
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
 pub struct Function {
     /// The function that's being mutated, including any containing namespaces.
     #[allow(clippy::struct_field_names)]
     pub function_name: String,
 
+    /// The modules, impls, and traits enclosing the function, outermost first.
+    ///
+    /// Together with [`Function::name`] these make up `function_name`.
+    pub namespace: Vec<String>,
+
+    /// The name of the function itself, without any enclosing namespaces.
+    pub name: String,
+
     /// The return type of the function, including a leading "-> ", as a fragment of Rust syntax.
     ///
     /// Empty if the function has no return type (i.e. returns `()`).
@@ -111,6 +240,15 @@ impl Mutant {
         v.join("")
     }
 
+    /// Return the name of this mutant, including line and column, prefixed by its genre.
+    ///
+    /// For example, `BinaryOperator: src/lib.rs:12:5: replace == with != in parse`.
+    ///
+    /// Name filters match against both this and the plain name.
+    pub fn genre_qualified_name(&self) -> String {
+        format!("{:?}: {}", self.genre, self.name(true))
+    }
+
     /// Return a one-line description of this mutant, with coloring, including the file names
     /// and optionally the line and column.
     pub fn to_styled_string(&self, show_line_col: bool) -> String {
@@ -157,6 +295,42 @@ impl Mutant {
             Genre::MatchArm => {
                 v.push(s("delete match arm"));
             }
+            Genre::MatchArmSwap => {
+                v.push(s("swap match arm bodies"));
+            }
+            Genre::IfSwap => {
+                v.push(s("swap if and else branches"));
+                if let Some(function) = &self.function {
+                    v.push(s(" in "));
+                    v.push(s(&function.function_name).bright().magenta());
+                }
+            }
+            Genre::AssertNeutralize => {
+                v.push(s("neutralize "));
+                let original = self.original_text();
+                let macro_name = original.split('!').next().unwrap_or_default();
+                v.push(s(format!("{macro_name}!")).yellow());
+                if let Some(function) = &self.function {
+                    v.push(s(" in "));
+                    v.push(s(&function.function_name).bright().magenta());
+                }
+            }
+            Genre::DebugRemoval => {
+                v.push(s("delete "));
+                let original = self.original_text();
+                let macro_name = original.split('!').next().unwrap_or_default();
+                v.push(s(format!("{macro_name}!")).yellow());
+                if let Some(function) = &self.function {
+                    v.push(s(" in "));
+                    v.push(s(&function.function_name).bright().magenta());
+                }
+            }
+            Genre::ConstDefault => {
+                v.push(s("replace const generic default "));
+                v.push(s(self.original_text()).yellow());
+                v.push(s(" with "));
+                v.push(s(&self.replacement).bright().yellow());
+            }
             _ => {
                 if self.replacement.is_empty() {
                     v.push(s("delete "));
@@ -186,6 +360,43 @@ impl Mutant {
         self.replacement.as_str()
     }
 
+    /// Return the source lines around this mutant, with line numbers.
+    ///
+    /// The original lines covered by the span are marked with `-`, followed by the same
+    /// lines with the replacement applied marked with `+`. `radius` unchanged lines are shown
+    /// before and after.
+    pub fn source_context(&self, radius: usize) -> String {
+        let code = self.source_file.code();
+        let original_lines = code.lines().collect_vec();
+        let mutated_code = self.span.replace(code, &self.replacement);
+        let mutated_lines = mutated_code.lines().collect_vec();
+        let start = self.span.start.line;
+        let end = self.span.end.line;
+        let mutated_end = start + self.replacement.matches('\n').count();
+        let first = start.saturating_sub(radius).max(1);
+        let last = (end + radius).min(original_lines.len()).max(end);
+        let width = last.to_string().len();
+        let mut out = String::new();
+        let mut push_line = |marker: char, line_no: usize, lines: &[&str]| {
+            let text = lines.get(line_no - 1).copied().unwrap_or_default();
+            out.push_str(format!("{marker}{line_no:>width$} | {text}").trim_end());
+            out.push('\n');
+        };
+        for line_no in first..start {
+            push_line(' ', line_no, &original_lines);
+        }
+        for line_no in start..=end {
+            push_line('-', line_no, &original_lines);
+        }
+        for line_no in start..=mutated_end {
+            push_line('+', line_no, &mutated_lines);
+        }
+        for line_no in (end + 1)..=last {
+            push_line(' ', line_no, &original_lines);
+        }
+        out
+    }
+
     /// Return a unified diff for the mutant.
     ///
     /// The mutated text must be passed in because we should have already computed
@@ -201,6 +412,18 @@ impl Mutant {
             .to_string()
     }
 
+    /// Return a patch for the mutant, suitable for applying with `patch -p1` from the
+    /// root of the source tree.
+    ///
+    /// Unlike [`Mutant::diff`] this uses conventional `a/` and `b/` path prefixes in the header.
+    pub fn patch(&self, mutated_code: &str) -> String {
+        let path = self.source_file.tree_relative_slashes();
+        TextDiff::from_lines(self.source_file.code(), mutated_code)
+            .unified_diff()
+            .header(&format!("a/{path}"), &format!("b/{path}"))
+            .to_string()
+    }
+
     /// Apply this mutant to the relevant file within a `BuildDir`.
     pub fn apply(&self, build_dir: &BuildDir, mutated_code: &str) -> Result<()> {
         trace!(?self, "Apply mutant");
@@ -249,6 +472,7 @@ impl Serialize for Mutant {
     {
         // custom serialize to omit inessential info
         let mut ss = serializer.serialize_struct("Mutant", 7)?;
+        ss.serialize_field("name", &self.name(true))?;
         ss.serialize_field("package", &self.source_file.package.name)?;
         ss.serialize_field("file", &self.source_file.tree_relative_slashes())?;
         ss.serialize_field("function", &self.function.as_ref().map(Arc::as_ref))?;
@@ -286,6 +510,8 @@ mod test {
                     function: Some(
                         Function {
                             function_name: "main",
+                            namespace: [],
+                            name: "main",
                             return_type: "",
                             span: Span(1, 1, 5, 2),
                         },
@@ -308,6 +534,8 @@ mod test {
                     function: Some(
                         Function {
                             function_name: "factorial",
+                            namespace: [],
+                            name: "factorial",
                             return_type: "-> u32",
                             span: Span(7, 1, 13, 2),
                         },
@@ -346,6 +574,7 @@ mod test {
             descriptions,
             [
                 "replace controlled_loop with ()",
+                "replace break with continue in controlled_loop",
                 "replace > with == in controlled_loop",
                 "replace > with < in controlled_loop",
                 "replace > with => in controlled_loop",
@@ -436,6 +665,41 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn source_context_of_multi_line_span() {
+        let code = indoc! { r"
+            fn sum(v: &[u32]) -> u32 {
+                let mut total = 0;
+                for x in v {
+                    total += x;
+                }
+                total
+            }
+        "};
+        let mutants = mutate_source_str(code, &Options::default()).unwrap();
+        let fn_value = mutants
+            .iter()
+            .find(|m| m.genre == Genre::FnValue && m.replacement == "1")
+            .unwrap();
+        assert_eq!(
+            fn_value.source_context(1),
+            indoc! { r"
+                 1 | fn sum(v: &[u32]) -> u32 {
+                -2 |     let mut total = 0;
+                -3 |     for x in v {
+                -4 |         total += x;
+                -5 |     }
+                -6 |     total
+                +2 |     1
+                 7 | }
+            "}
+        );
+        assert_eq!(
+            fn_value.source_context(0).lines().next().unwrap(),
+            "-2 |     let mut total = 0;"
+        );
+    }
+
     fn strip_trailing_space(s: &str) -> String {
         // Split on \n so that we retain empty lines etc
         s.split('\n').map(str::trim_end).join("\n")
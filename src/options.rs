@@ -10,11 +10,14 @@
 
 #![warn(clippy::pedantic)]
 
+use std::collections::BTreeMap;
 use std::env;
 #[cfg(test)]
 use std::ffi::OsString;
+use std::str::FromStr;
 use std::time::Duration;
 
+use anyhow::anyhow;
 use camino::{Utf8Path, Utf8PathBuf};
 use globset::GlobSet;
 use regex::RegexSet;
@@ -24,10 +27,21 @@ use syn::Expr;
 use tracing::warn;
 
 use crate::config::Config;
+use crate::exclude_range::ExcludeRange;
+use crate::fnvalue::{ErrorExprs, ValueOptions};
 use crate::glob::build_glob_set;
-use crate::mutant::Mutant;
+use crate::mutant::{Genre, Mutant};
+use crate::outcome::SummaryOutcome;
+use crate::result_format::ResultFormat;
 use crate::{Args, BaselineStrategy, Context, Phase, Result, ValueEnum};
 
+/// Traits whose impls are skipped by default, because they're usually boilerplate.
+const DEFAULT_SKIP_TRAIT_IMPLS: &[&str] = &["Debug", "Display", "Hash"];
+
+/// Trait methods that are skipped by default, in any trait, because they're usually
+/// mechanical.
+const DEFAULT_SKIP_TRAIT_METHODS: &[&str] = &["fmt", "eq", "partial_cmp", "hash"];
+
 /// Options for mutation testing, based on both command-line arguments and the
 /// config file.
 #[derive(Default, Debug, Clone)]
@@ -42,6 +56,9 @@ pub struct Options {
     /// Don't run the tests, just see if each mutant builds.
     pub check_only: bool,
 
+    /// Copy the baseline's `target` directory into additional build directories.
+    pub copy_target: bool,
+
     /// Copy `.git` and other VCS directories to build directories.
     pub copy_vcs: bool,
 
@@ -60,6 +77,12 @@ pub struct Options {
     /// Don't delete scratch directories.
     pub leak_dirs: bool,
 
+    /// Create scratch directories inside this directory, rather than the system temp dir.
+    pub scratch_dir: Option<Utf8PathBuf>,
+
+    /// Don't generate values for `&mut` return types, because they leak memory.
+    pub no_leak_mut: bool,
+
     /// The time limit for test tasks, if set.
     ///
     /// If this is not set by the user it's None, in which case there is no time limit
@@ -70,6 +93,9 @@ pub struct Options {
     /// The time multiplier for test tasks, if set (relative to baseline test duration).
     pub test_timeout_multiplier: Option<f64>,
 
+    /// Test time limits for mutants of particular genres, overriding the general test timeout.
+    pub test_timeout_by_genre: Vec<(Genre, Duration)>,
+
     /// Which packages to test for a given mutant.
     ///
     /// Comes from `--test-workspace` etc.
@@ -85,25 +111,38 @@ pub struct Options {
     /// The time multiplier for build tasks, if set (relative to baseline build duration).
     pub build_timeout_multiplier: Option<f64>,
 
+    /// Stop starting new mutants after this much time since the start of testing.
+    pub total_timeout: Option<Duration>,
+
     /// The minimum test timeout, as a floor on the autoset value.
     pub minimum_test_timeout: Duration,
 
     pub print_caught: bool,
     pub print_unviable: bool,
 
+    /// Print results only for mutants with this outcome, from `--caught-only` or
+    /// `--missed-only`.
+    pub print_only: Option<SummaryOutcome>,
+
+    /// Template for the line printed for each mutant's result, from `--format`.
+    pub format: Option<ResultFormat>,
+
     pub show_times: bool,
 
     /// Show logs even from mutants that were caught, or source/unmutated builds.
     pub show_all_logs: bool,
 
+    /// How much to print about each mutant, from `--quiet` and `--verbose`.
+    pub verbosity: Verbosity,
+
     /// List mutants with line and column numbers.
     pub show_line_col: bool,
 
-    /// Test mutants in random order.
-    ///
-    /// This is now the default, so that repeated partial runs are more likely to find
-    /// interesting results.
-    pub shuffle: bool,
+    /// Order in which to test mutants.
+    pub order: MutantOrder,
+
+    /// Seed for shuffling mutants with [`MutantOrder::Random`], or None to pick one.
+    pub shuffle_seed: Option<u64>,
 
     /// Don't mutate arguments to functions or methods matching any of these name.
     ///
@@ -111,9 +150,25 @@ pub struct Options {
     /// `::`.
     pub skip_calls: Vec<String>,
 
+    /// Don't mutate impls of traits with any of these names.
+    ///
+    /// This matches against the last component of the trait path, so `fmt::Debug` is
+    /// matched by `Debug`.
+    pub skip_trait_impls: Vec<String>,
+
+    /// Don't mutate methods with any of these names in trait impls, or default methods
+    /// in traits, whichever trait they belong to.
+    pub skip_trait_methods: Vec<String>,
+
     /// Cargo profile.
     pub profile: Option<String>,
 
+    /// Rustup toolchain to pass as `cargo +TOOLCHAIN`, if any.
+    pub toolchain: Option<String>,
+
+    /// Only mutate functions with `pub` or `pub(...)` visibility, and trait impl methods.
+    pub pub_only: bool,
+
     /// Additional arguments for every cargo invocation.
     pub additional_cargo_args: Vec<String>,
 
@@ -123,27 +178,102 @@ pub struct Options {
     /// Selection of features for cargo.
     pub features: super::Features,
 
+    /// Also mutate example targets.
+    pub examine_examples: bool,
+
+    /// Skip functions whose bodies have fewer than this many statements.
+    pub min_statements: usize,
+
+    /// Count each arm of a `match` as a statement for `min_statements`.
+    pub count_match_arms: bool,
+
+    /// Generate off-by-one mutants of literal const generic defaults.
+    pub mutate_const_defaults: bool,
+
+    /// Replace reads of `self.field` with other values, for fields of simple types.
+    pub mutate_field_reads: bool,
+
+    /// Also mutate `unsafe fn`s.
+    pub mutate_unsafe: bool,
+
+    /// Replace the only use of each function parameter with `Default::default()`.
+    pub replace_args_with_default: bool,
+
+    /// Delete statements that call `dbg!`, `println!`, or `eprintln!`.
+    pub remove_debug_output: bool,
+
+    /// Run the tests for a caught mutant up to this many more times, reporting it as
+    /// missed if they ever pass.
+    pub retry: usize,
+
+    /// Shell command to run in the build directory before building each mutant.
+    pub pre_mutant_command: Option<String>,
+
+    /// Shell command to run in the build directory after testing each mutant.
+    pub post_mutant_command: Option<String>,
+
+    /// Also replace `Vec<u8>` return values with a non-empty byte string.
+    pub byte_strings: bool,
+
+    /// Also replace the bodies of functions returning `()` with `panic!()`.
+    pub unit_panic: bool,
+
+    /// Also replace the bodies of functions returning `Result<(), E>` with `panic!()`.
+    pub panic_mutants: bool,
+
+    /// Also replace values of unrecognized types by parsing `"0"` and `"1"`.
+    pub try_fromstr: bool,
+
+    /// Also replace float return values with NaN and the infinities.
+    pub float_specials: bool,
+
     /// Files to examine.
     pub examine_globset: Option<GlobSet>,
 
     /// Files to exclude.
     pub exclude_globset: Option<GlobSet>,
 
+    /// If set, only these files are examined, as tree-relative paths from `--examine-from-file`.
+    pub examine_files: Option<Vec<Utf8PathBuf>>,
+
+    /// Stop discovery after this many source files have produced mutants.
+    pub max_files: Option<usize>,
+
+    /// Cache the mutants discovered in each source file, and reuse them if it's unchanged.
+    pub discovery_cache: bool,
+
     /// Mutants to examine, as a regexp matched against the full name.
     pub examine_names: RegexSet,
 
+    /// Mutants to always examine, regardless of other filters, as a regexp matched
+    /// against the full name.
+    pub always_examine_names: RegexSet,
+
     /// Mutants to skip, as a regexp matched against the full name.
     pub exclude_names: RegexSet,
 
+    /// Skip mutants overlapping these ranges of lines.
+    pub exclude_ranges: Vec<ExcludeRange>,
+
+    /// Program run for each discovered mutant to decide whether to keep it.
+    pub filter_script: Option<Utf8PathBuf>,
+
     /// Create `mutants.out` within this directory (by default, the source directory).
     pub output_in_dir: Option<Utf8PathBuf>,
 
+    /// Write missed mutants to this file as a SARIF report.
+    pub sarif: Option<Utf8PathBuf>,
+
     /// Run this many `cargo build` or `cargo test` tasks in parallel.
     pub jobs: Option<usize>,
 
     /// Insert these values as errors from functions returning `Result`.
     pub error_values: Vec<String>,
 
+    /// Insert these values as errors from functions returning `Result` only when the error
+    /// type path ends with the key.
+    pub error_values_by_type: BTreeMap<String, Vec<String>>,
+
     /// Show ANSI colors.
     pub colors: Colors,
 
@@ -153,6 +283,12 @@ pub struct Options {
     /// Emit diffs showing just what changed.
     pub emit_diffs: bool,
 
+    /// When listing mutants, show this many lines of source context around each one.
+    pub list_context: Option<usize>,
+
+    /// List mutants as a Markdown table.
+    pub emit_markdown: bool,
+
     /// The tool to use to run tests.
     pub test_tool: TestTool,
 }
@@ -171,6 +307,23 @@ pub enum TestPackages {
     Named(Vec<String>),
 }
 
+/// Order in which mutants are tested.
+#[derive(Debug, Default, ValueEnum, Clone, Copy, Eq, PartialEq)]
+pub enum MutantOrder {
+    /// In the order they're discovered: by file, and then in order within the file.
+    #[default]
+    Source,
+
+    /// Shuffled randomly, so that partial runs see a sample of the whole tree.
+    ///
+    /// This is the default on the command line, so that repeated partial runs are more
+    /// likely to find interesting results.
+    Random,
+
+    /// Grouped by genre, and then in source order within each genre.
+    ByGenre,
+}
+
 /// Choice of tool to use to run tests.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, EnumString, Display, Deserialize)]
 #[strum(serialize_all = "snake_case")]
@@ -189,6 +342,31 @@ fn join_slices(a: &[String], b: &[String]) -> Vec<String> {
     a.iter().chain(b).cloned().collect()
 }
 
+/// How much to print about each scenario.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Print only missed mutants, scenarios with problems, and the final summary.
+    Quiet,
+    /// Print one line for each mutant outcome, subject to `--caught` and `--unviable`.
+    #[default]
+    Normal,
+    /// Also print each command as it's run, and record its environment in the log.
+    Verbose,
+    /// Also print the full output of every command.
+    VeryVerbose,
+}
+
+impl Verbosity {
+    fn from_args(args: &Args) -> Verbosity {
+        match (args.quiet, args.verbose) {
+            (true, _) => Verbosity::Quiet,
+            (false, 0) => Verbosity::Normal,
+            (false, 1) => Verbosity::Verbose,
+            (false, _) => Verbosity::VeryVerbose,
+        }
+    }
+}
+
 /// Should ANSI colors be drawn?
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Display, Deserialize, ValueEnum)]
 #[strum(serialize_all = "snake_case")]
@@ -230,9 +408,10 @@ impl Colors {
 
 impl Options {
     /// Build options by merging command-line args and config file.
+    #[allow(clippy::too_many_lines)] // Just a long but simple list of fields.
     pub(crate) fn new(args: &Args, config: &Config) -> Result<Options> {
         if args.no_copy_target {
-            warn!("--no-copy-target is deprecated and has no effect; target/ is never copied");
+            warn!("--no-copy-target is deprecated; use --copy-target=false");
         }
 
         let minimum_test_timeout = Duration::from_secs_f64(
@@ -260,6 +439,34 @@ impl Options {
             TestPackages::Mutated
         };
 
+        // Command line values come after config values, and replace them for the same genre.
+        let mut genre_timeouts: Vec<(String, f64)> = config
+            .timeout_per_genre
+            .iter()
+            .map(|(genre, secs)| (genre.clone(), *secs))
+            .collect();
+        for arg in &args.timeout_per_genre {
+            let (genre, secs) = arg
+                .split_once('=')
+                .with_context(|| format!("Genre timeout {arg:?} should be like GENRE=SECONDS"))?;
+            let secs = secs
+                .parse::<f64>()
+                .with_context(|| format!("Invalid timeout in {arg:?}"))?;
+            genre_timeouts.push((genre.to_owned(), secs));
+        }
+        let mut test_timeout_by_genre: Vec<(Genre, Duration)> = Vec::new();
+        for (genre_name, secs) in genre_timeouts {
+            let genre = Genre::from_str(&genre_name)
+                .map_err(|_| anyhow!("Unknown genre {genre_name:?} in genre timeout"))?;
+            test_timeout_by_genre.retain(|(g, _)| *g != genre);
+            let timeout = Duration::try_from_secs_f64(secs).map_err(|_| {
+                anyhow!(
+                    "--timeout-per-genre for {genre_name:?} must be a non-negative number of seconds, not {secs}"
+                )
+            })?;
+            test_timeout_by_genre.push((genre, timeout));
+        }
+
         let mut skip_calls: Vec<String> = args
             .skip_calls
             .iter()
@@ -274,6 +481,43 @@ impl Options {
         {
             skip_calls.push("with_capacity".to_owned());
         }
+        let mut skip_trait_impls: Vec<String> = args
+            .skip_trait_impls
+            .iter()
+            .flat_map(|s| s.split(','))
+            .map(ToString::to_string)
+            .chain(config.skip_trait_impls.iter().cloned())
+            .collect();
+        if args
+            .skip_trait_impls_defaults
+            .or(config.skip_trait_impls_defaults)
+            .unwrap_or(true)
+        {
+            skip_trait_impls.extend(
+                DEFAULT_SKIP_TRAIT_IMPLS
+                    .iter()
+                    .map(|name| (*name).to_owned()),
+            );
+        }
+        let mut skip_trait_methods: Vec<String> = args
+            .skip_trait_methods
+            .iter()
+            .flat_map(|s| s.split(','))
+            .map(ToString::to_string)
+            .chain(config.skip_trait_methods.iter().cloned())
+            .collect();
+        if args
+            .skip_trait_methods_defaults
+            .or(config.skip_trait_methods_defaults)
+            .unwrap_or(true)
+        {
+            skip_trait_methods.extend(
+                DEFAULT_SKIP_TRAIT_METHODS
+                    .iter()
+                    .map(|name| (*name).to_owned()),
+            );
+        }
+        let verbosity = Verbosity::from_args(args);
 
         let options = Options {
             additional_cargo_args: join_slices(&args.cargo_arg, &config.additional_cargo_args),
@@ -289,16 +533,57 @@ impl Options {
             cap_lints: args.cap_lints.unwrap_or(config.cap_lints),
             check_only: args.check,
             colors: args.colors,
+            copy_target: !args.no_copy_target
+                && args.copy_target.or(config.copy_target).unwrap_or(true),
             copy_vcs: args.copy_vcs.or(config.copy_vcs).unwrap_or(false),
             emit_json: args.json,
             emit_diffs: args.diff,
+            list_context: args.context,
+            emit_markdown: args.markdown,
+            exclude_ranges: args.exclude_range.clone(),
+            filter_script: args.filter_script.clone(),
             error_values: join_slices(&args.error, &config.error_values),
+            error_values_by_type: config.error_values_by_type.clone(),
+            always_examine_names: RegexSet::new(&config.always_examine_names)
+                .context("Failed to compile always_examine_names regex")?,
             examine_names: RegexSet::new(or_slices(&args.examine_re, &config.examine_re))
                 .context("Failed to compile examine_re regex")?,
             exclude_names: RegexSet::new(or_slices(&args.exclude_re, &config.exclude_re))
                 .context("Failed to compile exclude_re regex")?,
+            examine_examples: args.examine_examples || config.examine_examples,
+            max_files: args.max_files,
+            discovery_cache: args.discovery_cache || config.discovery_cache,
+            min_statements: args.min_statements.or(config.min_statements).unwrap_or(1),
+            count_match_arms: args.count_match_arms || config.count_match_arms,
+            mutate_const_defaults: args.mutate_const_defaults || config.mutate_const_defaults,
+            mutate_field_reads: args.mutate_field_reads || config.mutate_field_reads,
+            mutate_unsafe: args.mutate_unsafe || config.mutate_unsafe,
+            remove_debug_output: args.remove_debug_output || config.remove_debug_output,
+            replace_args_with_default: args.replace_args_with_default
+                || config.replace_args_with_default,
+            retry: args.retry.or(config.retry).unwrap_or(0),
+            pre_mutant_command: args
+                .pre_mutant_command
+                .as_ref()
+                .or(config.pre_mutant_command.as_ref())
+                .cloned(),
+            post_mutant_command: args
+                .post_mutant_command
+                .as_ref()
+                .or(config.post_mutant_command.as_ref())
+                .cloned(),
+            byte_strings: args.byte_strings || config.byte_strings,
+            unit_panic: args.unit_panic || config.unit_panic,
+            panic_mutants: args.panic_mutants || config.panic_mutants,
+            try_fromstr: args.try_fromstr || config.try_fromstr,
+            float_specials: args.float_specials || config.float_specials,
             examine_globset: build_glob_set(or_slices(&args.file, &config.examine_globs))?,
             exclude_globset: build_glob_set(or_slices(&args.exclude, &config.exclude_globs))?,
+            examine_files: args
+                .examine_from_file
+                .as_deref()
+                .map(read_examine_files)
+                .transpose()?,
             features: args.features.clone(),
             gitignore: args.gitignore,
             in_place: args.in_place,
@@ -306,20 +591,47 @@ impl Options {
             jobserver: args.jobserver,
             jobserver_tasks: args.jobserver_tasks,
             leak_dirs: args.leak_dirs,
+            no_leak_mut: args.no_leak_mut || config.leak_mut == Some(false),
             minimum_test_timeout,
             output_in_dir: args.output.clone().or(config.output.clone()),
+            sarif: args.sarif.clone(),
+            scratch_dir: args.scratch_dir.clone(),
             print_caught: args.caught,
+            format: args.format.clone(),
             print_unviable: args.unviable,
+            print_only: if args.caught_only {
+                Some(SummaryOutcome::CaughtMutant)
+            } else if args.missed_only {
+                Some(SummaryOutcome::MissedMutant)
+            } else {
+                None
+            },
             profile: args.profile.as_ref().or(config.profile.as_ref()).cloned(),
-            shuffle: !args.no_shuffle,
+            pub_only: args.pub_only || config.pub_only == Some(true),
+            order: args.order.unwrap_or(if args.no_shuffle {
+                MutantOrder::Source
+            } else {
+                MutantOrder::Random
+            }),
+            shuffle_seed: args.shuffle_seed,
             show_line_col: args.line_col,
             show_times: !args.no_times,
-            show_all_logs: args.all_logs,
+            show_all_logs: args.all_logs || verbosity >= Verbosity::VeryVerbose,
+            verbosity,
             skip_calls,
+            skip_trait_impls,
+            skip_trait_methods,
             test_package,
             test_timeout: args.timeout.map(Duration::from_secs_f64),
+            total_timeout: args.total_timeout.map(Into::into),
+            test_timeout_by_genre,
             test_timeout_multiplier: args.timeout_multiplier.or(config.timeout_multiplier),
             test_tool: args.test_tool.or(config.test_tool).unwrap_or_default(),
+            toolchain: args
+                .toolchain
+                .as_ref()
+                .or(config.toolchain.as_ref())
+                .cloned(),
         };
         if let Some(jobs) = options.jobs {
             if jobs >= 8 {
@@ -364,26 +676,93 @@ impl Options {
         }
     }
 
+    /// The options that control which values are generated for a type.
+    pub(crate) fn value_options(&self) -> ValueOptions {
+        ValueOptions {
+            no_leak_mut: self.no_leak_mut,
+            byte_strings: self.byte_strings,
+            try_fromstr: self.try_fromstr,
+            float_specials: self.float_specials,
+        }
+    }
+
+    /// Describe the options that affect which mutants are generated from a source file,
+    /// before they're filtered, so that cached mutants are only reused with the same options.
+    ///
+    /// This must include every option that's read while visiting a source file.
+    pub(crate) fn discovery_fingerprint(&self) -> String {
+        format!(
+            "{:?}",
+            (
+                self.value_options(),
+                (&self.error_values, &self.error_values_by_type),
+                (
+                    &self.features,
+                    &self.additional_cargo_args,
+                    &self.additional_cargo_test_args,
+                ),
+                (
+                    &self.skip_calls,
+                    &self.skip_trait_impls,
+                    &self.skip_trait_methods,
+                ),
+                (
+                    self.pub_only,
+                    (self.min_statements, self.count_match_arms),
+                    self.unit_panic,
+                    self.panic_mutants,
+                ),
+                (
+                    self.mutate_const_defaults,
+                    self.mutate_field_reads,
+                    self.mutate_unsafe,
+                    self.replace_args_with_default,
+                    self.remove_debug_output,
+                    self.examine_examples,
+                ),
+            )
+        )
+    }
+
     /// Return the syn ASTs for the error values, which should be inserted as return values
     /// from functions returning `Result`.
-    pub(crate) fn parsed_error_exprs(&self) -> Result<Vec<Expr>> {
-        self.error_values
+    pub(crate) fn parsed_error_exprs(&self) -> Result<ErrorExprs> {
+        let parse_expr = |e: &String| -> Result<Expr> {
+            syn::parse_str(e).with_context(|| format!("Failed to parse error value {e:?}"))
+        };
+        let general = self
+            .error_values
             .iter()
-            .map(|e| {
-                syn::parse_str(e).with_context(|| format!("Failed to parse error value {e:?}"))
-            })
-            .collect()
+            .map(parse_expr)
+            .collect::<Result<Vec<Expr>>>()?;
+        let mut by_type = Vec::new();
+        for (type_name, values) in &self.error_values_by_type {
+            let type_path: syn::Path = syn::parse_str(type_name)
+                .with_context(|| format!("Failed to parse error type {type_name:?}"))?;
+            for value in values {
+                by_type.push((type_path.clone(), parse_expr(value)?));
+            }
+        }
+        Ok(ErrorExprs {
+            general,
+            by_type,
+            result_alias_error: None,
+        })
     }
 
     /// True if the options allow mutants to be generated from the given path.
     ///
-    /// That is: it matches the examine globset (if specified) and does not match the exclude globset
-    /// (if specified).
+    /// That is: it matches the examine globset (if specified), is one of the examined files
+    /// (if specified), and does not match the exclude globset (if specified).
     pub fn allows_source_file_path(&self, path: &Utf8Path) -> bool {
         // TODO: Use Option::is_none_or when MSRV>1.80
         self.examine_globset
             .as_ref()
             .map_or(true, |g| g.is_match(path))
+            && self
+                .examine_files
+                .as_ref()
+                .map_or(true, |files| files.iter().any(|f| f == path))
             && !self
                 .exclude_globset
                 .as_ref()
@@ -391,10 +770,26 @@ impl Options {
     }
 
     /// True if the options allow this mutant to be tested.
+    ///
+    /// Name regexes match against either the plain name or the genre-qualified name.
+    /// Mutants matching `always_examine_names` are allowed regardless of other name filters.
     pub fn allows_mutant(&self, mutant: &Mutant) -> bool {
-        let name = mutant.name(true);
-        (self.examine_names.is_empty() || self.examine_names.is_match(&name))
-            && (self.exclude_names.is_empty() || !self.exclude_names.is_match(&name))
+        if self.always_examines_mutant(mutant) {
+            return true;
+        }
+        let names = [mutant.name(true), mutant.genre_qualified_name()];
+        (self.examine_names.is_empty() || names.iter().any(|n| self.examine_names.is_match(n)))
+            && (self.exclude_names.is_empty()
+                || !names.iter().any(|n| self.exclude_names.is_match(n)))
+    }
+
+    /// True if this mutant should be tested even if file, name, or diff filters would
+    /// otherwise drop it.
+    pub fn always_examines_mutant(&self, mutant: &Mutant) -> bool {
+        !self.always_examine_names.is_empty()
+            && [mutant.name(true), mutant.genre_qualified_name()]
+                .iter()
+                .any(|n| self.always_examine_names.is_match(n))
     }
 }
 
@@ -407,6 +802,19 @@ fn or_slices<'a: 'c, 'b: 'c, 'c, T>(a: &'a [T], b: &'b [T]) -> &'c [T] {
     }
 }
 
+/// Read the list of files for `--examine-from-file`: one tree-relative path per line,
+/// ignoring blank lines and comments starting with `#`.
+fn read_examine_files(path: &Utf8Path) -> Result<Vec<Utf8PathBuf>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read examined files from {path:?}"))?;
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| Utf8PathBuf::from(line.strip_prefix("./").unwrap_or(line)))
+        .collect())
+}
+
 #[cfg(test)]
 mod test {
     use std::io::Write;
@@ -418,6 +826,7 @@ mod test {
     use tempfile::NamedTempFile;
 
     use super::*;
+    use crate::pretty::ToPrettyString;
     use crate::Args;
 
     #[test]
@@ -456,6 +865,28 @@ mod test {
         assert_eq!(options.baseline, BaselineStrategy::Run);
     }
 
+    #[test]
+    fn order_from_args() {
+        let config = Config::default();
+        let order = |argv: &[&str]| {
+            let args = Args::try_parse_from(argv).unwrap();
+            Options::new(&args, &config).unwrap().order
+        };
+        assert_eq!(order(&["mutants"]), MutantOrder::Random);
+        assert_eq!(order(&["mutants", "--shuffle"]), MutantOrder::Random);
+        assert_eq!(order(&["mutants", "--no-shuffle"]), MutantOrder::Source);
+        assert_eq!(order(&["mutants", "--order=source"]), MutantOrder::Source);
+        assert_eq!(
+            order(&["mutants", "--order=by-genre"]),
+            MutantOrder::ByGenre
+        );
+        assert!(Args::try_parse_from(["mutants", "--order=random", "--no-shuffle"]).is_err());
+
+        let args = Args::parse_from(["mutants", "--shuffle-seed=1234"]);
+        let options = Options::new(&args, &config).unwrap();
+        assert_eq!(options.shuffle_seed, Some(1234));
+    }
+
     #[test]
     fn options_from_timeout_args() {
         let args = Args::parse_from(["mutants", "--timeout=2.0"]);
@@ -716,6 +1147,23 @@ mod test {
         assert_eq!(options.test_package, TestPackages::Mutated);
     }
 
+    #[test]
+    fn always_examine_names_from_config() {
+        let args = Args::try_parse_from(["mutants"]).unwrap();
+        let config = Config::from_str(r#"always_examine_names = ["critical::", "auth"]"#).unwrap();
+        let options = Options::new(&args, &config).unwrap();
+        assert_eq!(options.always_examine_names.len(), 2);
+        assert!(options
+            .always_examine_names
+            .is_match("src/critical.rs: replace critical::check"));
+
+        let config = Config::from_str(r#"always_examine_names = ["("]"#).unwrap();
+        let err = Options::new(&args, &config).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Failed to compile always_examine_names regex"));
+    }
+
     #[test]
     fn test_workspace_config_true() {
         let args = Args::try_parse_from(["mutants"]).unwrap();
@@ -787,6 +1235,49 @@ mod test {
         );
     }
 
+    #[test]
+    fn min_statements_from_args_and_config() {
+        let args = Args::parse_from(["mutants"]);
+        let options = Options::new(&args, &Config::default()).unwrap();
+        assert_eq!(options.min_statements, 1);
+
+        let config = Config::from_str("min_statements = 3").unwrap();
+        let options = Options::new(&args, &config).unwrap();
+        assert_eq!(options.min_statements, 3);
+
+        let args = Args::parse_from(["mutants", "--min-statements=2"]);
+        let options = Options::new(&args, &config).unwrap();
+        assert_eq!(options.min_statements, 2);
+        assert!(!options.count_match_arms);
+
+        let config = Config::from_str("count_match_arms = true").unwrap();
+        let options = Options::new(&args, &config).unwrap();
+        assert!(options.count_match_arms);
+
+        let args = Args::parse_from(["mutants", "--count-match-arms"]);
+        let options = Options::new(&args, &Config::default()).unwrap();
+        assert!(options.count_match_arms);
+    }
+
+    #[test]
+    fn pub_only_from_args_and_config() {
+        let args = Args::parse_from(["mutants"]);
+        let options = Options::new(&args, &Config::default()).unwrap();
+        assert!(!options.pub_only);
+
+        let config = Config::from_str("pub_only = true").unwrap();
+        let options = Options::new(&args, &config).unwrap();
+        assert!(options.pub_only);
+
+        let config = Config::from_str("pub_only = false").unwrap();
+        let options = Options::new(&args, &config).unwrap();
+        assert!(!options.pub_only);
+
+        let args = Args::parse_from(["mutants", "--pub-only"]);
+        let options = Options::new(&args, &config).unwrap();
+        assert!(options.pub_only);
+    }
+
     #[test]
     fn default_skip_calls_includes_with_capacity() {
         let args = Args::try_parse_from(["mutants"]).unwrap();
@@ -870,6 +1361,91 @@ mod test {
         assert_eq!(options.skip_calls, ["x", "y", "with_capacity"]);
     }
 
+    #[test]
+    fn error_values_by_type_from_config() {
+        let args = Args::try_parse_from(["mutants"]).unwrap();
+        let config = Config::from_str(
+            r#"
+            error_values = ["anyhow!(\"mutated\")"]
+
+            [error_values_by_type]
+            "io::Error" = ["io::Error::other(\"mutated\")"]
+            "#,
+        )
+        .unwrap();
+        let options = Options::new(&args, &config).unwrap();
+        let error_exprs = options.parsed_error_exprs().unwrap();
+        assert_eq!(error_exprs.general.len(), 1);
+        assert_eq!(error_exprs.by_type.len(), 1);
+        assert_eq!(error_exprs.by_type[0].0.to_pretty_string(), "io::Error");
+        assert_eq!(
+            error_exprs.by_type[0].1.to_pretty_string(),
+            r#"io::Error::other("mutated")"#
+        );
+    }
+
+    #[test]
+    fn copy_target() {
+        let options = Options::new(&Args::parse_from(["mutants"]), &Config::default()).unwrap();
+        assert!(options.copy_target);
+
+        let args = Args::parse_from(["mutants", "--copy-target=false"]);
+        let options = Options::new(&args, &Config::default()).unwrap();
+        assert!(!options.copy_target);
+
+        let args = Args::parse_from(["mutants", "--no-copy-target"]);
+        let options = Options::new(&args, &Config::default()).unwrap();
+        assert!(!options.copy_target);
+
+        let args = Args::parse_from(["mutants", "--copy-target=true"]);
+        let config = Config::from_str("copy_target = false").unwrap();
+        let options = Options::new(&args, &config).unwrap();
+        assert!(options.copy_target);
+
+        let args = Args::parse_from(["mutants"]);
+        let config = Config::from_str("copy_target = false").unwrap();
+        let options = Options::new(&args, &config).unwrap();
+        assert!(!options.copy_target);
+    }
+
+    #[test]
+    fn no_leak_mut() {
+        let options = Options::new(&Args::parse_from(["mutants"]), &Config::default()).unwrap();
+        assert!(!options.no_leak_mut);
+
+        let args = Args::parse_from(["mutants", "--no-leak-mut"]);
+        let options = Options::new(&args, &Config::default()).unwrap();
+        assert!(options.no_leak_mut);
+
+        let args = Args::parse_from(["mutants"]);
+        let config = Config::from_str("leak_mut = false").unwrap();
+        let options = Options::new(&args, &config).unwrap();
+        assert!(options.no_leak_mut);
+    }
+
+    #[test]
+    fn verbosity_from_quiet_and_verbose() {
+        for (argv, verbosity) in [
+            (&["mutants"][..], Verbosity::Normal),
+            (&["mutants", "-q"], Verbosity::Quiet),
+            (&["mutants", "--verbose"], Verbosity::Verbose),
+            (
+                &["mutants", "--verbose", "--verbose"],
+                Verbosity::VeryVerbose,
+            ),
+        ] {
+            let options = Options::new(&Args::parse_from(argv), &Config::default()).unwrap();
+            assert_eq!(options.verbosity, verbosity, "{argv:?}");
+        }
+        let options = Options::new(
+            &Args::parse_from(["mutants", "--verbose", "--verbose"]),
+            &Config::default(),
+        )
+        .unwrap();
+        assert!(options.show_all_logs);
+        assert!(Args::try_parse_from(["mutants", "--quiet", "--verbose"]).is_err());
+    }
+
     #[test]
     fn copy_vcs() {
         let args = Args::parse_from(["mutants", "--copy-vcs=true"]);
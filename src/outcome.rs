@@ -13,7 +13,6 @@ use output::ScenarioOutput;
 use serde::ser::SerializeStruct;
 use serde::Serialize;
 use serde::Serializer;
-use tracing::warn;
 
 use crate::console::plural;
 use crate::process::Exit;
@@ -30,8 +29,11 @@ use crate::{exit_code, output, Options, Result, Scenario};
 /// 3. `cargo tests` -- do the tests pass?
 ///
 /// Some scenarios such as freshening the tree don't run the tests.
+///
+/// If `--pre-mutant-command` is set, it runs as the first phase of each mutant.
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize)]
 pub enum Phase {
+    PreMutant,
     Check,
     Build,
     Test,
@@ -40,6 +42,7 @@ pub enum Phase {
 impl Phase {
     pub fn name(self) -> &'static str {
         match self {
+            Phase::PreMutant => "pre-mutant",
             Phase::Check => "check",
             Phase::Build => "build",
             Phase::Test => "test",
@@ -65,6 +68,17 @@ pub struct LabOutcome {
     pub timeout: usize,
     pub unviable: usize,
     pub success: usize,
+    /// Mutants that could not be tested because the pre-mutant command failed.
+    #[serde(skip_serializing_if = "is_zero")]
+    pub failed: usize,
+    /// Mutants that were not tested because the total timeout was reached.
+    #[serde(skip_serializing_if = "is_zero")]
+    pub unfinished: usize,
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)] // required by serde
+fn is_zero(n: &usize) -> bool {
+    *n == 0
 }
 
 impl LabOutcome {
@@ -82,10 +96,7 @@ impl LabOutcome {
                 SummaryOutcome::Timeout => self.timeout += 1,
                 SummaryOutcome::Unviable => self.unviable += 1,
                 SummaryOutcome::Success => self.success += 1,
-                SummaryOutcome::Failure => {
-                    // We don't expect to see failures that don't fit into the other categories.
-                    warn!("Unclassified failure for mutant {:?}", outcome.scenario);
-                }
+                SummaryOutcome::Failure => self.failed += 1,
             }
         }
         self.outcomes.push(outcome);
@@ -100,9 +111,11 @@ impl LabOutcome {
             .any(|o| !o.scenario.is_mutant() && !o.success())
         {
             exit_code::BASELINE_FAILED
+        } else if self.unfinished > 0 {
+            exit_code::UNFINISHED
         } else if self.timeout > 0 {
             exit_code::TIMEOUT
-        } else if self.missed > 0 {
+        } else if self.missed > 0 || self.failed > 0 {
             exit_code::FOUND_PROBLEMS
         } else {
             exit_code::SUCCESS
@@ -136,6 +149,15 @@ impl LabOutcome {
         if self.success != 0 {
             by_outcome.push(format!("{} succeeded", self.success));
         }
+        if self.failed != 0 {
+            by_outcome.push(format!("{} failed", self.failed));
+        }
+        if self.unfinished != 0 {
+            by_outcome.push(format!(
+                "{} not tested before the total timeout",
+                self.unfinished
+            ));
+        }
         s.push(by_outcome.join(", "));
         s.join("")
     }
@@ -228,9 +250,15 @@ impl ScenarioOutcome {
     }
 
     pub fn check_or_build_failed(&self) -> bool {
-        self.phase_results
-            .iter()
-            .any(|pr| pr.phase != Phase::Test && pr.process_status.is_failure())
+        self.phase_results.iter().any(|pr| {
+            matches!(pr.phase, Phase::Check | Phase::Build) && pr.process_status.is_failure()
+        })
+    }
+
+    /// True if the pre-mutant command failed, so the mutant was not built or tested.
+    pub fn pre_mutant_failed(&self) -> bool {
+        self.phase_result(Phase::PreMutant)
+            .is_some_and(|pr| !pr.is_success())
     }
 
     /// True if this outcome is a caught mutant: it's a mutant and the tests failed.
@@ -261,7 +289,9 @@ impl ScenarioOutcome {
                 }
             }
             Scenario::Mutant(_) => {
-                if self.check_or_build_failed() {
+                if self.pre_mutant_failed() {
+                    SummaryOutcome::Failure
+                } else if self.check_or_build_failed() {
                     SummaryOutcome::Unviable
                 } else if self.has_timeout() {
                     SummaryOutcome::Timeout
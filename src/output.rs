@@ -3,7 +3,9 @@
 //! A `mutants.out` directory holding logs and other output.
 
 use std::collections::{hash_map::Entry, HashMap};
-use std::fs::{create_dir, read_to_string, remove_dir_all, rename, write, File, OpenOptions};
+use std::fs::{
+    create_dir, create_dir_all, read_to_string, remove_dir_all, rename, write, File, OpenOptions,
+};
 use std::io::{BufWriter, Write};
 use std::path::Path;
 use std::thread::sleep;
@@ -236,6 +238,18 @@ impl OutputDir {
             .with_context(|| format!("open {debug_log_path}"))
     }
 
+    /// Record mutants that were not tested because the total timeout was reached.
+    pub fn add_unfinished(&mut self, mutants: &[Mutant]) -> Result<()> {
+        self.lab_outcome.unfinished += mutants.len();
+        self.write_lab_outcome()?;
+        let mut list = String::new();
+        for mutant in mutants {
+            list.push_str(&mutant.name(true));
+            list.push('\n');
+        }
+        write(self.path.join("unfinished.txt"), list).context("write unfinished.txt")
+    }
+
     pub fn write_mutants_list(&self, mutants: &[Mutant]) -> Result<()> {
         serde_json::to_writer_pretty(
             BufWriter::new(File::create(self.path.join("mutants.json"))?),
@@ -353,6 +367,20 @@ impl ScenarioOutput {
     }
 }
 
+/// Write one patch file per mutant into `dir`, creating it if necessary.
+///
+/// Files are named by the mutant's position in the list, followed by its location.
+pub fn write_patches(mutants: &[Mutant], dir: &Utf8Path) -> Result<()> {
+    create_dir_all(dir).with_context(|| format!("create patch directory {dir:?}"))?;
+    for (i, mutant) in mutants.iter().enumerate() {
+        let path = dir.join(format!("{i:04}_{}.patch", mutant.log_file_name_base()));
+        write(&path, mutant.patch(&mutant.mutated_code()))
+            .with_context(|| format!("write patch {path:?}"))?;
+    }
+    info!("Wrote {} patches to {dir}", mutants.len());
+    Ok(())
+}
+
 pub fn clean_filename(s: &str) -> String {
     s.replace('/', "__")
         .chars()
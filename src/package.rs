@@ -2,10 +2,11 @@
 
 //! Discover and represent cargo packages within a workspace.
 
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 
 use camino::{Utf8Path, Utf8PathBuf};
-use cargo_metadata::TargetKind;
+use cargo_metadata::{DependencyKind, TargetKind};
 use itertools::Itertools;
 use serde::Serialize;
 use tracing::{debug, debug_span, warn};
@@ -30,6 +31,20 @@ pub struct Package {
     /// The top source files for this package, relative to the workspace root,
     /// like `["src/lib.rs"]`.
     pub top_sources: Vec<Utf8PathBuf>,
+
+    /// The top source files for example targets in this package, relative to the
+    /// workspace root, like `["examples/demo.rs"]`.
+    ///
+    /// These are only mutated if `--examine-examples` is set.
+    pub example_sources: Vec<Utf8PathBuf>,
+
+    /// The features declared by the package, mapping each to the features and
+    /// dependencies that it enables.
+    pub features: BTreeMap<String, Vec<String>>,
+
+    /// The names of the package's normal (not dev or build) dependencies, as they're
+    /// referred to in its code, taking account of any renames.
+    pub dependencies: BTreeSet<String>,
 }
 
 /// Read `cargo-metadata` parsed output, and produce our package representation.
@@ -66,9 +81,25 @@ impl Package {
         };
         Some(Package {
             name,
-            top_sources: package_top_sources(workspace_root, package_metadata),
+            top_sources: package_top_sources(
+                workspace_root,
+                package_metadata,
+                should_mutate_target,
+            ),
+            example_sources: package_top_sources(
+                workspace_root,
+                package_metadata,
+                is_example_target,
+            ),
             version: package_metadata.version.to_string(),
             relative_dir,
+            features: package_metadata.features.clone(),
+            dependencies: package_metadata
+                .dependencies
+                .iter()
+                .filter(|dep| dep.kind == DependencyKind::Normal)
+                .map(|dep| dep.rename.as_ref().unwrap_or(&dep.name).clone())
+                .collect(),
         })
     }
 
@@ -78,17 +109,18 @@ impl Package {
 }
 
 /// Find all the files that are named in the `path` of targets in a
-/// Cargo manifest, if the kind of the target is one that we should mutate.
+/// Cargo manifest, if the kind of the target is selected by `filter`.
 ///
 /// These are the starting points for discovering source files.
 fn package_top_sources(
     workspace_root: &Utf8Path,
     package_metadata: &cargo_metadata::Package,
+    filter: fn(&cargo_metadata::Target) -> bool,
 ) -> Vec<Utf8PathBuf> {
     let mut found = Vec::new();
     let pkg_dir = package_metadata.manifest_path.parent().unwrap();
     for target in &package_metadata.targets {
-        if should_mutate_target(target) {
+        if filter(target) {
             if let Ok(relpath) = target
                 .src_path
                 .strip_prefix(workspace_root)
@@ -129,6 +161,13 @@ fn should_mutate_target(target: &cargo_metadata::Target) -> bool {
     })
 }
 
+/// True for example targets, which are mutated only if `--examine-examples` is set.
+///
+/// Tests and benchmarks are never mutated.
+fn is_example_target(target: &cargo_metadata::Target) -> bool {
+    target.kind.contains(&TargetKind::Example)
+}
+
 /// Selection of which specific packages to mutate or test.
 #[derive(Debug, Clone)]
 #[allow(clippy::module_name_repetitions)]
@@ -152,6 +191,9 @@ impl PackageSelection {
             version: version.to_string(),
             relative_dir: relative_dir.into(),
             top_sources: vec![top_source.into()],
+            example_sources: Vec::new(),
+            features: BTreeMap::new(),
+            dependencies: BTreeSet::new(),
         })])
     }
 }
@@ -36,14 +36,14 @@ where
                     }
                 }
                 Ident(_) | Literal(_) => {
-                    if b.ends_with('=') || b.ends_with("=>") {
+                    if (b.ends_with('=') && !b.ends_with("..=")) || b.ends_with("=>") {
                         b.push(' ');
                     }
                     match tt {
                         Literal(l) => b.push_str(&l.to_string()),
                         Ident(i) => b.push_str(&i.to_string()),
                         _ => unreachable!(),
-                    };
+                    }
                     if let Some(next) = ts.peek() {
                         match next {
                             Ident(_) | Literal(_) => b.push(' '),
@@ -51,19 +51,24 @@ where
                                 ',' | ';' | '<' | '>' | ':' | '.' | '!' => (),
                                 _ => b.push(' '),
                             },
+                            Group(g) if g.delimiter() == Delimiter::Brace => b.push(' '),
                             Group(_) => (),
                         }
                     }
                 }
                 Group(g) => {
+                    let inner = g.stream().to_pretty_string();
                     match g.delimiter() {
+                        // Blocks like `async { 0 }` have spaces inside the braces.
+                        Delimiter::Brace if !inner.is_empty() => b.push_str("{ "),
                         Delimiter::Brace => b.push('{'),
                         Delimiter::Bracket => b.push('['),
                         Delimiter::Parenthesis => b.push('('),
                         Delimiter::None => (),
                     }
-                    b += &g.stream().to_pretty_string();
+                    b += &inner;
                     match g.delimiter() {
+                        Delimiter::Brace if !inner.is_empty() => b.push_str(" }"),
                         Delimiter::Brace => b.push('}'),
                         Delimiter::Bracket => b.push(']'),
                         Delimiter::Parenthesis => b.push(')'),
@@ -113,8 +118,23 @@ mod test {
         );
     }
 
+    #[test]
+    fn format_async_block() {
+        assert_eq!(
+            quote! { Box::pin(async { Ok(0) }) }.to_pretty_string(),
+            "Box::pin(async { Ok(0) })"
+        );
+        assert_eq!(quote! { async {} }.to_pretty_string(), "async {}");
+    }
+
     #[test]
     fn format_thick_arrow() {
         assert_eq!(quote! { a => b }.to_pretty_string(), "a => b");
     }
+
+    #[test]
+    fn format_ranges() {
+        assert_eq!(quote! { 0..1 }.to_pretty_string(), "0..1");
+        assert_eq!(quote! { 0..=1 }.to_pretty_string(), "0..=1");
+    }
 }
@@ -173,7 +173,7 @@ impl Exit {
 ///
 /// This isn't guaranteed to match the interpretation of a shell or to be safe.
 /// It's just for debug logs.
-fn quote_argv<S: AsRef<str>, I: IntoIterator<Item = S>>(argv: I) -> String {
+pub fn quote_argv<S: AsRef<str>, I: IntoIterator<Item = S>>(argv: I) -> String {
     let mut r = String::new();
     for s in argv {
         if !r.is_empty() {
@@ -0,0 +1,169 @@
+// Copyright 2024 Martin Pool
+
+//! Templates for the line printed for each mutant's result, from `--format`.
+
+use std::fmt::Write;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Error};
+
+use crate::mutant::Mutant;
+
+/// A parsed `--format` template, like `{outcome} {file}:{line}: {replacement}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResultFormat {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Placeholder(Placeholder),
+}
+
+/// A value that can be substituted into the template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placeholder {
+    File,
+    Line,
+    Function,
+    Genre,
+    Replacement,
+    Outcome,
+}
+
+impl FromStr for Placeholder {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "file" => Placeholder::File,
+            "line" => Placeholder::Line,
+            "function" => Placeholder::Function,
+            "genre" => Placeholder::Genre,
+            "replacement" => Placeholder::Replacement,
+            "outcome" => Placeholder::Outcome,
+            _ => bail!(
+                "unknown placeholder {{{s}}} in format: expected one of {{file}}, {{line}}, \
+                {{function}}, {{genre}}, {{replacement}}, {{outcome}}"
+            ),
+        })
+    }
+}
+
+impl FromStr for ResultFormat {
+    type Err = Error;
+
+    /// Parse a template, where `{{` and `}}` are literal braces.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.as_str().starts_with('{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.as_str().starts_with('}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let (name, rest) = chars
+                        .as_str()
+                        .split_once('}')
+                        .ok_or_else(|| anyhow!("unclosed {{ in format"))?;
+                    let placeholder = name.parse()?;
+                    chars = rest.chars();
+                    if !literal.is_empty() {
+                        parts.push(Part::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(Part::Placeholder(placeholder));
+                }
+                '}' => bail!("unmatched }} in format: use }}}} for a literal brace"),
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+        Ok(ResultFormat { parts })
+    }
+}
+
+impl ResultFormat {
+    /// Render the result line for a mutant, with the given outcome label.
+    pub fn render(&self, mutant: &Mutant, outcome: &str) -> String {
+        let mut s = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Literal(text) => s.push_str(text),
+                Part::Placeholder(Placeholder::File) => {
+                    s.push_str(&mutant.source_file.tree_relative_slashes());
+                }
+                Part::Placeholder(Placeholder::Line) => {
+                    write!(s, "{}", mutant.span.start.line).unwrap();
+                }
+                Part::Placeholder(Placeholder::Function) => {
+                    if let Some(function) = &mutant.function {
+                        s.push_str(&function.function_name);
+                    }
+                }
+                Part::Placeholder(Placeholder::Genre) => write!(s, "{:?}", mutant.genre).unwrap(),
+                Part::Placeholder(Placeholder::Replacement) => s.push_str(&mutant.replacement),
+                Part::Placeholder(Placeholder::Outcome) => s.push_str(outcome),
+            }
+        }
+        s
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use indoc::indoc;
+
+    use super::*;
+    use crate::visit::mutate_source_str;
+    use crate::Options;
+
+    #[test]
+    fn render_all_placeholders() {
+        let mutants = mutate_source_str(
+            indoc! {"
+                fn always_true() -> bool {
+                    true
+                }
+            "},
+            &Options::default(),
+        )
+        .unwrap();
+        let format: ResultFormat = "{outcome}\t{file}:{line} {{{function}}} {genre}: {replacement}"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            format.render(&mutants[0], "MISSED"),
+            "MISSED\tsrc/main.rs:2 {always_true} FnValue: false"
+        );
+    }
+
+    #[test]
+    fn invalid_templates_are_rejected() {
+        assert_eq!(
+            "{outcome} {path}"
+                .parse::<ResultFormat>()
+                .unwrap_err()
+                .to_string(),
+            "unknown placeholder {path} in format: expected one of {file}, {line}, \
+            {function}, {genre}, {replacement}, {outcome}"
+        );
+        assert_eq!(
+            "{outcome".parse::<ResultFormat>().unwrap_err().to_string(),
+            "unclosed { in format"
+        );
+        assert_eq!(
+            "outcome}".parse::<ResultFormat>().unwrap_err().to_string(),
+            "unmatched } in format: use }} for a literal brace"
+        );
+    }
+}
@@ -0,0 +1,197 @@
+// Copyright 2024 Martin Pool
+
+//! Report missed mutants as a SARIF 2.1.0 document, for code-scanning tools such as GitHub's.
+
+#![warn(clippy::pedantic)]
+
+use std::fs::write;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use itertools::Itertools;
+use serde_json::{json, Value};
+
+use crate::mutant::{Genre, Mutant};
+use crate::outcome::{LabOutcome, SummaryOutcome};
+use crate::scenario::Scenario;
+use crate::{Context, Result, VERSION};
+
+/// Write a SARIF document describing the missed mutants in a lab outcome.
+///
+/// `tree_root` is the root of the source tree that was mutated. Paths in the report are
+/// relative to the enclosing repository root, if one can be found, because that's how
+/// GitHub maps them onto files.
+pub fn write_sarif(path: &Utf8Path, lab_outcome: &LabOutcome, tree_root: &Utf8Path) -> Result<()> {
+    let missed = lab_outcome
+        .outcomes
+        .iter()
+        .filter(|outcome| outcome.summary() == SummaryOutcome::MissedMutant)
+        .filter_map(|outcome| match &outcome.scenario {
+            Scenario::Mutant(mutant) => Some(mutant),
+            Scenario::Baseline => None,
+        })
+        .collect_vec();
+    let doc = sarif_json(&missed, &repo_path_prefix(tree_root));
+    write(
+        path,
+        serde_json::to_string_pretty(&doc).expect("Serialize SARIF"),
+    )
+    .with_context(|| format!("write SARIF to {path:?}"))
+}
+
+/// Build a SARIF document with one result per mutant.
+///
+/// `path_prefix` is prepended to the tree-relative path of each source file.
+fn sarif_json(mutants: &[&Mutant], path_prefix: &str) -> Value {
+    let genres = mutants
+        .iter()
+        .map(|mutant| mutant.genre)
+        .unique()
+        .sorted_by_key(|genre| rule_id(*genre))
+        .collect_vec();
+    let rule_ids = genres.iter().map(|genre| rule_id(*genre)).collect_vec();
+    let rules = genres
+        .iter()
+        .map(|genre| {
+            json!({
+                "id": rule_id(*genre),
+                "shortDescription": { "text": format!("Missed mutant: {}", genre.description()) },
+                "helpUri": "https://mutants.rs/mutants.html",
+            })
+        })
+        .collect_vec();
+    let results = mutants
+        .iter()
+        .map(|mutant| {
+            let id = rule_id(mutant.genre);
+            let span = mutant.span;
+            json!({
+                "ruleId": id,
+                "ruleIndex": rule_ids.iter().position(|r| *r == id),
+                "level": "warning",
+                "message": {
+                    "text": format!(
+                        "Missed mutant: {} was not caught by any test",
+                        mutant.describe_change()
+                    ),
+                },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": {
+                            "uri": format!(
+                                "{path_prefix}{}",
+                                mutant.source_file.tree_relative_slashes()
+                            ),
+                            "uriBaseId": "%SRCROOT%",
+                        },
+                        "region": {
+                            "startLine": span.start.line,
+                            "startColumn": span.start.column,
+                            "endLine": span.end.line,
+                            "endColumn": span.end.column,
+                        },
+                    },
+                }],
+            })
+        })
+        .collect_vec();
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "cargo-mutants",
+                    "version": VERSION,
+                    "informationUri": "https://mutants.rs/",
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+fn rule_id(genre: Genre) -> String {
+    format!("{genre:?}")
+}
+
+/// Return the path of `tree_root` relative to the enclosing repository root, with a trailing
+/// slash, or an empty string if it is the repository root or no repository is found.
+fn repo_path_prefix(tree_root: &Utf8Path) -> String {
+    let Some(repo_root) = tree_root.ancestors().find(|dir| dir.join(".git").exists()) else {
+        return String::new();
+    };
+    let relative: Utf8PathBuf = tree_root
+        .strip_prefix(repo_root)
+        .expect("repo root is an ancestor")
+        .components()
+        .collect();
+    if relative.as_str().is_empty() {
+        String::new()
+    } else {
+        format!("{}/", relative.components().join("/"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::create_dir_all;
+
+    use camino::Utf8Path;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::{repo_path_prefix, sarif_json};
+    use crate::visit::mutate_source_str;
+    use crate::Options;
+
+    #[test]
+    fn sarif_result_for_mutant() {
+        let mutants = mutate_source_str(
+            indoc! {"
+                fn is_even(x: u32) -> bool {
+                    x % 2 == 0
+                }
+            "},
+            &Options::default(),
+        )
+        .unwrap();
+        let missed = mutants
+            .iter()
+            .filter(|m| m.replacement == "!=")
+            .collect::<Vec<_>>();
+        let doc = sarif_json(&missed, "crates/demo/");
+        assert_eq!(doc["version"], "2.1.0");
+        let run = &doc["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "cargo-mutants");
+        assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "BinaryOperator");
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["ruleId"], "BinaryOperator");
+        assert_eq!(results[0]["ruleIndex"], 0);
+        assert_eq!(
+            results[0]["message"]["text"],
+            "Missed mutant: replace == with != in is_even was not caught by any test"
+        );
+        let location = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(
+            location["artifactLocation"]["uri"],
+            "crates/demo/src/main.rs"
+        );
+        assert_eq!(location["region"]["startLine"], 2);
+        assert_eq!(location["region"]["startColumn"], 11);
+        assert_eq!(location["region"]["endLine"], 2);
+        assert_eq!(location["region"]["endColumn"], 13);
+    }
+
+    #[test]
+    fn repo_prefix_for_nested_tree() {
+        let tmp = TempDir::new().unwrap();
+        let root = Utf8Path::from_path(tmp.path()).unwrap();
+        create_dir_all(root.join(".git")).unwrap();
+        create_dir_all(root.join("crates/demo")).unwrap();
+        assert_eq!(repo_path_prefix(root), "");
+        assert_eq!(repo_path_prefix(&root.join("crates/demo")), "crates/demo/");
+    }
+}
@@ -96,7 +96,10 @@ impl SourceFile {
                 name: package_name.to_owned(),
                 relative_dir: Utf8PathBuf::new(),
                 top_sources,
+                example_sources: Vec::new(),
                 version: "0.1.0".to_owned(),
+                features: std::collections::BTreeMap::new(),
+                dependencies: std::collections::BTreeSet::new(),
             }),
             is_top,
         }
@@ -125,6 +128,7 @@ impl SourceFile {
 
 #[cfg(test)]
 mod test {
+    use std::collections::{BTreeMap, BTreeSet};
     use std::fs::File;
     use std::io::Write;
 
@@ -145,7 +149,10 @@ mod test {
             name: "imaginary-package".to_owned(),
             relative_dir: Utf8PathBuf::from(""),
             top_sources: vec!["src/lib.rs".into()],
+            example_sources: Vec::new(),
             version: "0.1.0".to_owned(),
+            features: BTreeMap::new(),
+            dependencies: BTreeSet::new(),
         };
         let source_file = SourceFile::load(temp_dir_path, Utf8Path::new(file_name), &package, true)
             .unwrap()
@@ -159,7 +166,10 @@ mod test {
             name: "imaginary-package".to_owned(),
             relative_dir: Utf8PathBuf::from(""),
             top_sources: vec!["src/lib.rs".into()],
+            example_sources: Vec::new(),
             version: "0.1.0".to_owned(),
+            features: BTreeMap::new(),
+            dependencies: BTreeSet::new(),
         });
         let source_file = SourceFile::load(
             Utf8Path::new("unimportant"),
@@ -44,8 +44,7 @@ impl TailFile {
         if n_read > 0 {
             if let Some(new_last) = String::from_utf8_lossy(&self.read_buf)
                 .lines()
-                .filter(|l| !l.trim().is_empty())
-                .last()
+                .rfind(|l| !l.trim().is_empty())
             {
                 new_last.clone_into(&mut self.last_line_seen);
             }
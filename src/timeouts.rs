@@ -7,6 +7,7 @@ use std::{cmp::max, time::Duration};
 use tracing::{info, warn};
 
 use crate::{
+    mutant::Genre,
     options::Options,
     outcome::{Phase, ScenarioOutcome},
 };
@@ -44,6 +45,22 @@ impl Timeouts {
             test: Some(test_timeout(None, options)),
         }
     }
+
+    /// Return the timeouts for a mutant of this genre, applying any per-genre test timeout.
+    pub fn for_genre(self, genre: Genre, options: &Options) -> Timeouts {
+        if let Some((_, test)) = options
+            .test_timeout_by_genre
+            .iter()
+            .find(|(g, _)| *g == genre)
+        {
+            Timeouts {
+                test: Some(*test),
+                ..self
+            }
+        } else {
+            self
+        }
+    }
 }
 
 const FALLBACK_TIMEOUT_SECS: u64 = 300;
@@ -120,6 +137,38 @@ mod test {
         );
     }
 
+    #[test]
+    fn minimum_test_timeout_floors_multiplied_baseline() {
+        let options = Options::from_arg_strs([
+            "mutants",
+            "--timeout-multiplier",
+            "2",
+            "--minimum-test-timeout",
+            "20s",
+        ]);
+
+        assert_eq!(options.minimum_test_timeout, Duration::from_secs(20));
+        assert_eq!(
+            test_timeout(Some(Duration::from_secs(1)), &options),
+            Duration::from_secs(20),
+        );
+        assert_eq!(
+            test_timeout(Some(Duration::from_secs(30)), &options),
+            Duration::from_secs(60),
+        );
+    }
+
+    #[test]
+    fn minimum_test_timeout_does_not_apply_to_explicit_timeout() {
+        let options =
+            Options::from_arg_strs(["mutants", "--timeout", "5", "--minimum-test-timeout", "20"]);
+
+        assert_eq!(
+            test_timeout(Some(Duration::from_secs(1)), &options),
+            Duration::from_secs(5),
+        );
+    }
+
     #[test]
     fn test_timeout_unaffected_by_in_place_build() {
         let options =
@@ -246,4 +295,56 @@ mod test {
         assert_eq!(test_timeout(None, &options), Duration::from_secs(300));
         assert_eq!(build_timeout(None, &options), None);
     }
+
+    #[test]
+    fn timeout_per_genre_overrides_test_timeout() {
+        let args =
+            Args::try_parse_from(["mutants", "--timeout-per-genre", "BinaryOperator=5"]).unwrap();
+        let config = Config::from_str(indoc! {r#"
+            timeout_per_genre = { BinaryOperator = 1.0, FnValue = 120.0 }
+        "#})
+        .unwrap();
+        let options = Options::new(&args, &config).unwrap();
+        let timeouts = Timeouts {
+            build: Some(Duration::from_secs(30)),
+            test: Some(Duration::from_secs(40)),
+        };
+
+        let binary = timeouts.for_genre(Genre::BinaryOperator, &options);
+        assert_eq!(binary.test, Some(Duration::from_secs(5)));
+        assert_eq!(binary.build, Some(Duration::from_secs(30)));
+        assert_eq!(
+            timeouts.for_genre(Genre::FnValue, &options).test,
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            timeouts.for_genre(Genre::MatchArm, &options).test,
+            Some(Duration::from_secs(40))
+        );
+    }
+
+    #[test]
+    fn timeout_per_genre_rejects_unknown_genre() {
+        let args =
+            Args::try_parse_from(["mutants", "--timeout-per-genre", "Comparison=5"]).unwrap();
+        let err = Options::new(&args, &Config::default()).unwrap_err();
+        assert!(
+            err.to_string().contains("Unknown genre \"Comparison\""),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn timeout_per_genre_rejects_invalid_durations() {
+        for value in ["-1", "NaN", "inf"] {
+            let arg = format!("FnValue={value}");
+            let args = Args::try_parse_from(["mutants", "--timeout-per-genre", &arg]).unwrap();
+            let err = Options::new(&args, &Config::default()).unwrap_err();
+            assert!(
+                err.to_string()
+                    .contains("--timeout-per-genre for \"FnValue\" must be a non-negative"),
+                "{err}"
+            );
+        }
+    }
 }
@@ -9,21 +9,35 @@
 
 #![warn(clippy::pedantic)]
 
-use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::RangeInclusive;
 use std::sync::Arc;
 use std::vec;
 
 use camino::{Utf8Path, Utf8PathBuf};
+use itertools::Itertools;
 use proc_macro2::{Ident, TokenStream};
 use quote::{quote, ToTokens};
+use serde::{Deserialize, Serialize};
 use syn::ext::IdentExt;
+use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::visit::Visit;
-use syn::{Attribute, BinOp, Block, Expr, ExprPath, File, ItemFn, ReturnType, Signature, UnOp};
+use syn::{
+    Attribute, BinOp, Block, Expr, ExprBinary, ExprBlock, ExprCall, ExprClosure, ExprLit,
+    ExprMethodCall, ExprParen, ExprPath, ExprRange, File, ItemFn, Lifetime, Lit, LitFloat, LitInt,
+    RangeLimits, ReturnType, Signature, Token, Type, UnOp, Visibility,
+};
 use tracing::{debug, debug_span, error, info, trace, trace_span, warn};
 
+use crate::cfg::{cfg_known_false, features_with_cargo_args, ActiveFeatures};
 use crate::console::WalkProgress;
-use crate::fnvalue::return_type_replacements;
+use crate::discovery_cache::DiscoveryCache;
+use crate::exclude_range::ExcludeRange;
+use crate::fnvalue::{
+    field_value_replacements, match_second_type_arg, replace_self_type, return_type_replacements,
+    static_value_replacements, ErrorExprs,
+};
 use crate::mutant::Function;
 use crate::package::Package;
 use crate::pretty::ToPrettyString;
@@ -51,6 +65,83 @@ impl Discovered {
             !c
         });
     }
+
+    /// Keep only the mutants with these names, from `--mutants-from-file`, warning about
+    /// any names that weren't found.
+    pub(crate) fn retain_named(&mut self, names: &[String], path: &Utf8Path) {
+        let wanted: HashSet<&str> = names.iter().map(String::as_str).collect();
+        let mut found: HashSet<String> = HashSet::new();
+        self.mutants.retain(|m| {
+            let name = m.name(true);
+            let keep = wanted.contains(name.as_str());
+            if keep {
+                found.insert(name);
+            }
+            keep
+        });
+        for name in names.iter().filter(|name| !found.contains(*name)) {
+            warn!("Mutant {name:?} from {path} was not found");
+        }
+    }
+
+    /// Keep only the mutants inside functions with exactly these names, from `--function`,
+    /// warning about any names that weren't found.
+    ///
+    /// Mutants inside a nested function belong only to the innermost function.
+    pub(crate) fn retain_functions(&mut self, function_names: &[String]) {
+        let mut found: HashSet<String> = HashSet::new();
+        self.mutants.retain(|m| {
+            let Some(function) = &m.function else {
+                return false;
+            };
+            let keep = function_names.contains(&function.function_name);
+            if keep {
+                found.insert(function.function_name.clone());
+            }
+            keep
+        });
+        for name in function_names.iter().filter(|name| !found.contains(*name)) {
+            warn!("No mutants were found in function {name:?}");
+        }
+    }
+
+    /// Warn about any files from `--examine-from-file` that weren't discovered, perhaps
+    /// because they were renamed or deleted.
+    pub(crate) fn warn_undiscovered_files(&self, files: &[Utf8PathBuf], path: &Utf8Path) {
+        for file in files {
+            if !self
+                .files
+                .iter()
+                .any(|source_file| source_file.tree_relative_path == *file)
+            {
+                warn!("File {file:?} from {path} is not one of the source files");
+            }
+        }
+    }
+
+    /// Remove mutants overlapping any of these line ranges, warning about ranges in files
+    /// that weren't discovered.
+    pub(crate) fn remove_excluded_ranges(&mut self, ranges: &[ExcludeRange]) {
+        for range in ranges {
+            if !self
+                .files
+                .iter()
+                .any(|file| file.tree_relative_slashes() == range.path)
+            {
+                warn!(
+                    path = range.path,
+                    "Exclude range file is not one of the source files"
+                );
+            }
+        }
+        self.mutants.retain(|mutant| {
+            let excluded = ranges.iter().any(|range| range.overlaps(mutant));
+            if excluded {
+                trace!(name = mutant.name(true), "skip mutant in excluded range");
+            }
+            !excluded
+        });
+    }
 }
 
 /// Discover all mutants and all source files.
@@ -67,31 +158,122 @@ pub fn walk_tree(
     let mut mutants = Vec::new();
     let mut files = Vec::new();
     let error_exprs = options.parsed_error_exprs()?;
+    let mut cache = options.discovery_cache.then(|| {
+        DiscoveryCache::load(
+            options.output_in_dir.as_deref().unwrap_or(workspace_dir),
+            options,
+        )
+    });
     let progress = console.start_walk_tree();
+    let mut files_with_mutants = 0;
     for package in packages {
-        let (mut package_mutants, mut package_files) =
-            walk_package(workspace_dir, package, &error_exprs, &progress, options)?;
+        if options
+            .max_files
+            .is_some_and(|max_files| files_with_mutants >= max_files)
+        {
+            debug!(
+                package = package.name,
+                "Reached --max-files; not walking further packages"
+            );
+            break;
+        }
+        let package_error_exprs = error_exprs.with_package_defaults(package);
+        let (mut package_mutants, mut package_files) = walk_package(
+            workspace_dir,
+            package,
+            &package_error_exprs,
+            &progress,
+            options,
+            &mut files_with_mutants,
+            cache.as_mut(),
+        )?;
         mutants.append(&mut package_mutants);
         files.append(&mut package_files);
     }
     progress.finish();
+    if let Some(cache) = cache {
+        cache.save()?;
+    }
     Ok(Discovered { mutants, files })
 }
 
 /// Walk one package, starting from its top files, discovering files
 /// and mutants.
-#[allow(clippy::from_iter_instead_of_collect)]
+///
+/// `files_with_mutants` counts source files that produced mutants, across all packages,
+/// so that the walk can stop at `--max-files`.
+///
+/// If the package defines a `Result` alias in one file, it may be used in files that were
+/// visited earlier, so if the alias's error type selects different error values, the
+/// package is walked again.
+///
+/// If `cache` is given, files that haven't changed since they were cached aren't parsed.
 fn walk_package(
     workspace_dir: &Utf8Path,
     package: &Package,
-    error_exprs: &[Expr],
+    error_exprs: &ErrorExprs,
     progress: &WalkProgress,
     options: &Options,
+    files_with_mutants: &mut usize,
+    mut cache: Option<&mut DiscoveryCache>,
 ) -> Result<(Vec<Mutant>, Vec<SourceFile>)> {
+    let files_with_mutants_before = *files_with_mutants;
+    let (mutants, files, result_alias_errors) = walk_package_files(
+        workspace_dir,
+        package,
+        error_exprs,
+        progress,
+        options,
+        files_with_mutants,
+        cache.as_deref_mut(),
+    )?;
+    if let Some(alias_error_exprs) = error_exprs.with_result_alias(&result_alias_errors) {
+        debug!(
+            package = package.name,
+            "Walking package again with error values for its Result alias"
+        );
+        *files_with_mutants = files_with_mutants_before;
+        let (mutants, files, _) = walk_package_files(
+            workspace_dir,
+            package,
+            &alias_error_exprs,
+            progress,
+            options,
+            files_with_mutants,
+            cache,
+        )?;
+        return Ok((mutants, files));
+    }
+    Ok((mutants, files))
+}
+
+/// Walk the files of one package, returning the mutants, the source files, and the error
+/// types of any `Result` aliases defined in the package.
+#[allow(clippy::from_iter_instead_of_collect)]
+fn walk_package_files(
+    workspace_dir: &Utf8Path,
+    package: &Package,
+    error_exprs: &ErrorExprs,
+    progress: &WalkProgress,
+    options: &Options,
+    files_with_mutants: &mut usize,
+    mut cache: Option<&mut DiscoveryCache>,
+) -> Result<(Vec<Mutant>, Vec<SourceFile>, Vec<Type>)> {
     let mut mutants = Vec::new();
     let mut files = Vec::new();
-    let mut filename_queue =
-        VecDeque::from_iter(package.top_sources.iter().map(|p| (p.to_owned(), true)));
+    let mut result_alias_errors = Vec::new();
+    let example_sources = if options.examine_examples {
+        package.example_sources.as_slice()
+    } else {
+        &[]
+    };
+    let mut filename_queue = VecDeque::from_iter(
+        package
+            .top_sources
+            .iter()
+            .chain(example_sources)
+            .map(|p| (p.to_owned(), true)),
+    );
     while let Some((path, package_top)) = filename_queue.pop_front() {
         let Some(source_file) = SourceFile::load(workspace_dir, &path, package, package_top)?
         else {
@@ -100,7 +282,11 @@ fn walk_package(
         };
         progress.increment_files(1);
         check_interrupted()?;
-        let (mut file_mutants, external_mods) = walk_file(&source_file, error_exprs, options)?;
+        let (mut file_mutants, external_mods, mut file_alias_errors) = match cache.as_deref_mut() {
+            Some(cache) => cache.walk_file(&source_file, error_exprs, options)?,
+            None => walk_file(&source_file, error_exprs, options)?,
+        };
+        result_alias_errors.append(&mut file_alias_errors);
         file_mutants.retain(|m| options.allows_mutant(m));
         progress.increment_mutants(file_mutants.len());
         // TODO: It would be better not to spend time generating mutants from
@@ -119,39 +305,116 @@ fn walk_package(
             }
         }
         if !options.allows_source_file_path(&source_file.tree_relative_path) {
-            continue;
+            // Mutants matching `always_examine_names` are kept even from excluded files.
+            file_mutants.retain(|m| options.always_examines_mutant(m));
+            if file_mutants.is_empty() {
+                continue;
+            }
+        }
+        if !file_mutants.is_empty() {
+            *files_with_mutants += 1;
         }
         mutants.append(&mut file_mutants);
         files.push(source_file);
+        if options
+            .max_files
+            .is_some_and(|max_files| *files_with_mutants >= max_files)
+        {
+            debug!("Reached --max-files; not walking any more files");
+            break;
+        }
     }
-    Ok((mutants, files))
+    Ok((mutants, files, result_alias_errors))
 }
 
 /// Find all possible mutants in a source file.
 ///
-/// Returns the mutants found, and the names of modules referenced by `mod` statements
-/// that should be visited later.
-fn walk_file(
+/// Returns the mutants found, the names of modules referenced by `mod` statements
+/// that should be visited later, and the error types of `Result` aliases defined in the file.
+pub(crate) fn walk_file(
     source_file: &SourceFile,
-    error_exprs: &[Expr],
+    error_exprs: &ErrorExprs,
     options: &Options,
-) -> Result<(Vec<Mutant>, Vec<ExternalModRef>)> {
+) -> Result<(Vec<Mutant>, Vec<ExternalModRef>, Vec<Type>)> {
     let _span = debug_span!("source_file", path = source_file.tree_relative_slashes()).entered();
     debug!("visit source file");
     let syn_file = syn::parse_str::<syn::File>(source_file.code())
         .with_context(|| format!("failed to parse {}", source_file.tree_relative_slashes()))?;
+    let result_alias_errors = result_alias_error_types(&syn_file);
+    let alias_error_exprs = error_exprs.with_result_alias(&result_alias_errors);
     let mut visitor = DiscoveryVisitor {
-        error_exprs,
+        error_exprs: alias_error_exprs.as_ref().unwrap_or(error_exprs),
         external_mods: Vec::new(),
         mutants: Vec::new(),
         mod_namespace_stack: Vec::new(),
         namespace_stack: Vec::new(),
         fn_stack: Vec::new(),
+        fn_arg_types: Vec::new(),
+        in_trait_impl: false,
+        in_const_generic_arg: false,
+        impl_self_ty: None,
+        fn_value_replacements: HashSet::new(),
+        loop_labels: Vec::new(),
+        struct_fields: struct_fields(&syn_file),
+        fn_return_types: fn_return_types(&syn_file),
+        in_place_expr: false,
+        active_features: ActiveFeatures::for_package(
+            &source_file.package,
+            &features_with_cargo_args(
+                &options.features,
+                options
+                    .additional_cargo_args
+                    .iter()
+                    .chain(&options.additional_cargo_test_args),
+            ),
+        ),
         source_file: source_file.clone(),
         options,
     };
     visitor.visit_file(&syn_file);
-    Ok((visitor.mutants, visitor.external_mods))
+    let skipped_lines = pragma_skipped_lines(source_file);
+    if !skipped_lines.is_empty() {
+        visitor.mutants.retain(|mutant| {
+            !skipped_lines.iter().any(|lines| {
+                lines.contains(&mutant.span.start.line) && lines.contains(&mutant.span.end.line)
+            })
+        });
+    }
+    Ok((visitor.mutants, visitor.external_mods, result_alias_errors))
+}
+
+/// Find ranges of lines, numbered from 1, where mutation is disabled by comment pragmas.
+///
+/// `// mutants:skip-file` disables mutation of the whole file. Mutation is disabled
+/// from a `// mutants:skip-start` line through the next `// mutants:skip-end` line,
+/// or to the end of the file if there is no end marker.
+///
+/// `syn` discards comments, so this scans the source text.
+fn pragma_skipped_lines(source_file: &SourceFile) -> Vec<RangeInclusive<usize>> {
+    let path = source_file.tree_relative_slashes();
+    let mut ranges = Vec::new();
+    let mut region_start = None;
+    for (line_number, line) in (1..).zip(source_file.code().lines()) {
+        match line.trim() {
+            "// mutants:skip-file" => return vec![1..=usize::MAX],
+            "// mutants:skip-start" => {
+                region_start.get_or_insert(line_number);
+            }
+            "// mutants:skip-end" => {
+                if let Some(start) = region_start.take() {
+                    ranges.push(start..=line_number);
+                } else {
+                    warn!(%path, line_number, "mutants:skip-end without mutants:skip-start");
+                }
+            }
+            _ => (),
+        }
+    }
+    if let Some(start) = region_start {
+        warn!(%path, line_number = start, "mutants:skip-start without mutants:skip-end");
+        ranges.push(start..=usize::MAX);
+    }
+    ranges
 }
 
 /// For testing: parse and generate mutants from one single file provided as a string.
@@ -165,7 +428,8 @@ pub fn mutate_source_str(code: &str, options: &Options) -> Result<Vec<Mutant>> {
         "cargo-mutants-testdata-internal",
         true,
     );
-    let (mutants, _) = walk_file(&source_file, &options.parsed_error_exprs()?, options)?;
+    let (mutants, _external_mods, _result_alias_errors) =
+        walk_file(&source_file, &options.parsed_error_exprs()?, options)?;
     Ok(mutants)
 }
 
@@ -174,8 +438,8 @@ pub fn mutate_source_str(code: &str, options: &Options) -> Result<Vec<Mutant>> {
 /// This is approximately a list of namespace components like `["foo", "bar"]` for
 /// `foo::bar`, but each may also be decorated with a `#[path="..."]` attribute,
 /// and they're attributed to a location in the source.
-#[derive(Clone, Debug, PartialEq, Eq)]
-struct ExternalModRef {
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ExternalModRef {
     /// Namespace components of the module path
     parts: Vec<ModNamespace>,
 }
@@ -187,7 +451,7 @@ struct ExternalModRef {
 /// folder and file names on the filesystem (e.g. `src/foo/bar.rs`).
 ///
 /// The field and method names in this struct distinguish between the uses of path elements.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 struct ModNamespace {
     /// Identifier of the module (e.g. `foo` for `mod foo;`)
     name: String,
@@ -243,31 +507,69 @@ struct DiscoveryVisitor<'o> {
     /// there are nested functions.
     fn_stack: Vec<Arc<Function>>,
 
+    /// The types of the simple named arguments of each function in `fn_stack`.
+    fn_arg_types: Vec<HashMap<String, Type>>,
+
+    /// True while visiting the items of an `impl Trait for Type` block.
+    in_trait_impl: bool,
+
+    /// True while visiting a const generic argument, like the `{ 2 * N }` in
+    /// `Buf<{ 2 * N }>`, where literals must stay constant.
+    in_const_generic_arg: bool,
+
+    /// The type of the `impl` block we're inside, if any, used to interpret `Self`.
+    impl_self_ty: Option<Type>,
+
+    /// The spans and replacements of the function value mutants generated so far, so
+    /// that a body that's just a bool literal isn't also mutated as a literal.
+    fn_value_replacements: HashSet<(Span, String)>,
+
+    /// The labels of the loops around the current expression, innermost last, with
+    /// `None` for unlabeled loops. This is cleared inside closures and async blocks,
+    /// which `break` and `continue` can't cross.
+    loop_labels: Vec<Option<Lifetime>>,
+
+    /// The types of the named fields of structs defined in this file, by struct name then
+    /// field name, used to generate values for reads of `self.field`.
+    struct_fields: HashMap<String, HashMap<String, Type>>,
+
+    /// The return types of the functions and methods defined in this file, by name,
+    /// leaving out names that are defined more than once with different types.
+    fn_return_types: HashMap<String, Type>,
+
+    /// True while visiting an expression that's assigned to or borrowed mutably, where
+    /// a field can't be replaced by a value.
+    in_place_expr: bool,
+
+    /// The features enabled in the package being visited, used to skip modules that
+    /// won't be built.
+    active_features: ActiveFeatures,
+
     /// The names from `mod foo;` statements that should be visited later,
     /// namespaced relative to the source file
     external_mods: Vec<ExternalModRef>,
 
     /// Parsed error expressions, from the config file or command line.
-    error_exprs: &'o [Expr],
+    error_exprs: &'o ErrorExprs,
 
     options: &'o Options,
 }
 
 impl DiscoveryVisitor<'_> {
-    fn enter_function(
-        &mut self,
-        function_name: &Ident,
-        return_type: &ReturnType,
-        span: proc_macro2::Span,
-    ) -> Arc<Function> {
+    fn enter_function(&mut self, sig: &Signature, span: proc_macro2::Span) -> Arc<Function> {
+        let function_name = &sig.ident;
+        let namespace = self.namespace_stack.clone();
         self.namespace_stack.push(function_name.to_string());
         let full_function_name = self.namespace_stack.join("::");
         let function = Arc::new(Function {
             function_name: full_function_name,
-            return_type: return_type.to_pretty_string(),
+            namespace,
+            name: function_name.to_string(),
+            return_type: sig.output.to_pretty_string(),
             span: span.into(),
         });
         self.fn_stack.push(Arc::clone(&function));
+        self.fn_arg_types.push(arg_types(sig));
         function
     }
 
@@ -280,23 +582,147 @@ impl DiscoveryVisitor<'_> {
             Some(function),
             "Function stack mismatch"
         );
+        self.fn_arg_types
+            .pop()
+            .expect("Argument types stack should not be empty");
     }
 
     /// Record that we generated some mutants.
     fn collect_mutant(&mut self, span: Span, replacement: &TokenStream, genre: Genre) {
+        self.collect_mutant_text(span, replacement.to_pretty_string(), genre);
+    }
+
+    /// Record a mutant whose replacement is already formatted as text.
+    ///
+    /// Replacements that are the same as the original code at the span, such as `0` in a
+    /// function that already returns `0`, are skipped, since they can never be caught.
+    fn collect_mutant_text(&mut self, span: Span, replacement: String, genre: Genre) {
+        if !replacement.is_empty()
+            && same_code(&span.extract(self.source_file.code()), &replacement)
+        {
+            trace!(
+                ?genre,
+                replacement,
+                "Replacement is the same as the original code; skipping"
+            );
+            return;
+        }
         self.mutants.push(Mutant {
             source_file: self.source_file.clone(),
             function: self.fn_stack.last().cloned(),
             span,
-            replacement: replacement.to_pretty_string(),
+            replacement,
             genre,
         });
     }
 
+    /// Visit an expression that's assigned to or mutably borrowed, so fields in it
+    /// aren't replaced by values.
+    fn visit_place_expr(&mut self, expr: &Expr) {
+        let outer = std::mem::replace(&mut self.in_place_expr, true);
+        self.visit_expr(expr);
+        self.in_place_expr = outer;
+    }
+
+    /// The type of `self.field` in a method of a struct defined in this file.
+    fn self_field_type(&self, i: &syn::ExprField) -> Option<&Type> {
+        let Expr::Path(ExprPath {
+            qself: None, path, ..
+        }) = i.base.as_ref()
+        else {
+            return None;
+        };
+        let syn::Member::Named(field) = &i.member else {
+            return None;
+        };
+        let Some(Type::Path(syn::TypePath {
+            qself: None,
+            path: self_path,
+        })) = &self.impl_self_ty
+        else {
+            return None;
+        };
+        if !path.is_ident("self") || self.fn_stack.is_empty() {
+            return None;
+        }
+        self.struct_fields
+            .get(&self_path.segments.last()?.ident.to_string())?
+            .get(&field.to_string())
+    }
+
+    /// Flag `.map(|x| x)` calls whose closure returns its argument, remove `.and_then`,
+    /// or make a predicate constant, to check that the transformation or filtering matters.
+    ///
+    /// Types aren't known here, so many of these will be unviable.
+    fn collect_combinator_mutants(&mut self, i: &ExprMethodCall) {
+        let arg = &i.args[0];
+        // Delete the whole call, leaving the receiver.
+        let call_span = Span {
+            start: i.dot_token.span.start().into(),
+            end: i.paren_token.span.close().end().into(),
+        };
+        if i.method == "map" {
+            // An identity closure does nothing, so deleting the call always builds, and
+            // a missed mutant points out the redundant call.
+            if is_identity_closure(arg) {
+                self.collect_mutant(call_span, &TokenStream::new(), Genre::CombinatorTweak);
+            }
+        } else if PREDICATE_METHODS.iter().any(|name| i.method == name) {
+            for value in [true, false] {
+                if !is_constant_closure(arg, value) {
+                    self.collect_mutant_text(
+                        arg.span().into(),
+                        format!("|_| {value}"),
+                        Genre::PredicateConst,
+                    );
+                }
+            }
+        } else if i.method == "and_then" {
+            // This builds if the closure returns the same type it's given.
+            self.collect_mutant(call_span, &TokenStream::new(), Genre::CombinatorTweak);
+        }
+    }
+
+    /// Replace the format string and arguments of a `write!` or `writeln!` call with `""`,
+    /// so that nothing is written, to check that the output is tested.
+    fn collect_format_mutant(&mut self, i: &syn::Macro) {
+        let Ok(args) = i.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated) else {
+            trace!("Can't parse write macro arguments");
+            return;
+        };
+        let (Some(format), Some(last)) = (args.iter().nth(1), args.last()) else {
+            return;
+        };
+        if args.len() == 2
+            && matches!(format, Expr::Lit(ExprLit { lit: Lit::Str(s), .. }) if s.value().is_empty())
+        {
+            return;
+        }
+        let span = Span {
+            start: format.span().start().into(),
+            end: last.span().end().into(),
+        };
+        self.collect_mutant_text(span, "\"\"".to_owned(), Genre::FormatMutation);
+    }
+
     fn collect_fn_mutants(&mut self, sig: &Signature, block: &Block) {
         if let Some(function) = self.fn_stack.last().cloned() {
             let body_span = function_body_span(block).expect("Empty function body");
-            let repls = return_type_replacements(&sig.output, self.error_exprs);
+            let return_type = if let Some(self_ty) = &self.impl_self_ty {
+                replace_self_type(&sig.output, self_ty)
+            } else {
+                sig.output.clone()
+            };
+            let mut repls = return_type_replacements(
+                &return_type,
+                self.error_exprs,
+                self.options.value_options(),
+            );
+            if (self.options.unit_panic && return_type_is_unit(&return_type))
+                || (self.options.panic_mutants && return_type_is_unit_result(&return_type))
+            {
+                repls.push(quote! { panic!("mutated!") });
+            }
             if repls.is_empty() {
                 debug!(
                     function_name = function.function_name,
@@ -304,30 +730,260 @@ impl DiscoveryVisitor<'_> {
                     "No mutants generated for this return type"
                 );
             } else {
-                let orig_block = block.to_token_stream().to_pretty_string();
                 for rep in repls {
-                    // Comparing strings is a kludge for proc_macro2 not (yet) apparently
-                    // exposing any way to compare token streams...
-                    //
-                    // TODO: Maybe this should move into collect_mutant, but at the moment
-                    // FnValue is the only genre that seems able to generate no-ops.
-                    //
-                    // The original block has braces and the replacements don't, so put
-                    // them back for the comparison...
-                    let new_block = quote!( { #rep } ).to_token_stream().to_pretty_string();
-                    // dbg!(&orig_block, &new_block);
-                    if orig_block == new_block {
-                        debug!("Replacement is the same as the function body; skipping");
-                    } else {
-                        self.collect_mutant(body_span, &rep, Genre::FnValue);
-                    }
+                    let replacement = rep.to_pretty_string();
+                    self.fn_value_replacements
+                        .insert((body_span, replacement.clone()));
+                    self.collect_mutant_text(body_span, replacement, Genre::FnValue);
                 }
             }
+            if self.options.replace_args_with_default {
+                self.collect_arg_default_mutants(sig, block);
+            }
         } else {
             warn!("collect_fn_mutants called while not in a function?");
         }
     }
 
+    /// Force the other branch of `if let Ok(x) = result` or `if let Err(e) = result`.
+    ///
+    /// For `Ok` patterns, the scrutinee is made to fail with each of the error values for
+    /// its error type, or `Default::default()` if there are none, and also to succeed with
+    /// a default value. For `Err` patterns, it's made to succeed with a default value. The
+    /// original expression is kept, and adapted with `and_then` or `or_else`, so that the
+    /// compiler still knows both the `Ok` and `Err` types.
+    fn collect_result_branch_mutants(&mut self, expr_let: &syn::ExprLet) {
+        let Some(variant) = result_pattern_variant(&expr_let.pat) else {
+            return;
+        };
+        if matches!(&*expr_let.expr, Expr::Reference(_)) {
+            // Methods on `&Result` would need to move out of the reference.
+            return;
+        }
+        let span: Span = expr_let.expr.span().into();
+        let text = span.extract(self.source_file.code());
+        // Parenthesize anything that might bind more loosely than a method call.
+        let text = if matches!(
+            &*expr_let.expr,
+            Expr::Call(_)
+                | Expr::MethodCall(_)
+                | Expr::Path(_)
+                | Expr::Field(_)
+                | Expr::Paren(_)
+                | Expr::Try(_)
+                | Expr::Macro(_)
+                | Expr::Index(_)
+        ) {
+            text
+        } else {
+            format!("({text})")
+        };
+        let error_type = self
+            .declared_type(&expr_let.expr)
+            .and_then(|type_| self.error_exprs.result_error_type(type_));
+        let replacements = if variant == "Ok" {
+            let mut error_values = self
+                .error_exprs
+                .for_error_type(error_type)
+                .into_iter()
+                .map(ToPrettyString::to_pretty_string)
+                .collect_vec();
+            if error_values.is_empty() {
+                error_values.push("Default::default()".to_owned());
+            }
+            error_values
+                .into_iter()
+                .map(|error| format!("{text}.and_then(|_| Err({error}))"))
+                .chain([format!(
+                    "{text}.or_else(|_| Ok::<_, ()>(Default::default()))"
+                )])
+                .collect_vec()
+        } else {
+            // The error type is still needed when `e` is used in the `Err` branch, so name
+            // it if we know it.
+            let ok = match error_type {
+                Some(error_type) => format!("Ok::<_, {}>", error_type.to_pretty_string()),
+                None => "Ok".to_owned(),
+            };
+            vec![format!("{text}.or_else(|_| {ok}(Default::default()))")]
+        };
+        for replacement in replacements {
+            self.collect_mutant_text(span, replacement, Genre::ResultBranch);
+        }
+    }
+
+    /// The declared type of an expression, if it's an argument of the enclosing function,
+    /// or a call to a function or method defined in this file.
+    fn declared_type(&self, expr: &Expr) -> Option<&Type> {
+        match expr {
+            Expr::Path(ExprPath {
+                qself: None, path, ..
+            }) => self
+                .fn_arg_types
+                .last()?
+                .get(&path.get_ident()?.to_string()),
+            Expr::Call(ExprCall { func, .. }) => match &**func {
+                Expr::Path(ExprPath { path, .. }) => self
+                    .fn_return_types
+                    .get(&path.segments.last()?.ident.to_string()),
+                _ => None,
+            },
+            Expr::MethodCall(ExprMethodCall { method, .. }) => {
+                self.fn_return_types.get(&method.to_string())
+            }
+            Expr::Paren(ExprParen { expr, .. }) => self.declared_type(expr),
+            _ => None,
+        }
+    }
+
+    /// Replace the only use of a parameter with `Default::default()`, to check whether
+    /// the parameter influences the result.
+    ///
+    /// Parameters used more than once, or whose uses can't be reliably found, for
+    /// example because they're mentioned inside a macro, are skipped.
+    fn collect_arg_default_mutants(&mut self, sig: &Signature, block: &Block) {
+        for input in &sig.inputs {
+            let syn::FnArg::Typed(pat_type) = input else {
+                continue;
+            };
+            let syn::Pat::Ident(pat_ident) = pat_type.pat.as_ref() else {
+                continue;
+            };
+            if !type_has_known_default(&pat_type.ty) {
+                continue;
+            }
+            let mut uses = ArgUses {
+                ident: &pat_ident.ident,
+                spans: Vec::new(),
+                unknown: false,
+            };
+            uses.visit_block(block);
+            match uses.spans.as_slice() {
+                [span] if !uses.unknown => {
+                    self.collect_mutant(
+                        *span,
+                        &quote! { Default::default() },
+                        Genre::ReplaceArgWithDefault,
+                    );
+                }
+                _ => trace!(
+                    arg = %pat_ident.ident,
+                    n_uses = uses.spans.len(),
+                    uses.unknown,
+                    "not replacing arg with default"
+                ),
+            }
+        }
+    }
+
+    /// Generate values one below and one above a literal const generic default.
+    fn collect_const_default_mutants(&mut self, lit: &LitInt) {
+        let Ok(value) = lit.base10_parse::<u128>() else {
+            return;
+        };
+        let suffix = lit.suffix();
+        for adjacent in [value.checked_sub(1), value.checked_add(1)]
+            .into_iter()
+            .flatten()
+        {
+            let replacement = LitInt::new(&format!("{adjacent}{suffix}"), lit.span());
+            self.collect_mutant(
+                lit.span().into(),
+                &replacement.to_token_stream(),
+                Genre::ConstDefault,
+            );
+        }
+    }
+
+    /// Replace a float literal with `0.0`, `1.0`, and its negation, skipping any that
+    /// have the same value as the original.
+    fn collect_float_literal_mutants(&mut self, lit: &LitFloat) {
+        let Ok(value) = lit.base10_parse::<f64>() else {
+            return;
+        };
+        let suffix = lit.suffix();
+        let digits = lit.base10_digits();
+        let replacements = [
+            (0.0, format!("0.0{suffix}")),
+            (1.0, format!("1.0{suffix}")),
+            (-value, format!("-{digits}{suffix}")),
+        ];
+        for (new_value, replacement) in replacements {
+            #[allow(clippy::float_cmp)] // Exact comparison is intended: we want a change.
+            if new_value != value {
+                self.collect_mutant_text(lit.span().into(), replacement, Genre::LiteralFloat);
+            }
+        }
+    }
+
+    /// Visit the body of a loop with this label.
+    fn in_loop<F>(&mut self, label: Option<&syn::Label>, f: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        self.loop_labels.push(label.map(|label| label.name.clone()));
+        f(self);
+        self.loop_labels.pop();
+    }
+
+    /// True if `--pub-only` is set and an item with this visibility should be skipped.
+    ///
+    /// Methods in trait impls can't have their own visibility, so they're treated as
+    /// public.
+    fn vis_excluded(&self, vis: &Visibility) -> bool {
+        self.options.pub_only && matches!(vis, Visibility::Inherited) && !self.in_trait_impl
+    }
+
+    /// True if the item has a `#[cfg(...)]` that's false for the features being built,
+    /// so it won't be compiled and its mutants could never be tested.
+    fn cfg_excluded(&self, attrs: &[Attribute]) -> bool {
+        if cfg_known_false(attrs, &self.active_features) {
+            debug!("item excluded by cfg for the active features");
+            true
+        } else {
+            false
+        }
+    }
+
+    /// True if the signature of a function is such that it should be excluded.
+    ///
+    /// `unsafe fn`s are skipped unless `--mutate-unsafe` is given.
+    fn fn_sig_excluded(&self, sig: &syn::Signature) -> bool {
+        if sig.unsafety.is_some() && !self.options.mutate_unsafe {
+            trace!("Skip unsafe fn");
+            true
+        } else {
+            false
+        }
+    }
+
+    /// True if a trait method has one of the names in `--skip-trait-methods`.
+    fn trait_method_skipped(&self, ident: &Ident) -> bool {
+        if let Some(hit) = self
+            .options
+            .skip_trait_methods
+            .iter()
+            .find(|name| ident == name)
+        {
+            trace!("skip trait method {hit}");
+            true
+        } else {
+            false
+        }
+    }
+
+    /// True if the function body is too small to be worth mutating, according to
+    /// `--min-statements`.
+    fn body_too_small(&self, block: &Block) -> bool {
+        let size = block_size(block, self.options.count_match_arms);
+        if size < self.options.min_statements {
+            trace!(size, "function body is smaller than --min-statements");
+            true
+        } else {
+            false
+        }
+    }
+
     /// Call a function with a namespace pushed onto the stack.
     ///
     /// This is used when recursively descending into a namespace.
@@ -359,6 +1015,26 @@ impl<'ast> Visit<'ast> for DiscoveryVisitor<'_> {
                 trace!("skip call to {hit}");
                 return;
             }
+            // Swap calls like `std::cmp::min(a, b)` or `f64::max(a, b)` to the other function.
+            // Transposing the arguments would usually give the same result, so isn't done.
+            if let Some(last) = path.segments.last() {
+                if !self.fn_stack.is_empty() && i.args.len() == 2 && last.arguments.is_none() {
+                    let replacement = if last.ident == "min" {
+                        Some("max")
+                    } else if last.ident == "max" {
+                        Some("min")
+                    } else {
+                        None
+                    };
+                    if let Some(replacement) = replacement {
+                        self.collect_mutant_text(
+                            last.ident.span().into(),
+                            replacement.to_owned(),
+                            Genre::CmpFnSwap,
+                        );
+                    }
+                }
+            }
         }
         syn::visit::visit_expr_call(self, i);
     }
@@ -372,6 +1048,60 @@ impl<'ast> Visit<'ast> for DiscoveryVisitor<'_> {
             trace!("skip method call to {hit}");
             return;
         }
+        if i.args.len() == 1 {
+            let replacement = if i.method == "min" {
+                Some(quote! { max })
+            } else if i.method == "max" {
+                Some(quote! { min })
+            } else {
+                None
+            };
+            if let Some(replacement) = replacement {
+                self.collect_mutant(i.method.span().into(), &replacement, Genre::MinMaxSwap);
+            }
+        }
+        if i.method == "clone" && i.args.is_empty() && i.turbofish.is_none() {
+            // Delete from the dot through the closing paren, leaving the receiver.
+            let span = Span {
+                start: i.dot_token.span.start().into(),
+                end: i.paren_token.span.close().end().into(),
+            };
+            self.collect_mutant(span, &TokenStream::new(), Genre::CloneRemoval);
+        }
+        // Iterator adapters that return an iterator over the same items can be removed
+        // to check that tests depend on the order or the selection of items.
+        // `.enumerate()` changes the item type, so usually won't build without it.
+        let is_iter_adapter = match i.method.to_string().as_str() {
+            "rev" | "enumerate" => i.args.is_empty(),
+            "skip" | "take" => i.args.len() == 1,
+            _ => false,
+        };
+        if is_iter_adapter && i.turbofish.is_none() {
+            let span = Span {
+                start: i.dot_token.span.start().into(),
+                end: i.paren_token.span.close().end().into(),
+            };
+            self.collect_mutant(span, &TokenStream::new(), Genre::IterAdapter);
+        }
+        if i.args.len() == 1 && i.turbofish.is_none() {
+            self.collect_combinator_mutants(i);
+            if i.method == "unwrap_or" {
+                for replacement in unwrap_or_replacements(&i.args[0]) {
+                    self.collect_mutant(
+                        i.args[0].span().into(),
+                        &replacement,
+                        Genre::UnwrapOrDefault,
+                    );
+                }
+            }
+            if let Some(replacement) = overflow_policy_swap(&i.method.to_string()) {
+                self.collect_mutant_text(
+                    i.method.span().into(),
+                    replacement,
+                    Genre::OverflowPolicy,
+                );
+            }
+        }
         syn::visit::visit_expr_method_call(self, i);
     }
 
@@ -395,13 +1125,22 @@ impl<'ast> Visit<'ast> for DiscoveryVisitor<'_> {
         )
         .entered();
         trace!("visit fn");
-        if fn_sig_excluded(&i.sig) || attrs_excluded(&i.attrs) || block_is_empty(&i.block) {
-            return;
+        // A function nested inside a trait method is not itself part of the trait impl.
+        let outer_in_trait_impl = self.in_trait_impl;
+        self.in_trait_impl = false;
+        if !(self.fn_sig_excluded(&i.sig)
+            || attrs_excluded(&i.attrs)
+            || self.cfg_excluded(&i.attrs)
+            || block_is_empty(&i.block)
+            || self.body_too_small(&i.block)
+            || self.vis_excluded(&i.vis))
+        {
+            let function = self.enter_function(&i.sig, i.span());
+            self.collect_fn_mutants(&i.sig, &i.block);
+            syn::visit::visit_item_fn(self, i);
+            self.leave_function(function);
         }
-        let function = self.enter_function(&i.sig.ident, &i.sig.output, i.span());
-        self.collect_fn_mutants(&i.sig, &i.block);
-        syn::visit::visit_item_fn(self, i);
-        self.leave_function(function);
+        self.in_trait_impl = outer_in_trait_impl;
     }
 
     /// Visit `fn foo()` within an `impl`.
@@ -415,14 +1154,18 @@ impl<'ast> Visit<'ast> for DiscoveryVisitor<'_> {
             name = function_name
         )
         .entered();
-        if fn_sig_excluded(&i.sig)
+        if self.fn_sig_excluded(&i.sig)
             || attrs_excluded(&i.attrs)
+            || self.cfg_excluded(&i.attrs)
             || i.sig.ident == "new"
+            || (self.in_trait_impl && self.trait_method_skipped(&i.sig.ident))
             || block_is_empty(&i.block)
+            || self.body_too_small(&i.block)
+            || self.vis_excluded(&i.vis)
         {
             return;
         }
-        let function = self.enter_function(&i.sig.ident, &i.sig.output, i.span());
+        let function = self.enter_function(&i.sig, i.span());
         self.collect_fn_mutants(&i.sig, &i.block);
         syn::visit::visit_impl_item_fn(self, i);
         self.leave_function(function);
@@ -437,14 +1180,19 @@ impl<'ast> Visit<'ast> for DiscoveryVisitor<'_> {
             name = function_name
         )
         .entered();
-        if fn_sig_excluded(&i.sig) || attrs_excluded(&i.attrs) || i.sig.ident == "new" {
+        if self.fn_sig_excluded(&i.sig)
+            || attrs_excluded(&i.attrs)
+            || self.cfg_excluded(&i.attrs)
+            || i.sig.ident == "new"
+            || self.trait_method_skipped(&i.sig.ident)
+        {
             return;
         }
         if let Some(block) = &i.default {
-            if block_is_empty(block) {
+            if block_is_empty(block) || self.body_too_small(block) {
                 return;
             }
-            let function = self.enter_function(&i.sig.ident, &i.sig.output, i.span());
+            let function = self.enter_function(&i.sig, i.span());
             self.collect_fn_mutants(&i.sig, block);
             syn::visit::visit_trait_item_fn(self, i);
             self.leave_function(function);
@@ -453,7 +1201,7 @@ impl<'ast> Visit<'ast> for DiscoveryVisitor<'_> {
 
     /// Visit `impl Foo { ...}` or `impl Debug for Foo { ... }`.
     fn visit_item_impl(&mut self, i: &'ast syn::ItemImpl) {
-        if attrs_excluded(&i.attrs) {
+        if attrs_excluded(&i.attrs) || self.cfg_excluded(&i.attrs) {
             return;
         }
         let type_name = i.self_ty.to_pretty_string();
@@ -462,18 +1210,32 @@ impl<'ast> Visit<'ast> for DiscoveryVisitor<'_> {
                 // Can't think of how to generate a viable different default.
                 return;
             }
+            if let Some(hit) = self
+                .options
+                .skip_trait_impls
+                .iter()
+                .find(|name| path_ends_with_names(trait_path, name))
+            {
+                trace!("skip impl of trait {hit}");
+                return;
+            }
             format!("<impl {trait} for {type_name}>", trait = trait_path.to_pretty_string())
         } else {
             type_name
         };
+        let outer_in_trait_impl = self.in_trait_impl;
+        self.in_trait_impl = i.trait_.is_some();
+        let outer_self_ty = self.impl_self_ty.replace((*i.self_ty).clone());
         self.in_namespace(&name, |v| syn::visit::visit_item_impl(v, i));
+        self.impl_self_ty = outer_self_ty;
+        self.in_trait_impl = outer_in_trait_impl;
     }
 
     /// Visit `trait Foo { ... }`
     fn visit_item_trait(&mut self, i: &'ast syn::ItemTrait) {
         let name = i.ident.to_pretty_string();
         let _span = trace_span!("trait", line = i.span().start().line, name).entered();
-        if attrs_excluded(&i.attrs) {
+        if attrs_excluded(&i.attrs) || self.cfg_excluded(&i.attrs) || self.vis_excluded(&i.vis) {
             return;
         }
         self.in_namespace(&name, |v| syn::visit::visit_item_trait(v, i));
@@ -487,6 +1249,9 @@ impl<'ast> Visit<'ast> for DiscoveryVisitor<'_> {
             trace!("mod excluded by attrs");
             return;
         }
+        if self.cfg_excluded(&node.attrs) {
+            return;
+        }
 
         let source_location = Span::from(node.span());
 
@@ -522,6 +1287,54 @@ impl<'ast> Visit<'ast> for DiscoveryVisitor<'_> {
         assert_eq!(self.mod_namespace_stack.pop(), Some(mod_namespace));
     }
 
+    /// Visit `const N: usize = 8` in the generic parameters of a type or trait.
+    fn visit_const_param(&mut self, i: &'ast syn::ConstParam) {
+        if attrs_excluded(&i.attrs) {
+            return;
+        }
+        if let Some(Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        })) = &i.default
+        {
+            if self.options.mutate_const_defaults {
+                self.collect_const_default_mutants(lit);
+            }
+        }
+        syn::visit::visit_const_param(self, i);
+    }
+
+    /// Visit `struct Foo { ... }`.
+    fn visit_item_struct(&mut self, i: &'ast syn::ItemStruct) {
+        if attrs_excluded(&i.attrs) {
+            return;
+        }
+        syn::visit::visit_item_struct(self, i);
+    }
+
+    /// Visit `static NAME: T = value;`, and replace the value with others of the same type.
+    ///
+    /// For a lazily initialized static like `static X: LazyLock<T> = LazyLock::new(|| value)`,
+    /// the value returned by the closure is replaced.
+    fn visit_item_static(&mut self, i: &'ast syn::ItemStatic) {
+        let _span = trace_span!("static", line = i.span().start().line, name = %i.ident).entered();
+        if attrs_excluded(&i.attrs) || self.cfg_excluded(&i.attrs) || self.vis_excluded(&i.vis) {
+            return;
+        }
+        let lazy_initializer = lazy_static_initializer(&i.ty, &i.expr);
+        let lazy = lazy_initializer.is_some();
+        let (type_, value) = lazy_initializer.unwrap_or((&i.ty, &i.expr));
+        for rep in static_value_replacements(
+            type_,
+            value,
+            self.error_exprs,
+            self.options.value_options(),
+            lazy,
+        ) {
+            self.collect_mutant(value.span().into(), &rep, Genre::StaticValue);
+        }
+        syn::visit::visit_item_static(self, i);
+    }
+
     /// Visit `a op b` expressions.
     fn visit_expr_binary(&mut self, i: &'ast syn::ExprBinary) {
         let _span = trace_span!("binary", line = i.op.span().start().line).entered();
@@ -570,20 +1383,71 @@ impl<'ast> Visit<'ast> for DiscoveryVisitor<'_> {
                 Vec::new()
             }
         };
-        replacements
-            .into_iter()
-            .for_each(|rep| self.collect_mutant(i.op.span().into(), &rep, Genre::BinaryOperator));
-        syn::visit::visit_expr_binary(self, i);
+        for rep in replacements {
+            self.collect_mutant(i.op.span().into(), &rep, Genre::BinaryOperator);
+        }
+        // Keep just one operand of `&&` or `||`, to check that each is needed. Both sides
+        // are bools, so this should be viable, except in a chain of `let` conditions where
+        // one side might use a name bound by the other.
+        if matches!(i.op, BinOp::And(_) | BinOp::Or(_))
+            && !expr_has_let(&i.left)
+            && !expr_has_let(&i.right)
+        {
+            let code = self.source_file.code();
+            let operands =
+                [&i.left, &i.right].map(|operand| Span::from(operand.span()).extract(code));
+            for replacement in operands {
+                self.collect_mutant_text(i.span().into(), replacement, Genre::ConditionPrune);
+            }
+        }
+        if is_compound_assignment(i.op) {
+            self.visit_place_expr(&i.left);
+            self.visit_expr(&i.right);
+        } else {
+            syn::visit::visit_expr_binary(self, i);
+        }
     }
 
-    fn visit_expr_unary(&mut self, i: &'ast syn::ExprUnary) {
-        let _span = trace_span!("unary", line = i.op.span().start().line).entered();
-        trace!("visit unary operator");
+    /// Visit `a = b`, where `a` is assigned rather than read.
+    fn visit_expr_assign(&mut self, i: &'ast syn::ExprAssign) {
         if attrs_excluded(&i.attrs) {
             return;
         }
-        match i.op {
-            UnOp::Not(_) | UnOp::Neg(_) => {
+        self.visit_place_expr(&i.left);
+        self.visit_expr(&i.right);
+    }
+
+    /// Visit `&a` or `&mut a`; in the second, `a` is borrowed rather than read.
+    fn visit_expr_reference(&mut self, i: &'ast syn::ExprReference) {
+        if i.mutability.is_some() {
+            self.visit_place_expr(&i.expr);
+        } else {
+            syn::visit::visit_expr_reference(self, i);
+        }
+    }
+
+    /// Visit `a.b`, and replace reads of `self.field` with values of the field's type.
+    fn visit_expr_field(&mut self, i: &'ast syn::ExprField) {
+        if self.options.mutate_field_reads && !self.in_place_expr {
+            if let Some(type_) = self.self_field_type(i) {
+                for rep in
+                    field_value_replacements(type_, self.error_exprs, self.options.value_options())
+                {
+                    self.collect_mutant(i.span().into(), &rep, Genre::FieldRead);
+                }
+            }
+        }
+        syn::visit::visit_expr_field(self, i);
+    }
+
+    fn visit_expr_unary(&mut self, i: &'ast syn::ExprUnary) {
+        let _span = trace_span!("unary", line = i.op.span().start().line).entered();
+        trace!("visit unary operator");
+        if attrs_excluded(&i.attrs) {
+            return;
+        }
+        match i.op {
+            UnOp::Not(_) | UnOp::Neg(_) => {
                 self.collect_mutant(i.op.span().into(), &quote! {}, Genre::UnaryOperator);
             }
             _ => {
@@ -592,10 +1456,281 @@ impl<'ast> Visit<'ast> for DiscoveryVisitor<'_> {
                     "No mutants generated for this unary operator"
                 );
             }
-        };
+        }
         syn::visit::visit_expr_unary(self, i);
     }
 
+    /// Visit `if cond { A } else { B }`, and generate a mutant swapping the branches.
+    fn visit_expr_if(&mut self, i: &'ast syn::ExprIf) {
+        let _span = trace_span!("if", line = i.span().start().line).entered();
+        if attrs_excluded(&i.attrs) {
+            return;
+        }
+        // Only swap a plain `else` block, not `else if`. The branches of an `if` with an
+        // `else` must have the same type, so swapping them should compile, unless the
+        // condition binds names with `let` that only the first branch can use.
+        if let Some((_, else_expr)) = &i.else_branch {
+            if let Expr::Block(ExprBlock {
+                block: else_block, ..
+            }) = &**else_expr
+            {
+                let then_block = &i.then_branch;
+                if !expr_has_let(&i.cond)
+                    && then_block.to_token_stream().to_string()
+                        != else_block.to_token_stream().to_string()
+                {
+                    // Build the replacement from the original text, rather than
+                    // pretty-printing tokens, so that the formatting of the branches
+                    // is preserved.
+                    let code = self.source_file.code();
+                    let then_span = then_block.brace_token.span.join();
+                    let else_span = else_block.brace_token.span.join();
+                    let whole = Span {
+                        start: i.if_token.span.start().into(),
+                        end: else_span.end().into(),
+                    };
+                    // `if cond `, then the else block, ` else `, and the then block.
+                    let replacement = [
+                        Span {
+                            start: whole.start,
+                            end: then_span.start().into(),
+                        }
+                        .extract(code),
+                        Span::from(else_span).extract(code),
+                        Span {
+                            start: then_span.end().into(),
+                            end: else_span.start().into(),
+                        }
+                        .extract(code),
+                        Span::from(then_span).extract(code),
+                    ]
+                    .concat();
+                    self.collect_mutant_text(whole, replacement, Genre::IfSwap);
+                }
+            }
+        }
+        // Delete an early `return` that ends an `if` block, so that execution carries on
+        // past it. This needs no type information, so it works the same inside closures,
+        // where the return type is often unknown. The block then has type `()`, which
+        // only surely builds if there's no `else`.
+        if i.else_branch.is_none() {
+            if let Some(stmt @ syn::Stmt::Expr(Expr::Return(_), _)) = i.then_branch.stmts.last() {
+                self.collect_mutant(stmt.span().into(), &TokenStream::new(), Genre::EarlyReturn);
+            }
+        }
+        if let Expr::Let(expr_let) = &*i.cond {
+            if !self.fn_stack.is_empty() {
+                self.collect_result_branch_mutants(expr_let);
+            }
+        }
+        syn::visit::visit_expr_if(self, i);
+    }
+
+    /// Visit a `for` loop, and change the end of a numeric range it iterates over by one
+    /// in each direction, or make the range empty.
+    fn visit_expr_for_loop(&mut self, i: &'ast syn::ExprForLoop) {
+        let _span = trace_span!("for", line = i.span().start().line).entered();
+        if attrs_excluded(&i.attrs) {
+            return;
+        }
+        if let Expr::Range(ExprRange {
+            start: Some(start),
+            limits: RangeLimits::HalfOpen(_),
+            end: Some(end),
+            ..
+        }) = &*i.expr
+        {
+            if !self.fn_stack.is_empty()
+                && (is_integer_range_bound(start) || is_integer_range_bound(end))
+            {
+                let code = self.source_file.code();
+                let end_span: Span = end.span().into();
+                let end_text = end_span.extract(code);
+                // Parenthesize anything that might bind more loosely than `-`.
+                let end_text = if matches!(
+                    &**end,
+                    Expr::Lit(_)
+                        | Expr::Path(_)
+                        | Expr::Field(_)
+                        | Expr::MethodCall(_)
+                        | Expr::Call(_)
+                        | Expr::Index(_)
+                        | Expr::Paren(_)
+                ) {
+                    end_text
+                } else {
+                    format!("({end_text})")
+                };
+                for replacement in [
+                    format!("{end_text} - 1"),
+                    format!("{end_text} + 1"),
+                    Span::from(start.span()).extract(code),
+                ] {
+                    self.collect_mutant_text(end_span, replacement, Genre::LoopBound);
+                }
+            }
+        }
+        self.in_loop(i.label.as_ref(), |v| syn::visit::visit_expr_for_loop(v, i));
+    }
+
+    fn visit_expr_while(&mut self, i: &'ast syn::ExprWhile) {
+        self.in_loop(i.label.as_ref(), |v| syn::visit::visit_expr_while(v, i));
+    }
+
+    fn visit_expr_loop(&mut self, i: &'ast syn::ExprLoop) {
+        self.in_loop(i.label.as_ref(), |v| syn::visit::visit_expr_loop(v, i));
+    }
+
+    fn visit_expr_closure(&mut self, i: &'ast ExprClosure) {
+        let outer_loop_labels = std::mem::take(&mut self.loop_labels);
+        syn::visit::visit_expr_closure(self, i);
+        self.loop_labels = outer_loop_labels;
+    }
+
+    fn visit_expr_async(&mut self, i: &'ast syn::ExprAsync) {
+        let outer_loop_labels = std::mem::take(&mut self.loop_labels);
+        syn::visit::visit_expr_async(self, i);
+        self.loop_labels = outer_loop_labels;
+    }
+
+    /// Replace `break` with `continue`, when it leaves a loop rather than a labeled
+    /// block, and doesn't carry a value.
+    fn visit_expr_break(&mut self, i: &'ast syn::ExprBreak) {
+        let breaks_loop = match &i.label {
+            None => !self.loop_labels.is_empty(),
+            Some(label) => self.loop_labels.iter().flatten().any(|l| l == label),
+        };
+        if breaks_loop && i.expr.is_none() && !self.fn_stack.is_empty() && !attrs_excluded(&i.attrs)
+        {
+            let label = &i.label;
+            self.collect_mutant(
+                i.span().into(),
+                &quote! { continue #label },
+                Genre::LoopControl,
+            );
+        }
+        syn::visit::visit_expr_break(self, i);
+    }
+
+    /// Replace `continue` with `break`.
+    fn visit_expr_continue(&mut self, i: &'ast syn::ExprContinue) {
+        if !self.loop_labels.is_empty() && !self.fn_stack.is_empty() && !attrs_excluded(&i.attrs) {
+            let label = &i.label;
+            self.collect_mutant(
+                i.span().into(),
+                &quote! { break #label },
+                Genre::LoopControl,
+            );
+        }
+        syn::visit::visit_expr_continue(self, i);
+    }
+
+    /// Visit a literal, and replace float and bool literals in functions with other values.
+    fn visit_expr_lit(&mut self, i: &'ast ExprLit) {
+        if !self.fn_stack.is_empty() && !self.in_const_generic_arg {
+            match &i.lit {
+                Lit::Float(lit) => self.collect_float_literal_mutants(lit),
+                Lit::Bool(lit) => {
+                    let span: Span = lit.span().into();
+                    let replacement = if lit.value { "false" } else { "true" };
+                    // A function whose whole body is the literal already has this
+                    // replacement as a FnValue mutant.
+                    if !self
+                        .fn_value_replacements
+                        .contains(&(span, replacement.to_owned()))
+                    {
+                        self.collect_mutant_text(span, replacement.to_owned(), Genre::LiteralBool);
+                    }
+                }
+                _ => (),
+            }
+        }
+        syn::visit::visit_expr_lit(self, i);
+    }
+
+    /// Don't look inside attributes, such as `#[cfg(...)]`, for literals to mutate.
+    fn visit_attribute(&mut self, _i: &'ast Attribute) {}
+
+    /// Visit a generic argument, noting when it's a const expression.
+    fn visit_generic_argument(&mut self, i: &'ast syn::GenericArgument) {
+        let outer = self.in_const_generic_arg;
+        self.in_const_generic_arg = matches!(i, syn::GenericArgument::Const(_));
+        syn::visit::visit_generic_argument(self, i);
+        self.in_const_generic_arg = outer;
+    }
+
+    /// Visit a statement, and delete statements that add to a collection or print debug
+    /// output.
+    fn visit_stmt(&mut self, i: &'ast syn::Stmt) {
+        const COLLECTION_METHODS: &[&str] = &["push", "insert", "extend"];
+        const DEBUG_MACROS: &[&str] = &["dbg", "println", "eprintln"];
+        match i {
+            syn::Stmt::Expr(Expr::MethodCall(call), Some(semi))
+                if !self.fn_stack.is_empty()
+                    && !attrs_excluded(&call.attrs)
+                    && COLLECTION_METHODS.iter().any(|name| call.method == name) =>
+            {
+                let span = Span {
+                    start: call.span().start().into(),
+                    end: semi.span.end().into(),
+                };
+                self.collect_mutant(span, &TokenStream::new(), Genre::CollectionMutation);
+            }
+            // Only statements ending in `;` are deleted, because `dbg!` returns its
+            // argument and so can't be removed from an expression.
+            syn::Stmt::Macro(syn::StmtMacro {
+                attrs,
+                mac,
+                semi_token: Some(semi),
+            }) if self.options.remove_debug_output
+                && !self.fn_stack.is_empty()
+                && !attrs_excluded(attrs)
+                && DEBUG_MACROS.iter().any(|name| {
+                    path_is(&mac.path, &[name]) || path_is(&mac.path, &["std", name])
+                }) =>
+            {
+                let span = Span {
+                    start: mac.path.span().start().into(),
+                    end: semi.span.end().into(),
+                };
+                self.collect_mutant(span, &TokenStream::new(), Genre::DebugRemoval);
+            }
+            _ => (),
+        }
+        syn::visit::visit_stmt(self, i);
+    }
+
+    /// Visit a macro invocation, and neutralize assertions of invariants in non-test code.
+    fn visit_macro(&mut self, i: &'ast syn::Macro) {
+        const ASSERT_MACROS: &[&str] = &[
+            "assert",
+            "assert_eq",
+            "assert_ne",
+            "debug_assert",
+            "debug_assert_eq",
+            "debug_assert_ne",
+        ];
+        // Tests, and code under `#[cfg(test)]`, are already excluded before we get here.
+        if !self.fn_stack.is_empty()
+            && ASSERT_MACROS
+                .iter()
+                .any(|name| path_is(&i.path, &[name]) || path_is(&i.path, &["std", name]))
+        {
+            let span = Span {
+                start: i.path.span().start().into(),
+                end: i.delimiter.span().close().end().into(),
+            };
+            self.collect_mutant(span, &quote! { () }, Genre::AssertNeutralize);
+        } else if !self.fn_stack.is_empty()
+            && ["write", "writeln"]
+                .iter()
+                .any(|name| path_is(&i.path, &[name]) || path_is(&i.path, &["std", name]))
+        {
+            self.collect_format_mutant(i);
+        }
+        syn::visit::visit_macro(self, i);
+    }
+
     fn visit_expr_match(&mut self, i: &'ast syn::ExprMatch) {
         let _span = trace_span!("match", line = i.span().start().line).entered();
 
@@ -624,6 +1759,56 @@ impl<'ast> Visit<'ast> for DiscoveryVisitor<'_> {
             trace!("match has no `_` pattern");
         }
 
+        // Swap the bodies of adjacent arms, other than wildcards, so that tests must
+        // distinguish between the cases.
+        let n_arms = i.arms.len();
+        for (index_a, (arm_a, arm_b)) in i.arms.iter().tuple_windows().enumerate() {
+            if matches!(arm_a.pat, syn::Pat::Wild(_))
+                || matches!(arm_b.pat, syn::Pat::Wild(_))
+                || !arm_b.attrs.is_empty()
+            {
+                continue;
+            }
+            let (body_a, body_b) = (&arm_a.body, &arm_b.body);
+            if body_a.to_token_stream().to_string() == body_b.to_token_stream().to_string() {
+                continue;
+            }
+            // Build the replacement from the original text, like for `if` branches, so
+            // that the formatting and comments of the arms are preserved. An arm whose
+            // body is a block needs no comma, but the body moved into it might.
+            let code = self.source_file.code();
+            let first_span: Span = body_a.span().into();
+            let second_span: Span = body_b.span().into();
+            let comma_after = |arm: &syn::Arm, body: &Expr| {
+                if arm.comma.is_none() && !expr_is_block_like(body) {
+                    ","
+                } else {
+                    ""
+                }
+            };
+            let replacement = [
+                second_span.extract(code).as_str(),
+                comma_after(arm_a, body_b),
+                &Span {
+                    start: first_span.end,
+                    end: second_span.start,
+                }
+                .extract(code),
+                &first_span.extract(code),
+                if index_a + 2 < n_arms {
+                    comma_after(arm_b, body_a)
+                } else {
+                    ""
+                },
+            ]
+            .concat();
+            let span = Span {
+                start: first_span.start,
+                end: second_span.end,
+            };
+            self.collect_mutant_text(span, replacement, Genre::MatchArmSwap);
+        }
+
         i.arms
             .iter()
             .flat_map(|arm| &arm.guard)
@@ -644,6 +1829,17 @@ impl<'ast> Visit<'ast> for DiscoveryVisitor<'_> {
     }
 }
 
+/// True if two fragments of code are the same, ignoring whitespace and comments.
+fn same_code(a: &str, b: &str) -> bool {
+    a == b
+        || match (a.parse::<TokenStream>(), b.parse::<TokenStream>()) {
+            // Comparing strings is a kludge for proc_macro2 not (yet) apparently
+            // exposing any way to compare token streams.
+            (Ok(a), Ok(b)) => a.to_pretty_string() == b.to_pretty_string(),
+            _ => false,
+        }
+}
+
 // Get the span of the block excluding the braces, or None if it is empty.
 fn function_body_span(block: &Block) -> Option<Span> {
     Some(Span {
@@ -737,13 +1933,47 @@ fn find_mod_source(
     None
 }
 
-/// True if the signature of a function is such that it should be excluded.
-fn fn_sig_excluded(sig: &syn::Signature) -> bool {
-    if sig.unsafety.is_some() {
-        trace!("Skip unsafe fn");
-        true
+/// If a pattern matches one variant of a `Result`, like `Ok(x)` or `Err(_)`, return
+/// the name of the variant.
+fn result_pattern_variant(pat: &syn::Pat) -> Option<&'static str> {
+    if let syn::Pat::TupleStruct(syn::PatTupleStruct { path, .. }) = pat {
+        ["Ok", "Err"]
+            .into_iter()
+            .find(|variant| path_ends_with(path, variant))
     } else {
-        false
+        None
+    }
+}
+
+/// True if an expression ends with a block, so that it needs no comma after it as the
+/// body of a match arm.
+fn expr_is_block_like(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::Block(_)
+            | Expr::Const(_)
+            | Expr::ForLoop(_)
+            | Expr::If(_)
+            | Expr::Loop(_)
+            | Expr::Match(_)
+            | Expr::TryBlock(_)
+            | Expr::Unsafe(_)
+            | Expr::While(_)
+    )
+}
+
+/// True if an `if` condition contains a `let` binding, like `if let Some(x) = y && x > 0`.
+fn expr_has_let(expr: &Expr) -> bool {
+    match expr {
+        Expr::Let(_) => true,
+        Expr::Binary(ExprBinary {
+            left,
+            op: BinOp::And(_),
+            right,
+            ..
+        }) => expr_has_let(left) || expr_has_let(right),
+        Expr::Paren(ExprParen { expr, .. }) => expr_has_let(expr),
+        _ => false,
     }
 }
 
@@ -756,11 +1986,393 @@ fn attrs_excluded(attrs: &[Attribute]) -> bool {
         .any(|attr| attr_is_cfg_test(attr) || attr_is_test(attr) || attr_is_mutants_skip(attr))
 }
 
+/// True if the type is one that is known to implement `Default`.
+fn type_has_known_default(type_: &Type) -> bool {
+    match type_ {
+        Type::Path(syn::TypePath { qself: None, path }) => {
+            let Some(last) = path.segments.last() else {
+                return false;
+            };
+            [
+                "bool", "char", "f32", "f64", "i8", "i16", "i32", "i64", "i128", "isize", "u8",
+                "u16", "u32", "u64", "u128", "usize", "String", "Vec", "VecDeque", "HashMap",
+                "HashSet", "BTreeMap", "BTreeSet", "Option", "Duration", "PathBuf",
+            ]
+            .iter()
+            .any(|name| last.ident == name)
+        }
+        Type::Reference(syn::TypeReference {
+            mutability: None,
+            elem,
+            ..
+        }) => match elem.as_ref() {
+            Type::Slice(_) => true,
+            Type::Path(syn::TypePath { qself: None, path }) => path.is_ident("str"),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn is_compound_assignment(op: BinOp) -> bool {
+    matches!(
+        op,
+        BinOp::AddAssign(_)
+            | BinOp::SubAssign(_)
+            | BinOp::MulAssign(_)
+            | BinOp::DivAssign(_)
+            | BinOp::RemAssign(_)
+            | BinOp::BitXorAssign(_)
+            | BinOp::BitAndAssign(_)
+            | BinOp::BitOrAssign(_)
+            | BinOp::ShlAssign(_)
+            | BinOp::ShrAssign(_)
+    )
+}
+
+/// Collect the types of the named fields of each struct defined in a file, including
+/// in inline modules, by struct name then field name.
+///
+/// Structs with the same name in different modules are merged, which might give the
+/// wrong type for a field, but that would just produce an unviable mutant.
+fn struct_fields(file: &File) -> HashMap<String, HashMap<String, Type>> {
+    struct StructFields(HashMap<String, HashMap<String, Type>>);
+
+    impl<'ast> Visit<'ast> for StructFields {
+        fn visit_item_struct(&mut self, i: &'ast syn::ItemStruct) {
+            let fields = self.0.entry(i.ident.to_string()).or_default();
+            for field in &i.fields {
+                if let Some(ident) = &field.ident {
+                    fields.insert(ident.to_string(), field.ty.clone());
+                }
+            }
+        }
+    }
+
+    let mut collector = StructFields(HashMap::new());
+    collector.visit_file(file);
+    collector.0
+}
+
+/// Find the return types of the functions and methods defined in a file, by name.
+///
+/// Names that are defined more than once with different return types are left out,
+/// since we can't tell which one is called.
+fn fn_return_types(file: &File) -> HashMap<String, Type> {
+    struct ReturnTypes(HashMap<String, Option<Type>>);
+
+    impl ReturnTypes {
+        fn insert(&mut self, sig: &Signature) {
+            let ReturnType::Type(_, type_) = &sig.output else {
+                return;
+            };
+            self.0
+                .entry(sig.ident.to_string())
+                .and_modify(|existing| {
+                    if existing.as_ref().is_some_and(|existing| {
+                        existing.to_pretty_string() != type_.to_pretty_string()
+                    }) {
+                        *existing = None;
+                    }
+                })
+                .or_insert_with(|| Some((**type_).clone()));
+        }
+    }
+
+    impl<'ast> Visit<'ast> for ReturnTypes {
+        fn visit_signature(&mut self, i: &'ast Signature) {
+            self.insert(i);
+        }
+    }
+
+    let mut collector = ReturnTypes(HashMap::new());
+    collector.visit_file(file);
+    collector
+        .0
+        .into_iter()
+        .filter_map(|(name, type_)| Some((name, type_?)))
+        .collect()
+}
+
+/// The types of the arguments of a function that are bound to a simple name, like
+/// `r: Result<u32, String>`.
+fn arg_types(sig: &Signature) -> HashMap<String, Type> {
+    sig.inputs
+        .iter()
+        .filter_map(|input| match input {
+            syn::FnArg::Typed(pat_type) => match pat_type.pat.as_ref() {
+                syn::Pat::Ident(pat_ident) => {
+                    Some((pat_ident.ident.to_string(), (*pat_type.ty).clone()))
+                }
+                _ => None,
+            },
+            syn::FnArg::Receiver(_) => None,
+        })
+        .collect()
+}
+
+/// Find the error types of `Result` aliases defined in a file, like
+/// `type Result<T> = std::result::Result<T, MyError>;`.
+fn result_alias_error_types(file: &File) -> Vec<Type> {
+    struct ResultAliases(Vec<Type>);
+
+    impl<'ast> Visit<'ast> for ResultAliases {
+        fn visit_item_type(&mut self, i: &'ast syn::ItemType) {
+            if i.ident == "Result" {
+                if let Type::Path(syn::TypePath { path, .. }) = &*i.ty {
+                    if let Some(error_type) = match_second_type_arg(path, "Result") {
+                        self.0.push(error_type.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    let mut collector = ResultAliases(Vec::new());
+    collector.visit_file(file);
+    collector.0
+}
+
+/// Find the places where a function parameter is used in the function body.
+struct ArgUses<'i> {
+    ident: &'i Ident,
+    /// Spans of expressions that are just the parameter name.
+    spans: Vec<Span>,
+    /// True if the parameter might be used or shadowed somewhere we can't replace it.
+    unknown: bool,
+}
+
+impl<'ast> Visit<'ast> for ArgUses<'_> {
+    fn visit_expr_path(&mut self, i: &'ast ExprPath) {
+        if i.qself.is_none() && i.path.is_ident(self.ident) {
+            self.spans.push(i.span().into());
+        }
+    }
+
+    /// `Foo { x }` uses `x` but can't be replaced without adding the field name.
+    fn visit_field_value(&mut self, i: &'ast syn::FieldValue) {
+        if i.colon_token.is_none() {
+            self.unknown = true;
+        }
+        syn::visit::visit_field_value(self, i);
+    }
+
+    /// Macro arguments aren't parsed, so we can't tell how they use the name.
+    fn visit_macro(&mut self, i: &'ast syn::Macro) {
+        if token_stream_mentions(&i.tokens, self.ident) {
+            self.unknown = true;
+        }
+    }
+
+    /// Another binding of the same name shadows the parameter.
+    fn visit_pat_ident(&mut self, i: &'ast syn::PatIdent) {
+        if i.ident == *self.ident {
+            self.unknown = true;
+        }
+        syn::visit::visit_pat_ident(self, i);
+    }
+
+    /// Nested items can't see the parameter.
+    fn visit_item(&mut self, _i: &'ast syn::Item) {}
+}
+
+fn token_stream_mentions(tokens: &TokenStream, ident: &Ident) -> bool {
+    tokens.clone().into_iter().any(|tree| match tree {
+        proc_macro2::TokenTree::Ident(i) => i == *ident,
+        proc_macro2::TokenTree::Group(group) => token_stream_mentions(&group.stream(), ident),
+        _ => false,
+    })
+}
+
+/// True if the function returns `()`, either implicitly or explicitly.
+fn return_type_is_unit(return_type: &ReturnType) -> bool {
+    match return_type {
+        ReturnType::Default => true,
+        ReturnType::Type(_, type_) => {
+            matches!(type_.as_ref(), Type::Tuple(tuple) if tuple.elems.is_empty())
+        }
+    }
+}
+
+/// True if the function returns a `Result` whose `Ok` type is `()`, like
+/// `Result<(), E>` or `io::Result<()>`.
+fn return_type_is_unit_result(return_type: &ReturnType) -> bool {
+    let ReturnType::Type(_, type_) = return_type else {
+        return false;
+    };
+    let Type::Path(syn::TypePath { path, .. }) = type_.as_ref() else {
+        return false;
+    };
+    let Some(last) = path.segments.last() else {
+        return false;
+    };
+    last.ident == "Result"
+        && matches!(&last.arguments, syn::PathArguments::AngleBracketed(args)
+            if matches!(args.args.first(),
+                Some(syn::GenericArgument::Type(Type::Tuple(tuple))) if tuple.elems.is_empty()))
+}
+
+/// The integer operations that have both `saturating_` and `wrapping_` methods
+/// returning the same type.
+const OVERFLOW_POLICY_OPS: &[&str] = &["add", "sub", "mul", "pow"];
+
+/// If this is a saturating or wrapping integer arithmetic method, return the name of
+/// the same operation with the other overflow policy.
+fn overflow_policy_swap(method: &str) -> Option<String> {
+    let (prefix, op) = method.split_once('_')?;
+    if !OVERFLOW_POLICY_OPS.contains(&op) {
+        return None;
+    }
+    match prefix {
+        "saturating" => Some(format!("wrapping_{op}")),
+        "wrapping" => Some(format!("saturating_{op}")),
+        _ => None,
+    }
+}
+
+/// True if the expression is a closure like `|x| x` that returns its argument.
+fn is_identity_closure(expr: &Expr) -> bool {
+    let Expr::Closure(ExprClosure { inputs, body, .. }) = expr else {
+        return false;
+    };
+    match (inputs.iter().exactly_one(), body.as_ref()) {
+        (Ok(syn::Pat::Ident(pat_ident)), Expr::Path(ExprPath { path, .. })) => {
+            path.is_ident(&pat_ident.ident)
+        }
+        _ => false,
+    }
+}
+
+/// Replacements for the default value passed to `.unwrap_or(x)`.
+///
+/// Without type information, the type can only be inferred from a literal: integer and
+/// string literals get values of the same type, while boolean and float literals are
+/// left to their own genres. Anything else is replaced with `Default::default()`, which
+/// builds for the many types that implement `Default`.
+fn unwrap_or_replacements(arg: &Expr) -> Vec<TokenStream> {
+    let replacements = match arg {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) => ["0", "1"]
+            .into_iter()
+            .map(|value| LitInt::new(&format!("{value}{}", lit.suffix()), lit.span()))
+            .filter(|replacement| replacement.base10_digits() != lit.base10_digits())
+            .map(ToTokens::into_token_stream)
+            .collect(),
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(lit), ..
+        }) => ["", "xyzzy"]
+            .into_iter()
+            .filter(|value| *value != lit.value())
+            .map(|value| quote! { #value })
+            .collect(),
+        Expr::Lit(ExprLit {
+            lit: Lit::Bool(_) | Lit::Float(_),
+            ..
+        }) => Vec::new(),
+        _ => vec![quote! { Default::default() }],
+    };
+    let original = arg.to_pretty_string();
+    replacements
+        .into_iter()
+        .filter(|replacement| replacement.to_pretty_string() != original)
+        .collect()
+}
+
+/// Lazily initialized types whose constructor takes a closure returning the value.
+const LAZY_TYPES: &[&str] = &["Lazy", "LazyLock", "LazyCell"];
+
+/// If a static is lazily initialized like `Lazy<T> = Lazy::new(|| value)`, return `T` and
+/// the value returned by the closure: its tail expression, if the body is a block.
+fn lazy_static_initializer<'a>(type_: &'a Type, expr: &'a Expr) -> Option<(&'a Type, &'a Expr)> {
+    let Type::Path(syn::TypePath { path, .. }) = type_ else {
+        return None;
+    };
+    let last = path.segments.last()?;
+    if !LAZY_TYPES.iter().any(|name| last.ident == name) {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(generics) = &last.arguments else {
+        return None;
+    };
+    let Some(syn::GenericArgument::Type(value_type)) = generics.args.first() else {
+        return None;
+    };
+    let Expr::Call(syn::ExprCall { func, args, .. }) = expr else {
+        return None;
+    };
+    let Expr::Path(ExprPath {
+        path: func_path, ..
+    }) = &**func
+    else {
+        return None;
+    };
+    if !path_ends_with(func_path, "new") {
+        return None;
+    }
+    let Ok(Expr::Closure(ExprClosure { body, .. })) = args.iter().exactly_one() else {
+        return None;
+    };
+    match &**body {
+        Expr::Block(ExprBlock { block, .. }) => match block.stmts.last()? {
+            syn::Stmt::Expr(tail, None) => Some((value_type, tail)),
+            _ => None,
+        },
+        body => Some((value_type, body)),
+    }
+}
+
+/// True if a range bound is an integer literal or a `.len()` call, so that the range
+/// is over integers rather than, say, chars.
+fn is_integer_range_bound(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(_), ..
+        }) => true,
+        Expr::MethodCall(call) => call.method == "len" && call.args.is_empty(),
+        _ => false,
+    }
+}
+
+/// Methods that take a predicate closure, which is replaced by a constant.
+const PREDICATE_METHODS: &[&str] = &["filter", "retain", "take_while", "skip_while"];
+
+/// True if the expression is a closure like `|_| true` that returns a constant `value`.
+fn is_constant_closure(expr: &Expr, value: bool) -> bool {
+    let Expr::Closure(ExprClosure { body, .. }) = expr else {
+        return false;
+    };
+    matches!(
+        body.as_ref(),
+        Expr::Lit(ExprLit {
+            lit: Lit::Bool(lit_bool),
+            ..
+        }) if lit_bool.value == value
+    )
+}
+
 /// True if the block (e.g. the contents of a function) is empty.
 fn block_is_empty(block: &syn::Block) -> bool {
     block.stmts.is_empty()
 }
 
+/// The number of statements in a block.
+///
+/// With `count_match_arms`, each arm of a `match` statement or tail expression counts as
+/// a statement, since one big `match` is as complex as many statements.
+fn block_size(block: &Block, count_match_arms: bool) -> usize {
+    block
+        .stmts
+        .iter()
+        .map(|stmt| match stmt {
+            syn::Stmt::Expr(Expr::Match(expr_match), _) if count_match_arms => {
+                expr_match.arms.len().max(1)
+            }
+            _ => 1,
+        })
+        .sum()
+}
+
 /// True if the attribute looks like `#[cfg(test)]`, or has "test"
 /// anywhere in it.
 fn attr_is_cfg_test(attr: &Attribute) -> bool {
@@ -804,6 +2416,21 @@ fn path_ends_with(path: &syn::Path, ident: &str) -> bool {
     path.segments.last().is_some_and(|s| s.ident == ident)
 }
 
+/// True if the path ends with these `::`-separated identifiers, so that `fmt::Debug`
+/// matches `std::fmt::Debug` but not `Debug` alone.
+///
+/// Like [`path_ends_with`], this does not check type arguments.
+fn path_ends_with_names(path: &syn::Path, names: &str) -> bool {
+    let names = names.trim_start_matches("::").split("::").collect_vec();
+    path.segments.len() >= names.len()
+        && path
+            .segments
+            .iter()
+            .rev()
+            .zip(names.iter().rev())
+            .all(|(segment, name)| segment.ident == name)
+}
+
 /// True if the attribute contains `mutants::skip`.
 ///
 /// This for example returns true for `#[mutants::skip]` or `#[cfg_attr(test, mutants::skip)]`.
@@ -862,13 +2489,16 @@ fn find_path_attribute(attrs: &[Attribute]) -> std::result::Result<Option<Utf8Pa
 
 #[cfg(test)]
 mod test {
+    use clap::Parser;
     use indoc::indoc;
     use itertools::Itertools;
     use pretty_assertions::assert_eq;
     use test_log::test;
 
+    use crate::config::Config;
     use crate::test_util::copy_of_testdata;
     use crate::workspace::{PackageFilter, Workspace};
+    use crate::Args;
 
     use super::*;
 
@@ -892,22 +2522,97 @@ mod test {
         assert!(!path_ends_with(&path, "K"));
     }
 
-    /// We should not generate mutants that produce the same tokens as the
-    /// source.
     #[test]
-    fn no_mutants_equivalent_to_source() {
+    fn path_ends_with_names() {
+        use super::path_ends_with_names;
+        use syn::parse_quote;
+
+        let path = parse_quote! { std::fmt::Debug };
+        assert!(path_ends_with_names(&path, "Debug"));
+        assert!(path_ends_with_names(&path, "fmt::Debug"));
+        assert!(path_ends_with_names(&path, "std::fmt::Debug"));
+        assert!(path_ends_with_names(&path, "::std::fmt::Debug"));
+        assert!(!path_ends_with_names(&path, "io::Debug"));
+        assert!(!path_ends_with_names(&path, "core::std::fmt::Debug"));
+
+        let path = parse_quote! { Debug };
+        assert!(!path_ends_with_names(&path, "fmt::Debug"));
+    }
+
+    #[test]
+    fn retain_functions_matches_nested_impl_method_exactly() {
         let code = indoc! { "
-            fn always_true() -> bool { true }
+            mod shapes {
+                pub struct Square(u32);
+
+                impl Square {
+                    pub fn area(&self) -> u32 {
+                        self.0 * self.0
+                    }
+
+                    pub fn scaled(&self, k: u32) -> Square {
+                        fn scale(x: u32, k: u32) -> u32 {
+                            x * k
+                        }
+                        Square(scale(self.0, k))
+                    }
+                }
+            }
         "};
-        let source_file = SourceFile::for_tests("src/lib.rs", code, "unimportant", true);
-        let (mutants, _files) =
-            walk_file(&source_file, &[], &Options::default()).expect("walk_file");
-        let mutant_names = mutants.iter().map(|m| m.name(false)).collect_vec();
-        // It would be good to suggest replacing this with 'false', breaking a key behavior,
-        // but bad to replace it with 'true', changing nothing.
+        let mutants = mutate_source_str(code, &Options::default()).unwrap();
+        let retained = |function_names: &[&str]| {
+            let mut discovered = Discovered {
+                mutants: mutants.clone(),
+                files: Vec::new(),
+            };
+            discovered
+                .retain_functions(&function_names.iter().map(|s| (*s).to_owned()).collect_vec());
+            discovered
+                .mutants
+                .iter()
+                .map(|m| m.name(true))
+                .collect_vec()
+        };
         assert_eq!(
-            mutant_names,
-            ["src/lib.rs: replace always_true -> bool with false"]
+            retained(&["shapes::Square::area"]),
+            [
+                "src/main.rs:6:13: replace shapes::Square::area -> u32 with 0",
+                "src/main.rs:6:13: replace shapes::Square::area -> u32 with 1",
+                "src/main.rs:6:20: replace * with + in shapes::Square::area",
+                "src/main.rs:6:20: replace * with / in shapes::Square::area",
+            ]
+        );
+        assert_eq!(
+            retained(&["shapes::Square::scaled::scale"]),
+            [
+                "src/main.rs:11:17: replace shapes::Square::scaled::scale -> u32 with 0",
+                "src/main.rs:11:17: replace shapes::Square::scaled::scale -> u32 with 1",
+                "src/main.rs:11:19: replace * with + in shapes::Square::scaled::scale",
+                "src/main.rs:11:19: replace * with / in shapes::Square::scaled::scale",
+            ]
+        );
+        // Only an exact match of the whole name is accepted.
+        assert_eq!(retained(&["Square::area"]), [] as [String; 0]);
+        assert_eq!(retained(&["shapes::Square"]), [] as [String; 0]);
+    }
+
+    /// We should not generate mutants that produce the same tokens as the
+    /// source.
+    #[test]
+    fn no_mutants_equivalent_to_source() {
+        let code = indoc! { "
+            fn always_true() -> bool { true }
+        "};
+        let source_file = SourceFile::for_tests("src/lib.rs", code, "unimportant", true);
+        let (mutants, _files, _result_alias_errors) =
+            walk_file(&source_file, &ErrorExprs::default(), &Options::default())
+                .expect("walk_file");
+        let mutant_names = mutants.iter().map(|m| m.name(false)).collect_vec();
+        // It would be good to suggest replacing this with 'false', breaking a key behavior,
+        // but bad to replace it with 'true', changing nothing.
+        assert_eq!(
+            mutant_names,
+            ["src/lib.rs: replace always_true -> bool with false"]
         );
     }
 
@@ -1128,6 +2833,7 @@ mod test {
                 src/main.rs: replace <impl From<Apath> for String>::from -> String with String::new()
                 src/main.rs: replace <impl From<Apath> for String>::from -> String with "xyzzy".into()
                 src/main.rs: replace <impl From<&'a str> for Apath>::from -> Apath with Default::default()
+                src/main.rs: neutralize assert! in <impl From<&'a str> for Apath>::from
             "#}
         );
     }
@@ -1162,90 +2868,1815 @@ mod test {
     }
 
     #[test]
-    fn skip_match_arms_without_fallback() {
-        let options = Options::default();
+    fn self_return_type_uses_impl_type() {
         let mutants = mutate_source_str(
             indoc! {"
-                fn main() {
-                    match x {
-                        X::A => {},
-                        X::B => {},
+                trait Parse: Sized {
+                    fn parse(s: &str) -> Option<Self>;
+                }
+
+                impl Parse for u32 {
+                    fn parse(s: &str) -> Option<Self> {
+                        s.parse().ok()
+                    }
+                }
+
+                struct Builder {
+                    verbose: bool,
+                }
+
+                impl Builder {
+                    fn verbose(mut self) -> Self {
+                        self.verbose = true;
+                        self
                     }
                 }
             "},
+            &Options::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            mutants.iter().map(|m| m.name(true)).collect_vec(),
+            [
+                "src/main.rs:7:9: replace <impl Parse for u32>::parse -> Option<Self> with None",
+                "src/main.rs:7:9: replace <impl Parse for u32>::parse -> Option<Self> with Some(0)",
+                "src/main.rs:7:9: replace <impl Parse for u32>::parse -> Option<Self> with Some(1)",
+                "src/main.rs:17:9: replace Builder::verbose -> Self with Default::default()",
+                "src/main.rs:17:24: replace true with false in Builder::verbose",
+            ]
+        );
+    }
+
+    #[test]
+    fn static_associated_fn_returning_result_self_is_mutated() {
+        let options = Options {
+            error_values: vec!["String::new()".to_owned()],
+            ..Options::default()
+        };
+        let mutants = mutate_source_str(
+            indoc! {r#"
+                trait Parse: Sized {
+                    fn parse(s: &str) -> Result<Self, String>;
+                }
+
+                impl Parse for u16 {
+                    fn parse(s: &str) -> Result<Self, String> {
+                        s.parse().map_err(|_| "bad".to_owned())
+                    }
+                }
+
+                #[derive(Default)]
+                struct Port(u16);
+
+                impl Port {
+                    fn parse(s: &str) -> Result<Self, String> {
+                        let port = u16::parse(s)?;
+                        Ok(Port(port))
+                    }
+                }
+            "#},
             &options,
         )
         .unwrap();
+        assert_eq!(
+            mutants.iter().map(|m| m.name(true)).collect_vec(),
+            [
+                "src/main.rs:7:9: replace <impl Parse for u16>::parse -> Result<Self, String> with Ok(0)",
+                "src/main.rs:7:9: replace <impl Parse for u16>::parse -> Result<Self, String> with Ok(1)",
+                "src/main.rs:7:9: replace <impl Parse for u16>::parse -> Result<Self, String> with Err(String::new())",
+                "src/main.rs:16:9: replace Port::parse -> Result<Self, String> with Ok(Default::default())",
+                "src/main.rs:16:9: replace Port::parse -> Result<Self, String> with Err(String::new())",
+            ]
+        );
+    }
 
-        let empty: &[&str] = &[];
+    #[test]
+    fn const_generic_defaults_are_mutated_only_when_requested() {
+        let code = indoc! {"
+            struct Buf<const N: usize = 8> {
+                data: [u8; N],
+            }
+
+            impl<const N: usize> Buf<N> {
+                fn capacity(&self) -> usize {
+                    N
+                }
+            }
+
+            #[mutants::skip]
+            struct Skipped<const M: usize = 4>;
+
+            struct Zero<const Z: u8 = 0u8>;
+        "};
+        let names = |options: &Options| {
+            mutate_source_str(code, options)
+                .unwrap()
+                .iter()
+                .map(|m| m.name(true))
+                .collect_vec()
+        };
         assert_eq!(
-            mutants
+            names(&Options::default()),
+            [
+                "src/main.rs:7:9: replace Buf<N>::capacity -> usize with 0",
+                "src/main.rs:7:9: replace Buf<N>::capacity -> usize with 1",
+            ]
+        );
+        assert_eq!(
+            names(&Options {
+                mutate_const_defaults: true,
+                ..Default::default()
+            }),
+            [
+                "src/main.rs:1:29: replace const generic default 8 with 7",
+                "src/main.rs:1:29: replace const generic default 8 with 9",
+                "src/main.rs:7:9: replace Buf<N>::capacity -> usize with 0",
+                "src/main.rs:7:9: replace Buf<N>::capacity -> usize with 1",
+                "src/main.rs:14:27: replace const generic default 0u8 with 1u8",
+            ]
+        );
+    }
+
+    #[test]
+    fn unit_panic_replaces_unit_functions_with_panic() {
+        let code = indoc! {r#"
+            fn log(s: &str) {
+                println!("{s}");
+            }
+
+            fn flush() -> () {
+                std::io::stdout().flush().unwrap();
+            }
+
+            fn len(s: &str) -> usize {
+                s.len()
+            }
+        "#};
+        let names = |options: &Options| {
+            mutate_source_str(code, options)
+                .unwrap()
                 .iter()
-                .filter(|m| m.genre == Genre::MatchArm)
+                .filter(|m| m.genre == Genre::FnValue)
                 .map(|m| m.name(true))
-                .collect_vec(),
-            empty
+                .collect_vec()
+        };
+        assert_eq!(
+            names(&Options::default()),
+            [
+                "src/main.rs:2:5: replace log with ()",
+                "src/main.rs:6:5: replace flush -> () with ()",
+                "src/main.rs:10:5: replace len -> usize with 0",
+                "src/main.rs:10:5: replace len -> usize with 1",
+            ]
+        );
+        assert_eq!(
+            names(&Options {
+                unit_panic: true,
+                ..Default::default()
+            }),
+            [
+                "src/main.rs:2:5: replace log with ()",
+                "src/main.rs:2:5: replace log with panic!(\"mutated!\")",
+                "src/main.rs:6:5: replace flush -> () with ()",
+                "src/main.rs:6:5: replace flush -> () with panic!(\"mutated!\")",
+                "src/main.rs:10:5: replace len -> usize with 0",
+                "src/main.rs:10:5: replace len -> usize with 1",
+            ]
         );
     }
 
     #[test]
-    fn mutate_match_guard() {
-        let options = Options::default();
+    fn panic_mutants_replace_unit_result_functions_with_panic() {
+        let code = indoc! {r#"
+            fn save(path: &Path) -> Result<(), Error> {
+                fs::write(path, "hello")?;
+                Ok(())
+            }
+
+            fn flush() -> io::Result<()> {
+                std::io::stdout().flush()
+            }
+
+            fn count() -> Result<usize, Error> {
+                Ok(fs::read_dir(".")?.count())
+            }
+        "#};
+        let names = |options: &Options| {
+            mutate_source_str(code, options)
+                .unwrap()
+                .iter()
+                .filter(|m| m.genre == Genre::FnValue)
+                .map(|m| m.name(true))
+                .collect_vec()
+        };
+        assert_eq!(
+            names(&Options::default()),
+            [
+                "src/main.rs:2:5: replace save -> Result<(), Error> with Ok(())",
+                "src/main.rs:7:5: replace flush -> io::Result<()> with Ok(())",
+                "src/main.rs:11:5: replace count -> Result<usize, Error> with Ok(0)",
+                "src/main.rs:11:5: replace count -> Result<usize, Error> with Ok(1)",
+            ]
+        );
+        assert_eq!(
+            names(&Options {
+                panic_mutants: true,
+                ..Default::default()
+            }),
+            [
+                "src/main.rs:2:5: replace save -> Result<(), Error> with Ok(())",
+                "src/main.rs:2:5: replace save -> Result<(), Error> with panic!(\"mutated!\")",
+                "src/main.rs:7:5: replace flush -> io::Result<()> with Ok(())",
+                "src/main.rs:7:5: replace flush -> io::Result<()> with panic!(\"mutated!\")",
+                "src/main.rs:11:5: replace count -> Result<usize, Error> with Ok(0)",
+                "src/main.rs:11:5: replace count -> Result<usize, Error> with Ok(1)",
+            ]
+        );
+    }
+
+    #[test]
+    fn replace_args_used_once_with_default() {
         let mutants = mutate_source_str(
-            indoc! {"
-                fn main() {
-                    match x {
-                        X::A if foo() => {},
-                        X::A => {},
-                        X::B => {},
-                        X::C if bar() => {},
-                    }
+            indoc! {r#"
+                fn scale(x: u32, factor: u32, name: &str, out: &mut Vec<u32>) -> u32 {
+                    out.push(x);
+                    x * factor
                 }
-            "},
-            &options,
+
+                fn greet(name: &str, loud: bool) -> String {
+                    let s = format!("hello {name}");
+                    if loud { s.to_uppercase() } else { s }
+                }
+
+                fn shadowed(n: usize, p: Point) -> usize {
+                    let n = n + 1;
+                    Point { n }.n + p.x
+                }
+            "#},
+            &Options {
+                replace_args_with_default: true,
+                ..Default::default()
+            },
         )
         .unwrap();
         assert_eq!(
             mutants
                 .iter()
-                .filter(|m| m.genre == Genre::MatchArmGuard)
+                .filter(|m| m.genre == Genre::ReplaceArgWithDefault)
                 .map(|m| m.name(true))
                 .collect_vec(),
             [
-                "src/main.rs:3:17: replace match guard with true",
-                "src/main.rs:3:17: replace match guard with false",
-                "src/main.rs:6:17: replace match guard with true",
-                "src/main.rs:6:17: replace match guard with false",
+                "src/main.rs:3:9: replace factor with Default::default() in scale",
+                "src/main.rs:8:8: replace loud with Default::default() in greet",
             ]
         );
     }
 
     #[test]
-    fn skip_removing_match_arm_with_guard() {
-        let options = Options::default();
-        let mutants = mutate_source_str(
-            indoc! {"
-                fn main() {
-                    match x {
-                        X::A if foo() => {},
-                        X::A => {},
-                        _ => {},
+    fn replace_self_field_reads() {
+        let code = indoc! {"
+            struct Counter {
+                count: u32,
+                enabled: bool,
+                items: Vec<String>,
+            }
+
+            impl Counter {
+                fn next(&mut self) -> Option<u32> {
+                    if !self.enabled {
+                        return None;
                     }
+                    self.count += 1;
+                    self.items.clear();
+                    Some(self.count * 2)
                 }
-            "},
-            &options,
-        )
-        .unwrap();
+
+                fn reset(&mut self) {
+                    self.count = 0;
+                    let c = &mut self.count;
+                    *c = 1;
+                }
+            }
+        "};
+        let field_read_names = |options: &Options| {
+            mutate_source_str(code, options)
+                .unwrap()
+                .iter()
+                .filter(|m| m.genre == Genre::FieldRead)
+                .map(|m| m.name(true))
+                .collect_vec()
+        };
+        assert_eq!(field_read_names(&Options::default()), [] as [String; 0]);
         assert_eq!(
-            mutants
+            field_read_names(&Options {
+                mutate_field_reads: true,
+                ..Default::default()
+            }),
+            [
+                "src/main.rs:9:13: replace self.enabled with true in Counter::next",
+                "src/main.rs:9:13: replace self.enabled with false in Counter::next",
+                "src/main.rs:14:14: replace self.count with 0 in Counter::next",
+                "src/main.rs:14:14: replace self.count with 1 in Counter::next",
+            ]
+        );
+    }
+
+    #[test]
+    fn remove_debug_output_statements() {
+        let code = indoc! {r#"
+            fn double(x: u32) -> u32 {
+                dbg!(x);
+                eprintln!("doubling {x}");
+                let y = dbg!(x * 2);
+                std::println!("got {y}");
+                dbg!(y)
+            }
+
+            #[test]
+            fn test_double() {
+                println!("testing");
+                assert_eq!(double(2), 4);
+            }
+
+            #[cfg(test)]
+            mod test {
+                fn helper() {
+                    eprintln!("helper");
+                }
+            }
+        "#};
+        let debug_removal_names = |options: &Options| {
+            mutate_source_str(code, options)
+                .unwrap()
+                .iter()
+                .filter(|m| m.genre == Genre::DebugRemoval)
+                .map(|m| m.name(true))
+                .collect_vec()
+        };
+        assert_eq!(debug_removal_names(&Options::default()), [] as [String; 0]);
+        assert_eq!(
+            debug_removal_names(&Options {
+                remove_debug_output: true,
+                ..Default::default()
+            }),
+            [
+                "src/main.rs:2:5: delete dbg! in double",
+                "src/main.rs:3:5: delete eprintln! in double",
+                "src/main.rs:5:5: delete std::println! in double",
+            ]
+        );
+    }
+
+    #[test]
+    fn boilerplate_trait_impls_are_skipped_by_default() {
+        let code = indoc! {r#"
+            impl fmt::Debug for Point {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "({}, {})", self.x, self.y)
+                }
+            }
+
+            impl Ord for Point {
+                fn cmp(&self, other: &Self) -> Ordering {
+                    self.x.cmp(&other.x)
+                }
+            }
+        "#};
+        let names = |args: &[&str]| {
+            let args = Args::try_parse_from(args).unwrap();
+            let options = Options::new(&args, &Config::default()).unwrap();
+            mutate_source_str(code, &options)
+                .unwrap()
                 .iter()
-                .filter(|m| matches!(m.genre, Genre::MatchArmGuard | Genre::MatchArm))
                 .map(|m| m.name(true))
+                .collect_vec()
+        };
+        assert_eq!(
+            names(&["mutants"]),
+            ["src/main.rs:9:9: replace <impl Ord for Point>::cmp -> Ordering with Default::default()"]
+        );
+        assert_eq!(names(&["mutants", "--skip-trait-impls=Ord"]), [""; 0]);
+        // `fmt` is also skipped by name, in any trait.
+        assert_eq!(
+            names(&["mutants", "--skip-trait-impls-defaults=false"]),
+            ["src/main.rs:9:9: replace <impl Ord for Point>::cmp -> Ordering with Default::default()"]
+        );
+        assert_eq!(
+            names(&[
+                "mutants",
+                "--skip-trait-impls-defaults=false",
+                "--skip-trait-methods-defaults=false"
+            ]),
+            [
+                "src/main.rs:3:9: replace <impl fmt::Debug for Point>::fmt -> fmt::Result with Ok(Default::default())",
+                r#"src/main.rs:3:19: replace "({}, {})", self.x, self.y with "" in <impl fmt::Debug for Point>::fmt"#,
+                "src/main.rs:9:9: replace <impl Ord for Point>::cmp -> Ordering with Default::default()",
+            ]
+        );
+    }
+
+    #[test]
+    fn skip_trait_impls_matches_multi_segment_paths() {
+        let code = indoc! {r#"
+            impl std::fmt::Debug for Point {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "({}, {})", self.x, self.y)
+                }
+            }
+
+            impl Debug for Line {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "line")
+                }
+            }
+        "#};
+        let args = Args::try_parse_from([
+            "mutants",
+            "--skip-trait-impls-defaults=false",
+            "--skip-trait-methods-defaults=false",
+            "--skip-trait-impls=fmt::Debug",
+        ])
+        .unwrap();
+        let options = Options::new(&args, &Config::default()).unwrap();
+        assert_eq!(
+            mutate_source_str(code, &options)
+                .unwrap()
+                .iter()
+                .map(|m| m.function.as_ref().unwrap().function_name.clone())
+                .unique()
                 .collect_vec(),
+            ["<impl Debug for Line>::fmt"]
+        );
+    }
+
+    #[test]
+    fn hand_written_partial_eq_is_mutated_unless_skipped() {
+        let code = indoc! {"
+            impl PartialEq for Point {
+                fn eq(&self, other: &Self) -> bool {
+                    self.x == other.x
+                }
+            }
+
+            impl PartialOrd for Point {
+                fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                    self.x.partial_cmp(&other.x)
+                }
+            }
+        "};
+        let functions = |args: &[&str]| {
+            let args = Args::try_parse_from(args).unwrap();
+            let options = Options::new(&args, &Config::default()).unwrap();
+            mutate_source_str(code, &options)
+                .unwrap()
+                .iter()
+                .map(|m| m.function.as_ref().unwrap().function_name.clone())
+                .unique()
+                .collect_vec()
+        };
+        // `eq` and `partial_cmp` are skipped by name by default, but the traits aren't.
+        assert_eq!(functions(&["mutants"]), [""; 0]);
+        assert_eq!(
+            functions(&["mutants", "--skip-trait-methods-defaults=false"]),
             [
-                "src/main.rs:4:9: delete match arm",
-                "src/main.rs:3:17: replace match guard with true",
-                "src/main.rs:3:17: replace match guard with false",
+                "<impl PartialEq for Point>::eq",
+                "<impl PartialOrd for Point>::partial_cmp"
+            ]
+        );
+        assert_eq!(
+            functions(&[
+                "mutants",
+                "--skip-trait-methods-defaults=false",
+                "--skip-trait-impls=PartialEq"
+            ]),
+            ["<impl PartialOrd for Point>::partial_cmp"]
+        );
+    }
+
+    #[test]
+    fn trait_methods_are_skipped_by_name() {
+        let code = indoc! {r#"
+            impl Render for Point {
+                fn fmt(&self, out: &mut String) -> bool {
+                    out.push_str("point");
+                    true
+                }
+
+                fn width(&self) -> usize {
+                    5
+                }
+            }
+
+            trait Shape {
+                fn fmt(&self) -> String {
+                    "shape".to_owned()
+                }
+            }
+
+            impl Point {
+                fn fmt(&self) -> bool {
+                    true
+                }
+            }
+        "#};
+        let functions = |args: &[&str]| {
+            let args = Args::try_parse_from(args).unwrap();
+            let options = Options::new(&args, &Config::default()).unwrap();
+            mutate_source_str(code, &options)
+                .unwrap()
+                .iter()
+                .map(|m| m.function.as_ref().unwrap().function_name.clone())
+                .unique()
+                .collect_vec()
+        };
+        // Inherent methods aren't affected.
+        assert_eq!(
+            functions(&["mutants"]),
+            ["<impl Render for Point>::width", "Point::fmt"]
+        );
+        assert_eq!(
+            functions(&["mutants", "--skip-trait-methods=width"]),
+            ["Point::fmt"]
+        );
+        assert_eq!(
+            functions(&["mutants", "--skip-trait-methods-defaults=false"]),
+            [
+                "<impl Render for Point>::fmt",
+                "<impl Render for Point>::width",
+                "Shape::fmt",
+                "Point::fmt"
+            ]
+        );
+    }
+
+    #[test]
+    fn min_statements_skips_small_functions() {
+        let code = indoc! {"
+            fn one() -> u32 {
+                3
+            }
+
+            fn two(a: u32) -> u32 {
+                let b = a + 1;
+                b * 2
+            }
+
+            fn classify(a: u32) -> &'static str {
+                match a {
+                    0 => \"zero\",
+                    1 => \"one\",
+                    _ => \"many\",
+                }
+            }
+        "};
+        let functions = |min_statements: usize, count_match_arms: bool| {
+            mutate_source_str(
+                code,
+                &Options {
+                    min_statements,
+                    count_match_arms,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .iter()
+            .map(|m| m.function.as_ref().unwrap().function_name.clone())
+            .unique()
+            .collect_vec()
+        };
+        assert_eq!(functions(1, false), ["one", "two", "classify"]);
+        assert_eq!(functions(2, false), ["two"]);
+        assert_eq!(functions(3, false), [""; 0]);
+        assert_eq!(functions(1, true), ["one", "two", "classify"]);
+        assert_eq!(functions(2, true), ["two", "classify"]);
+        assert_eq!(functions(3, true), ["classify"]);
+        assert_eq!(functions(4, true), [""; 0]);
+    }
+
+    #[test]
+    fn swap_if_else_branches() {
+        let mutants = mutate_source_str(
+            indoc! {"
+                fn sign(x: i32) -> i32 {
+                    if x < 0 { -1 } else { 1 }
+                }
+
+                fn f(a: Option<u32>, b: bool) {
+                    if let Some(a) = a { println!(\"{a}\") } else { println!(\"none\") }
+                    if b { g() } else if a.is_none() { h() }
+                    if b { g() } else { g() }
+                }
+            "},
+            &Options::default(),
+        )
+        .unwrap();
+        let swaps = mutants
+            .iter()
+            .filter(|m| m.genre == Genre::IfSwap)
+            .collect_vec();
+        assert_eq!(
+            swaps.iter().map(|m| m.name(true)).collect_vec(),
+            ["src/main.rs:2:5: swap if and else branches in sign"]
+        );
+        assert_eq!(
+            swaps[0].mutated_code(),
+            indoc! {"
+                fn sign(x: i32) -> i32 {
+                    if x < 0 { 1 } else { -1 } /* ~ changed by cargo-mutants ~ */
+                }
+
+                fn f(a: Option<u32>, b: bool) {
+                    if let Some(a) = a { println!(\"{a}\") } else { println!(\"none\") }
+                    if b { g() } else if a.is_none() { h() }
+                    if b { g() } else { g() }
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn no_leak_mut_suppresses_mut_ref_replacements() {
+        let code = indoc! {"
+            struct Counter {
+                n: u32,
+            }
+
+            impl Counter {
+                fn get_mut(&mut self) -> &mut u32 {
+                    &mut self.n
+                }
+            }
+        "};
+        let mutants = mutate_source_str(code, &Options::default()).unwrap();
+        assert_eq!(
+            mutants.iter().map(|m| m.name(true)).collect_vec(),
+            [
+                "src/main.rs:7:9: replace Counter::get_mut -> &mut u32 with Box::leak(Box::new(0))",
+                "src/main.rs:7:9: replace Counter::get_mut -> &mut u32 with Box::leak(Box::new(1))",
+            ]
+        );
+        let options = Options {
+            no_leak_mut: true,
+            ..Default::default()
+        };
+        assert_eq!(mutate_source_str(code, &options).unwrap(), []);
+    }
+
+    #[test]
+    fn skip_file_pragma() {
+        let mutants = mutate_source_str(
+            indoc! {"
+                // mutants:skip-file
+
+                fn a() -> bool {
+                    true
+                }
+            "},
+            &Options::default(),
+        )
+        .unwrap();
+        assert_eq!(mutants, []);
+    }
+
+    #[test]
+    fn skip_region_pragmas() {
+        let mutants = mutate_source_str(
+            indoc! {"
+                fn a() -> bool {
+                    true
+                }
+
+                // mutants:skip-start
+                fn b() -> bool {
+                    true
+                }
+                // mutants:skip-end
+
+                fn c() -> usize {
+                    // mutants:skip-start
+                    let x = 1 + 2;
+                    // mutants:skip-end
+                    x * 3
+                }
+            "},
+            &Options::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            mutants.iter().map(|m| m.name(true)).collect_vec(),
+            [
+                "src/main.rs:2:5: replace a -> bool with false",
+                "src/main.rs:13:5: replace c -> usize with 0",
+                "src/main.rs:13:5: replace c -> usize with 1",
+                "src/main.rs:15:7: replace * with + in c",
+                "src/main.rs:15:7: replace * with / in c",
+            ]
+        );
+    }
+
+    #[test]
+    fn unterminated_skip_region_extends_to_end_of_file() {
+        let mutants = mutate_source_str(
+            indoc! {"
+                fn a() -> bool {
+                    true
+                }
+
+                // mutants:skip-start
+                fn b() -> bool {
+                    true
+                }
+            "},
+            &Options::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            mutants.iter().map(|m| m.name(true)).collect_vec(),
+            ["src/main.rs:2:5: replace a -> bool with false"]
+        );
+    }
+
+    #[test]
+    fn swap_adjacent_match_arm_bodies() {
+        let options = Options::default();
+        let mutants = mutate_source_str(
+            indoc! {r#"
+                fn name(x: u32) -> &'static str {
+                    match x {
+                        0 => "zero",
+                        1 if true => "one",
+                        2 => "two",
+                        3 => "two",
+                        _ => "many",
+                    }
+                }
+
+                fn scale(x: u32) -> u32 {
+                    match x {
+                        0 => {
+                            // Nothing to scale.
+                            0
+                        }
+                        1 => 10,
+                        _ => 100,
+                    }
+                }
+            "#},
+            &options,
+        )
+        .unwrap();
+        let swaps = mutants
+            .iter()
+            .filter(|m| m.genre == Genre::MatchArmSwap)
+            .collect_vec();
+        assert_eq!(
+            swaps.iter().map(|m| m.name(true)).collect_vec(),
+            [
+                "src/main.rs:3:14: swap match arm bodies",
+                "src/main.rs:4:22: swap match arm bodies",
+                "src/main.rs:13:14: swap match arm bodies",
+            ]
+        );
+        assert_eq!(
+            swaps.iter().map(|m| m.replacement_text()).collect_vec(),
+            [
+                "\"one\",\n        1 if true => \"zero\"",
+                "\"two\",\n        2 => \"one\"",
+                "10,\n        1 => {\n            // Nothing to scale.\n            0\n        }",
+            ]
+        );
+    }
+
+    #[test]
+    fn skip_match_arms_without_fallback() {
+        let options = Options::default();
+        let mutants = mutate_source_str(
+            indoc! {"
+                fn main() {
+                    match x {
+                        X::A => {},
+                        X::B => {},
+                    }
+                }
+            "},
+            &options,
+        )
+        .unwrap();
+
+        let empty: &[&str] = &[];
+        assert_eq!(
+            mutants
+                .iter()
+                .filter(|m| m.genre == Genre::MatchArm)
+                .map(|m| m.name(true))
+                .collect_vec(),
+            empty
+        );
+    }
+
+    #[test]
+    fn mutate_match_guard() {
+        let options = Options::default();
+        let mutants = mutate_source_str(
+            indoc! {"
+                fn main() {
+                    match x {
+                        X::A if foo() => {},
+                        X::A => {},
+                        X::B => {},
+                        X::C if bar() => {},
+                    }
+                }
+            "},
+            &options,
+        )
+        .unwrap();
+        assert_eq!(
+            mutants
+                .iter()
+                .filter(|m| m.genre == Genre::MatchArmGuard)
+                .map(|m| m.name(true))
+                .collect_vec(),
+            [
+                "src/main.rs:3:17: replace match guard with true",
+                "src/main.rs:3:17: replace match guard with false",
+                "src/main.rs:6:17: replace match guard with true",
+                "src/main.rs:6:17: replace match guard with false",
+            ]
+        );
+    }
+
+    #[test]
+    fn skip_removing_match_arm_with_guard() {
+        let options = Options::default();
+        let mutants = mutate_source_str(
+            indoc! {"
+                fn main() {
+                    match x {
+                        X::A if foo() => {},
+                        X::A => {},
+                        _ => {},
+                    }
+                }
+            "},
+            &options,
+        )
+        .unwrap();
+        assert_eq!(
+            mutants
+                .iter()
+                .filter(|m| matches!(m.genre, Genre::MatchArmGuard | Genre::MatchArm))
+                .map(|m| m.name(true))
+                .collect_vec(),
+            [
+                "src/main.rs:4:9: delete match arm",
+                "src/main.rs:3:17: replace match guard with true",
+                "src/main.rs:3:17: replace match guard with false",
+            ]
+        );
+    }
+
+    #[test]
+    fn pub_only_skips_private_functions() {
+        let options = Options {
+            pub_only: true,
+            ..Default::default()
+        };
+        let mutants = mutate_source_str(
+            indoc! {"
+                pub fn visible() -> bool {
+                    true
+                }
+
+                pub(crate) fn crate_visible() -> bool {
+                    true
+                }
+
+                fn private() -> bool {
+                    true
+                }
+
+                pub struct S;
+
+                impl S {
+                    pub fn method(&self) -> bool {
+                        true
+                    }
+
+                    fn private_method(&self) -> bool {
+                        true
+                    }
+                }
+
+                impl Clone for S {
+                    fn clone(&self) -> S {
+                        S
+                    }
+                }
+
+                trait Private {
+                    fn provided(&self) -> bool {
+                        true
+                    }
+                }
+            "},
+            &options,
+        )
+        .unwrap();
+        assert_eq!(
+            mutants.iter().map(|m| m.name(false)).collect_vec(),
+            [
+                "src/main.rs: replace visible -> bool with false",
+                "src/main.rs: replace crate_visible -> bool with false",
+                "src/main.rs: replace S::method -> bool with false",
+                "src/main.rs: replace <impl Clone for S>::clone -> S with Default::default()",
+            ]
+        );
+    }
+
+    #[test]
+    fn pub_only_skips_private_functions_nested_in_trait_methods() {
+        let options = Options {
+            pub_only: true,
+            ..Default::default()
+        };
+        let mutants = mutate_source_str(
+            indoc! {"
+                pub struct S;
+
+                impl Clone for S {
+                    fn clone(&self) -> S {
+                        fn helper() -> bool {
+                            true
+                        }
+                        S
+                    }
+                }
+            "},
+            &options,
+        )
+        .unwrap();
+        assert_eq!(
+            mutants.iter().map(|m| m.name(false)).collect_vec(),
+            ["src/main.rs: replace <impl Clone for S>::clone -> S with Default::default()"]
+        );
+    }
+
+    #[test]
+    fn replace_unwrap_or_default_values() {
+        let mutants = mutate_source_str(
+            indoc! {r#"
+                fn count(n: Option<u32>) -> u32 {
+                    n.unwrap_or(5)
+                }
+
+                fn name(s: Option<&str>) -> &str {
+                    s.unwrap_or("anon")
+                }
+
+                fn limit(x: Option<Limit>) -> Limit {
+                    x.unwrap_or(Limit::max())
+                }
+
+                fn same(x: Option<Limit>) -> Limit {
+                    x.unwrap_or(Default::default())
+                }
+            "#},
+            &Options::default(),
+        )
+        .unwrap();
+        let unwrap_or_mutants = mutants
+            .iter()
+            .filter(|m| m.genre == Genre::UnwrapOrDefault)
+            .collect_vec();
+        assert_eq!(
+            unwrap_or_mutants.iter().map(|m| m.name(true)).collect_vec(),
+            [
+                "src/main.rs:2:17: replace 5 with 0 in count",
+                "src/main.rs:2:17: replace 5 with 1 in count",
+                r#"src/main.rs:6:17: replace "anon" with "" in name"#,
+                r#"src/main.rs:6:17: replace "anon" with "xyzzy" in name"#,
+                "src/main.rs:10:17: replace Limit::max() with Default::default() in limit",
+            ]
+        );
+        assert_eq!(
+            unwrap_or_mutants[0].mutated_code().lines().nth(1).unwrap(),
+            "    n.unwrap_or(0 /* ~ changed by cargo-mutants ~ */)"
+        );
+    }
+
+    #[test]
+    fn replacements_same_as_original_code_are_skipped() {
+        let mutants = mutate_source_str(
+            indoc! {"
+                fn zero() -> u32 {
+                    0
+                }
+
+                fn nothing() -> Option<u32> {
+                    // Not yet implemented.
+                    None
+                }
+
+                fn empty() -> Vec<String> {
+                    vec! [ ]
+                }
+            "},
+            &Options::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            mutants
+                .iter()
+                .filter(|m| m.genre == Genre::FnValue)
+                .map(|m| m.name(false))
+                .collect_vec(),
+            [
+                "src/main.rs: replace zero -> u32 with 1",
+                "src/main.rs: replace nothing -> Option<u32> with Some(0)",
+                "src/main.rs: replace nothing -> Option<u32> with Some(1)",
+                "src/main.rs: replace empty -> Vec<String> with vec![String::new()]",
+                r#"src/main.rs: replace empty -> Vec<String> with vec!["xyzzy".into()]"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn delete_early_returns_in_functions_and_closures() {
+        let mutants = mutate_source_str(
+            indoc! {"
+                fn positive(v: &[i32], strict: bool) -> Vec<Option<i32>> {
+                    if v.is_empty() {
+                        return Vec::new();
+                    }
+                    v.iter()
+                        .map(|&x| {
+                            if strict && x <= 0 {
+                                return None;
+                            }
+                            Some(x)
+                        })
+                        .collect()
+                }
+
+                fn either(c: bool) -> u32 {
+                    if c {
+                        return 1;
+                    } else {
+                        return 2;
+                    }
+                }
+            "},
+            &Options::default(),
+        )
+        .unwrap();
+        let return_mutants = mutants
+            .iter()
+            .filter(|m| m.genre == Genre::EarlyReturn)
+            .collect_vec();
+        assert_eq!(
+            return_mutants.iter().map(|m| m.name(true)).collect_vec(),
+            [
+                "src/main.rs:3:9: delete return Vec::new(); in positive",
+                "src/main.rs:8:17: delete return None; in positive",
+            ]
+        );
+        assert_eq!(
+            return_mutants[1].mutated_code().lines().nth(7).unwrap(),
+            "                 /* ~ changed by cargo-mutants ~ */"
+        );
+    }
+
+    #[test]
+    fn force_branches_of_if_let_ok_and_err() {
+        let code = indoc! {r#"
+            fn load(path: &str) -> u32 {
+                if let Ok(text) = std::fs::read_to_string(path) {
+                    text.len() as u32
+                } else {
+                    0
+                }
+            }
+
+            fn report(r: Result<u32, String>) {
+                if let Err(e) = r {
+                    eprintln!("{e}");
+                }
+            }
+
+            fn borrowed(r: Result<u32, String>) -> u32 {
+                if let Ok(n) = &r {
+                    *n
+                } else {
+                    0
+                }
+            }
+        "#};
+        let result_branch_names = |options: &Options| {
+            mutate_source_str(code, options)
+                .unwrap()
+                .iter()
+                .filter(|m| m.genre == Genre::ResultBranch)
+                .map(|m| m.name(true))
+                .collect_vec()
+        };
+        assert_eq!(
+            result_branch_names(&Options::default()),
+            [
+                "src/main.rs:2:23: replace std::fs::read_to_string(path) with std::fs::read_to_string(path).and_then(|_| Err(Default::default())) in load",
+                "src/main.rs:2:23: replace std::fs::read_to_string(path) with std::fs::read_to_string(path).or_else(|_| Ok::<_, ()>(Default::default())) in load",
+                "src/main.rs:10:21: replace r with r.or_else(|_| Ok::<_, String>(Default::default())) in report",
+            ]
+        );
+        let options = Options {
+            error_values: vec!["String::new()".to_owned()],
+            ..Options::default()
+        };
+        assert_eq!(
+            result_branch_names(&options),
+            [
+                "src/main.rs:2:23: replace std::fs::read_to_string(path) with std::fs::read_to_string(path).and_then(|_| Err(String::new())) in load",
+                "src/main.rs:2:23: replace std::fs::read_to_string(path) with std::fs::read_to_string(path).or_else(|_| Ok::<_, ()>(Default::default())) in load",
+                "src/main.rs:10:21: replace r with r.or_else(|_| Ok::<_, String>(Default::default())) in report",
+            ]
+        );
+    }
+
+    #[test]
+    fn force_err_branch_with_error_values_for_the_scrutinee_type() {
+        let code = indoc! {"
+            fn parse(s: &str) -> Result<u32, ParseIntError> {
+                s.parse()
+            }
+
+            fn parse_or_zero(s: &str) -> u32 {
+                if let Ok(n) = parse(s) {
+                    n
+                } else {
+                    0
+                }
+            }
+        "};
+        let options = Options {
+            error_values: vec!["anyhow!(\"mutated\")".to_owned()],
+            error_values_by_type: [(
+                "ParseIntError".to_owned(),
+                vec!["\"x\".parse::<u32>().unwrap_err()".to_owned()],
+            )]
+            .into(),
+            ..Options::default()
+        };
+        let names = mutate_source_str(code, &options)
+            .unwrap()
+            .iter()
+            .filter(|m| m.genre == Genre::ResultBranch)
+            .map(|m| m.name(true))
+            .collect_vec();
+        assert_eq!(
+            names,
+            [
+                r#"src/main.rs:6:20: replace parse(s) with parse(s).and_then(|_| Err("x".parse::<u32>().unwrap_err())) in parse_or_zero"#,
+                "src/main.rs:6:20: replace parse(s) with parse(s).or_else(|_| Ok::<_, ()>(Default::default())) in parse_or_zero",
+            ]
+        );
+    }
+
+    #[test]
+    fn swap_min_and_max_functions() {
+        let mutants = mutate_source_str(
+            indoc! {"
+                use std::cmp::{max, min};
+
+                fn clamp(x: u32, lo: u32, hi: u32) -> u32 {
+                    std::cmp::min(max(x, lo), hi)
+                }
+
+                fn smaller(a: f64, b: f64) -> f64 {
+                    f64::min(a, b)
+                }
+
+                fn not_a_pair(v: &[u32]) -> u32 {
+                    min(v)
+                }
+
+                const LIMIT: u32 = min(10, 20);
+            "},
+            &Options::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            mutants
+                .iter()
+                .filter(|m| m.genre == Genre::CmpFnSwap)
+                .map(|m| m.name(true))
+                .collect_vec(),
+            [
+                "src/main.rs:4:15: replace min with max in clamp",
+                "src/main.rs:4:19: replace max with min in clamp",
+                "src/main.rs:8:10: replace min with max in smaller",
+            ]
+        );
+    }
+
+    #[test]
+    fn remove_iterator_adapters() {
+        let mutants = mutate_source_str(
+            indoc! {"
+                fn last_names(names: &[String], n: usize) -> Vec<(usize, String)> {
+                    names
+                        .iter()
+                        .rev()
+                        .skip(1)
+                        .take(n)
+                        .cloned()
+                        .enumerate()
+                        .collect()
+                }
+
+                fn newest(v: &[u32]) -> Option<&u32> {
+                    v.iter().rev().next()
+                }
+            "},
+            &Options::default(),
+        )
+        .unwrap();
+        let adapter_mutants = mutants
+            .iter()
+            .filter(|m| m.genre == Genre::IterAdapter)
+            .collect_vec();
+        assert_eq!(
+            adapter_mutants.iter().map(|m| m.name(true)).collect_vec(),
+            [
+                "src/main.rs:8:9: delete .enumerate() in last_names",
+                "src/main.rs:6:9: delete .take(n) in last_names",
+                "src/main.rs:5:9: delete .skip(1) in last_names",
+                "src/main.rs:4:9: delete .rev() in last_names",
+                "src/main.rs:13:13: delete .rev() in newest",
+            ]
+        );
+        assert_eq!(
+            adapter_mutants[4].mutated_code().lines().nth(12).unwrap(),
+            "    v.iter() /* ~ changed by cargo-mutants ~ */.next()"
+        );
+    }
+
+    #[test]
+    fn remove_clone_calls() {
+        let options = Options::default();
+        let mutants = mutate_source_str(
+            indoc! {"
+                fn f(a: &String, b: Vec<u8>) -> usize {
+                    let c = a.clone();
+                    let d = b.clone().into_iter().count();
+                    c.len() + d + a.clone_from_slice()
+                }
+            "},
+            &options,
+        )
+        .unwrap();
+        let clone_mutants = mutants
+            .iter()
+            .filter(|m| m.genre == Genre::CloneRemoval)
+            .collect_vec();
+        assert_eq!(
+            clone_mutants.iter().map(|m| m.name(true)).collect_vec(),
+            [
+                "src/main.rs:2:14: delete .clone() in f",
+                "src/main.rs:3:14: delete .clone() in f",
+            ]
+        );
+        assert_eq!(
+            clone_mutants[1].mutated_code().lines().nth(2).unwrap(),
+            "    let d = b /* ~ changed by cargo-mutants ~ */.into_iter().count();"
+        );
+    }
+
+    #[test]
+    fn swap_min_and_max_method_calls() {
+        let options = Options::default();
+        let mutants = mutate_source_str(
+            indoc! {"
+                fn clamp(a: u32, b: u32, c: u32) -> u32 {
+                    a.max(b).min(c)
+                }
+
+                fn other(a: u32, b: u32) -> u32 {
+                    a.min_by(b, |x, y| x.cmp(y))
+                }
+            "},
+            &options,
+        )
+        .unwrap();
+        assert_eq!(
+            mutants
+                .iter()
+                .filter(|m| m.genre == Genre::MinMaxSwap)
+                .map(|m| m.name(true))
+                .collect_vec(),
+            [
+                "src/main.rs:2:14: replace min with max in clamp",
+                "src/main.rs:2:7: replace max with min in clamp",
+            ]
+        );
+    }
+
+    #[test]
+    fn function_namespace_segments() {
+        let mutants = mutate_source_str(
+            indoc! {"
+                mod outer {
+                    struct Counter(u32);
+
+                    impl Counter {
+                        fn get(&self) -> u32 {
+                            self.0
+                        }
+                    }
+                }
+            "},
+            &Options::default(),
+        )
+        .unwrap();
+        let function = mutants[0].function.as_ref().unwrap();
+        assert_eq!(function.function_name, "outer::Counter::get");
+        assert_eq!(function.namespace, ["outer", "Counter"]);
+        assert_eq!(function.name, "get");
+    }
+
+    #[test]
+    fn neutralize_assertions_outside_tests() {
+        let mutants = mutate_source_str(
+            indoc! {"
+                fn checked_div(a: u32, b: u32) -> u32 {
+                    assert_ne!(b, 0, \"divide by zero\");
+                    debug_assert!(a >= b);
+                    format!(\"{a}\");
+                    a / b
+                }
+
+                #[cfg(test)]
+                mod test {
+                    fn helper() {
+                        assert!(super::checked_div(4, 2) == 2);
+                    }
+                }
+            "},
+            &Options::default(),
+        )
+        .unwrap();
+        let assert_mutants = mutants
+            .iter()
+            .filter(|m| m.genre == Genre::AssertNeutralize)
+            .collect_vec();
+        assert_eq!(
+            assert_mutants.iter().map(|m| m.name(true)).collect_vec(),
+            [
+                "src/main.rs:2:5: neutralize assert_ne! in checked_div",
+                "src/main.rs:3:5: neutralize debug_assert! in checked_div",
+            ]
+        );
+        assert_eq!(
+            assert_mutants[0].mutated_code().lines().nth(1).unwrap(),
+            "    () /* ~ changed by cargo-mutants ~ */;"
+        );
+    }
+
+    #[test]
+    fn tweak_combinator_closures() {
+        let options = Options::default();
+        let mutants = mutate_source_str(
+            indoc! {"
+                fn f(a: Option<u32>, v: &[u32]) -> usize {
+                    let b = a.map(|x| x + 1).and_then(|x| x.checked_sub(2));
+                    let c = a.map(|x| x).filter(|_| true);
+                    v.iter().filter(|x| **x > 2).count() + b.map_or(0, |x| x as usize)
+                }
+            "},
+            &options,
+        )
+        .unwrap();
+        assert_eq!(
+            mutants
+                .iter()
+                .filter(|m| m.genre == Genre::CombinatorTweak)
+                .map(|m| m.name(true))
+                .collect_vec(),
+            [
+                "src/main.rs:2:29: delete .and_then(|x| x.checked_sub(2)) in f",
+                "src/main.rs:3:14: delete .map(|x| x) in f",
+            ]
+        );
+    }
+
+    #[test]
+    fn replace_predicates_with_constants() {
+        let mutants = mutate_source_str(
+            indoc! {"
+                fn f(v: &mut Vec<u32>) -> usize {
+                    v.retain(|x| *x != 0);
+                    let a = v.iter().filter(|_| true).count();
+                    v.iter().take_while(|x| **x < 9).count() + a
+                }
+            "},
+            &Options::default(),
+        )
+        .unwrap();
+        let names = |genre| {
+            mutants
+                .iter()
+                .filter(|m| m.genre == genre)
+                .map(|m| m.name(true))
+                .collect_vec()
+        };
+        assert_eq!(
+            names(Genre::PredicateConst),
+            [
+                "src/main.rs:2:14: replace |x| *x != 0 with |_| true in f",
+                "src/main.rs:2:14: replace |x| *x != 0 with |_| false in f",
+                "src/main.rs:3:29: replace |_| true with |_| false in f",
+                "src/main.rs:4:25: replace |x| **x < 9 with |_| true in f",
+                "src/main.rs:4:25: replace |x| **x < 9 with |_| false in f",
+            ]
+        );
+        // Operators inside the closures are mutated too.
+        assert_eq!(
+            names(Genre::BinaryOperator),
+            [
+                "src/main.rs:2:21: replace != with == in f",
+                "src/main.rs:4:46: replace + with - in f",
+                "src/main.rs:4:46: replace + with * in f",
+                "src/main.rs:4:33: replace < with == in f",
+                "src/main.rs:4:33: replace < with > in f",
+                "src/main.rs:4:33: replace < with <= in f",
+            ]
+        );
+    }
+
+    #[test]
+    fn swap_integer_overflow_policy() {
+        let mutants = mutate_source_str(
+            indoc! {"
+                fn f(a: u32, b: u32) -> u32 {
+                    let c = a.saturating_add(b).wrapping_mul(2);
+                    let d = a.checked_sub(b).unwrap_or(0).wrapping_pow(3);
+                    c.saturating_div(b) + d.overflowing_add(1).0
+                }
+            "},
+            &Options::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            mutants
+                .iter()
+                .filter(|m| m.genre == Genre::OverflowPolicy)
+                .map(|m| m.name(true))
+                .collect_vec(),
+            [
+                "src/main.rs:2:33: replace wrapping_mul with saturating_mul in f",
+                "src/main.rs:2:15: replace saturating_add with wrapping_add in f",
+                "src/main.rs:3:43: replace wrapping_pow with saturating_pow in f",
+            ]
+        );
+    }
+
+    #[test]
+    fn delete_collection_update_statements() {
+        let mutants = mutate_source_str(
+            indoc! {"
+                fn f(v: &mut Vec<u32>, s: &mut HashSet<u32>) -> bool {
+                    v.push(1);
+                    s.insert(2);
+                    v.extend([3, 4]);
+                    let added = s.insert(5);
+                    added && s.insert(6)
+                }
+            "},
+            &Options::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            mutants
+                .iter()
+                .filter(|m| m.genre == Genre::CollectionMutation)
+                .map(|m| m.name(true))
+                .collect_vec(),
+            [
+                "src/main.rs:2:5: delete v.push(1); in f",
+                "src/main.rs:3:5: delete s.insert(2); in f",
+                "src/main.rs:4:5: delete v.extend([3, 4]); in f",
+            ]
+        );
+    }
+
+    #[test]
+    fn replace_float_literals() {
+        let mutants = mutate_source_str(
+            indoc! {"
+                const SCALE: f64 = 2.5;
+
+                fn damp(x: f32, v: [f64; 2]) -> f32 {
+                    let tolerance = 1e-9;
+                    x * 0.5f32 + 1.0 + 0.0
+                }
+            "},
+            &Options::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            mutants
+                .iter()
+                .filter(|m| m.genre == Genre::LiteralFloat)
+                .map(|m| m.name(true))
+                .collect_vec(),
+            [
+                "src/main.rs:4:21: replace 1e-9 with 0.0 in damp",
+                "src/main.rs:4:21: replace 1e-9 with 1.0 in damp",
+                "src/main.rs:4:21: replace 1e-9 with -1e-9 in damp",
+                "src/main.rs:5:9: replace 0.5f32 with 0.0f32 in damp",
+                "src/main.rs:5:9: replace 0.5f32 with 1.0f32 in damp",
+                "src/main.rs:5:9: replace 0.5f32 with -0.5f32 in damp",
+                "src/main.rs:5:18: replace 1.0 with 0.0 in damp",
+                "src/main.rs:5:18: replace 1.0 with -1.0 in damp",
+                "src/main.rs:5:24: replace 0.0 with 1.0 in damp",
+            ]
+        );
+    }
+
+    #[test]
+    fn float_literals_in_const_generic_args_are_not_mutated() {
+        let mutants = mutate_source_str(
+            indoc! {"
+                fn f() -> usize {
+                    Buf::<{ 2.0 as usize }>::len()
+                }
+            "},
+            &Options::default(),
+        )
+        .unwrap();
+        assert!(!mutants.iter().any(|m| m.genre == Genre::LiteralFloat));
+    }
+
+    #[test]
+    fn flip_bool_literals() {
+        let mutants = mutate_source_str(
+            indoc! {"
+                fn configure() {
+                    let enabled = true;
+                    start(enabled, false);
+                }
+
+                fn always_true() -> bool {
+                    true
+                }
+
+                #[cfg(test)]
+                mod test {
+                    fn fake_config() -> Config {
+                        let verbose = true;
+                        Config { verbose }
+                    }
+                }
+            "},
+            &Options::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            mutants
+                .iter()
+                .filter(|m| m.genre == Genre::LiteralBool)
+                .map(|m| m.name(true))
+                .collect_vec(),
+            [
+                "src/main.rs:2:19: replace true with false in configure",
+                "src/main.rs:3:20: replace false with true in configure",
+            ]
+        );
+    }
+
+    #[test]
+    fn mutate_for_loop_range_bounds() {
+        let mutants = mutate_source_str(
+            indoc! {"
+                fn sum(v: &[u32], n: usize) -> u32 {
+                    let mut total = 0;
+                    for i in 0..n {
+                        total += v[i];
+                    }
+                    for j in 1..v.len() {
+                        total += v[j];
+                    }
+                    for k in 0..n << 1 {
+                        total += v[k];
+                    }
+                    for c in 'a'..'z' {
+                        print(c);
+                    }
+                    for x in v.iter() {
+                        total += x;
+                    }
+                    total
+                }
+            "},
+            &Options::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            mutants
+                .iter()
+                .filter(|m| m.genre == Genre::LoopBound)
+                .map(|m| m.name(true))
+                .collect_vec(),
+            [
+                "src/main.rs:3:17: replace n with n - 1 in sum",
+                "src/main.rs:3:17: replace n with n + 1 in sum",
+                "src/main.rs:3:17: replace n with 0 in sum",
+                "src/main.rs:6:17: replace v.len() with v.len() - 1 in sum",
+                "src/main.rs:6:17: replace v.len() with v.len() + 1 in sum",
+                "src/main.rs:6:17: replace v.len() with 1 in sum",
+                "src/main.rs:9:17: replace n << 1 with (n << 1) - 1 in sum",
+                "src/main.rs:9:17: replace n << 1 with (n << 1) + 1 in sum",
+                "src/main.rs:9:17: replace n << 1 with 0 in sum",
+            ]
+        );
+    }
+
+    #[test]
+    fn replace_static_values() {
+        let mutants = mutate_source_str(
+            indoc! {"
+                static LIMIT: usize = 10;
+                static NAME: &str = \"demo\";
+                static TRIGGER: AtomicBool = AtomicBool::new(false);
+                static EMPTY: Vec<u32> = Vec::new();
+                static GREETING: String = String::new();
+                static MAYBE: Option<u8> = None;
+                static CONFIG: LazyLock<Vec<String>> = LazyLock::new(|| {
+                    let home = home_dir();
+                    vec![home]
+                });
+                #[mutants::skip]
+                static SKIPPED: bool = true;
+                static COMPUTED: bool = 3 == (2 + 1);
+                #[cfg(test)]
+                static TEST_ONLY: u32 = 5;
+                #[cfg(feature = \"unknown\")]
+                static GATED: u32 = 5;
+            "},
+            &Options::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            mutants
+                .iter()
+                .filter(|m| m.genre == Genre::StaticValue)
+                .map(|m| m.name(true))
+                .collect_vec(),
+            [
+                "src/main.rs:1:23: replace 10 with 0",
+                "src/main.rs:1:23: replace 10 with 1",
+                "src/main.rs:2:21: replace \"demo\" with \"\"",
+                "src/main.rs:2:21: replace \"demo\" with \"xyzzy\"",
+                "src/main.rs:6:28: replace None with Some(0)",
+                "src/main.rs:6:28: replace None with Some(1)",
+                "src/main.rs:9:5: replace vec![home] with vec![]",
+                "src/main.rs:9:5: replace vec![home] with vec![String::new()]",
+                "src/main.rs:9:5: replace vec![home] with vec![\"xyzzy\".into()]",
+            ]
+        );
+    }
+
+    #[test]
+    fn pub_only_skips_private_statics() {
+        let mutants = mutate_source_str(
+            indoc! {"
+                pub static PUBLIC: u32 = 5;
+                static PRIVATE: u32 = 5;
+            "},
+            &Options {
+                pub_only: true,
+                ..Options::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            mutants.iter().map(|m| m.name(true)).collect_vec(),
+            [
+                "src/main.rs:1:26: replace 5 with 0",
+                "src/main.rs:1:26: replace 5 with 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn blank_write_macro_output() {
+        let mutants = mutate_source_str(
+            indoc! {r#"
+                fn render(out: &mut String, name: &str) -> fmt::Result {
+                    write!(out, "hello {}", name)?;
+                    writeln!(out, "!")?;
+                    std::writeln!(out)?;
+                    write!(out, "")
+                }
+
+                #[cfg(test)]
+                mod test {
+                    fn helper(out: &mut String) {
+                        write!(out, "test").unwrap();
+                    }
+                }
+            "#},
+            &Options::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            mutants
+                .iter()
+                .filter(|m| m.genre == Genre::FormatMutation)
+                .map(|m| m.name(true))
+                .collect_vec(),
+            [
+                r#"src/main.rs:2:17: replace "hello {}", name with "" in render"#,
+                r#"src/main.rs:3:19: replace "!" with "" in render"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn prune_condition_operands() {
+        let mutants = mutate_source_str(
+            indoc! {"
+                fn ready(a: bool, b: u32, c: &[u8]) -> u32 {
+                    if a && b > 0 {
+                        return 1;
+                    }
+                    if let Some(x) = c.first() && *x > 0 {
+                        return 2;
+                    }
+                    if a || (b == 2 && c.is_empty()) {
+                        return 3;
+                    }
+                    0
+                }
+            "},
+            &Options::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            mutants
+                .iter()
+                .filter(|m| m.genre == Genre::ConditionPrune)
+                .map(|m| m.name(true))
+                .collect_vec(),
+            [
+                "src/main.rs:2:8: replace a && b > 0 with a in ready",
+                "src/main.rs:2:8: replace a && b > 0 with b > 0 in ready",
+                "src/main.rs:8:8: replace a || (b == 2 && c.is_empty()) with a in ready",
+                "src/main.rs:8:8: replace a || (b == 2 && c.is_empty()) with (b == 2 && c.is_empty()) in ready",
+                "src/main.rs:8:14: replace b == 2 && c.is_empty() with b == 2 in ready",
+                "src/main.rs:8:14: replace b == 2 && c.is_empty() with c.is_empty() in ready",
+            ]
+        );
+    }
+
+    #[test]
+    fn swap_break_and_continue() {
+        let mutants = mutate_source_str(
+            indoc! {"
+                fn first_even(v: &[u32]) -> Option<u32> {
+                    let mut found = None;
+                    'outer: for x in v {
+                        if x % 2 == 1 {
+                            continue;
+                        }
+                        let done = loop {
+                            break true;
+                        };
+                        'block: {
+                            if done {
+                                break 'block;
+                            }
+                        }
+                        v.iter().for_each(|_| {});
+                        found = Some(*x);
+                        while found.is_some() {
+                            break 'outer;
+                        }
+                    }
+                    found
+                }
+            "},
+            &Options::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            mutants
+                .iter()
+                .filter(|m| m.genre == Genre::LoopControl)
+                .map(|m| m.name(true))
+                .collect_vec(),
+            [
+                "src/main.rs:5:13: replace continue with break in first_even",
+                "src/main.rs:18:13: replace break 'outer with continue 'outer in first_even",
             ]
         );
     }
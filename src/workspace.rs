@@ -30,6 +30,7 @@ use tracing::{debug, error, warn};
 use crate::cargo::cargo_bin;
 use crate::console::Console;
 use crate::interrupt::check_interrupted;
+use crate::mutant::Mutant;
 use crate::options::Options;
 use crate::package::{packages_from_metadata, Package, PackageSelection};
 use crate::visit::{walk_tree, Discovered};
@@ -88,6 +89,11 @@ impl Workspace {
         &self.metadata.workspace_root
     }
 
+    /// The `[package.metadata.mutants]` table from the root package's `Cargo.toml`, if any.
+    pub fn mutants_metadata(&self) -> Option<&serde_json::Value> {
+        self.metadata.root_package()?.metadata.get("mutants")
+    }
+
     /// Open the workspace containing a given directory.
     pub fn open<P: AsRef<Path>>(start_dir: P) -> Result<Self> {
         let start_dir = start_dir.as_ref();
@@ -164,6 +170,17 @@ impl Workspace {
             }
             PackageFilter::All => Ok(PackageSelection::All),
             PackageFilter::Explicit(names) => {
+                // Mutating a package that doesn't exist is most likely a typo, and would
+                // otherwise silently test nothing.
+                if let Some(name) = names
+                    .iter()
+                    .find(|name| !self.packages.iter().any(|p| p.name == **name))
+                {
+                    bail!(
+                        "Package {name:?} not found in source tree; available packages are: {}",
+                        self.packages.iter().map(|p| &p.name).join(", ")
+                    );
+                }
                 Ok(PackageSelection::Explicit(self.packages_by_name(names)))
             }
         }
@@ -183,12 +200,30 @@ impl Workspace {
         options: &Options,
         console: &Console,
     ) -> Result<Discovered> {
-        walk_tree(
+        self.discover_filtered(package_filter, options, console, |_| true)
+    }
+
+    /// Make all the mutants from the filtered packages in this workspace, and then keep
+    /// only those for which `filter` returns true.
+    ///
+    /// The filter is applied after all other filtering options, such as `--file` and
+    /// `--exclude-re`, so it's only called for mutants that would otherwise be tested.
+    pub fn discover_filtered(
+        &self,
+        package_filter: &PackageFilter,
+        options: &Options,
+        console: &Console,
+        filter: impl Fn(&Mutant) -> bool,
+    ) -> Result<Discovered> {
+        let mut discovered = walk_tree(
             self.root(),
             &self.expand_selection(self.filter_packages(package_filter)?),
             options,
             console,
-        )
+        )?;
+        discovered.remove_excluded_ranges(&options.exclude_ranges);
+        discovered.mutants.retain(|mutant| filter(mutant));
+        Ok(discovered)
     }
 
     /// Return the default workspace packages.
@@ -413,4 +448,27 @@ mod test {
             ["main/src/main.rs", "main2/src/main.rs"]
         );
     }
+
+    #[test]
+    fn discover_filtered_keeps_only_accepted_mutants() {
+        let tmp = copy_of_testdata("factorial");
+        let workspace = Workspace::open(tmp.path()).expect("Find workspace root");
+        let discovered = workspace
+            .discover_filtered(
+                &PackageFilter::All,
+                &Options::default(),
+                &Console::new(),
+                |mutant| mutant.function.as_ref().unwrap().name == "main",
+            )
+            .unwrap();
+        assert_eq!(
+            discovered
+                .mutants
+                .iter()
+                .map(|mutant| mutant.name(false))
+                .collect_vec(),
+            ["src/bin/factorial.rs: replace main with ()"]
+        );
+        assert_eq!(discovered.files.len(), 1);
+    }
 }
@@ -0,0 +1,14 @@
+#[cfg(feature = "stats")]
+pub mod stats;
+
+pub fn double(x: u32) -> u32 {
+    x * 2
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn double() {
+        assert_eq!(super::double(4), 8);
+    }
+}
@@ -0,0 +1,11 @@
+pub fn mean(values: &[u32]) -> u32 {
+    values.iter().sum::<u32>() / values.len() as u32
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn mean() {
+        assert_eq!(super::mean(&[2, 4, 6]), 4);
+    }
+}
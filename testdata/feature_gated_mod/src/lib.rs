@@ -0,0 +1,21 @@
+#[cfg(feature = "simd")]
+mod simd;
+
+#[cfg(not(feature = "simd"))]
+mod fallback;
+
+#[cfg(feature = "std")]
+pub fn sum(values: &[u32]) -> u32 {
+    #[cfg(feature = "simd")]
+    return simd::sum(values);
+    #[cfg(not(feature = "simd"))]
+    return fallback::sum(values);
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn sum() {
+        assert_eq!(super::sum(&[1, 2, 3]), 6);
+    }
+}
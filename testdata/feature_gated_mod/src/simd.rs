@@ -0,0 +1,10 @@
+// This would need a nightly compiler, but it's not built without the `simd` feature.
+use std::simd::u32x4;
+
+pub fn sum(values: &[u32]) -> u32 {
+    let (chunks, rest) = values.as_chunks::<4>();
+    let total = chunks
+        .iter()
+        .fold(u32x4::splat(0), |acc, chunk| acc + u32x4::from_array(*chunk));
+    total.to_array().iter().sum::<u32>() + rest.iter().sum::<u32>()
+}
@@ -0,0 +1,30 @@
+//! A test that fails the first time it sees each wrong answer, and passes after that.
+//!
+//! This simulates a flaky test, so we can check that `--retry` reruns the tests for
+//! caught mutants, and reports them as missed if the tests pass on a retry.
+
+pub fn double(a: u32) -> u32 {
+    a * 2
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::path::Path;
+
+    use super::*;
+
+    #[test]
+    fn double_four() {
+        let result = double(4);
+        if result != 8 {
+            // Remember this wrong answer, in the build directory, so that it's let
+            // through next time.
+            let marker = Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("seen_{result}"));
+            if !marker.exists() {
+                fs::write(&marker, "").unwrap();
+                panic!("double(4) returned {result}");
+            }
+        }
+    }
+}
@@ -0,0 +1,20 @@
+/// Parse a port number, falling back to a default if it's invalid.
+///
+/// The tests only pass valid ports, so forcing the `Ok` branch is missed.
+pub fn parse_port(s: &str) -> u16 {
+    if let Ok(port) = s.parse::<u16>() {
+        port
+    } else {
+        8080
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn valid_port() {
+        assert_eq!(parse_port("1234"), 1234);
+    }
+}
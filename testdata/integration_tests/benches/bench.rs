@@ -0,0 +1,10 @@
+use cargo_mutants_testdata_integration_tests::double;
+
+fn main() {
+    assert_eq!(double(21), forty_two());
+}
+
+/// Benchmarks are never mutated.
+fn forty_two() -> u32 {
+    42
+}
@@ -0,0 +1,10 @@
+use cargo_mutants_testdata_integration_tests::double;
+
+fn main() {
+    println!("{}", quadruple(3));
+}
+
+/// Examples are only mutated with `--examine-examples`.
+fn quadruple(n: u32) -> u32 {
+    double(double(n))
+}
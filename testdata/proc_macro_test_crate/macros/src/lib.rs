@@ -0,0 +1,23 @@
+use std::iter::once;
+
+use proc_macro::{Literal, TokenStream, TokenTree};
+
+/// Count the comma-separated items passed to the macro.
+#[proc_macro]
+pub fn count_items(item: TokenStream) -> TokenStream {
+    once(TokenTree::Literal(Literal::usize_unsuffixed(count(item)))).collect()
+}
+
+fn count(item: TokenStream) -> usize {
+    let mut count = 0;
+    let mut in_item = false;
+    for tt in item {
+        if matches!(&tt, TokenTree::Punct(p) if p.as_char() == ',') {
+            in_item = false;
+        } else if !in_item {
+            in_item = true;
+            count += 1;
+        }
+    }
+    count
+}
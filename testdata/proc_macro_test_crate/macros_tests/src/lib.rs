@@ -0,0 +1 @@
+//! Tests for the macros are in `tests/`.
@@ -0,0 +1,11 @@
+use cargo_mutants_testdata_proc_macro_test_crate_macros::count_items;
+
+#[test]
+fn count_several() {
+    assert_eq!(count_items!(a, b + 1, c), 3);
+}
+
+#[test]
+fn count_none() {
+    assert_eq!(count_items!(), 0);
+}
@@ -0,0 +1,45 @@
+//! A ring buffer index that checks its invariants with assertions.
+//!
+//! The test for `Slot::at` checks that an out-of-range slot is rejected, so
+//! neutralizing that assertion is caught. Nothing tests the assertion in
+//! `Slot::advance`, so neutralizing it is missed.
+
+pub struct Slot {
+    index: usize,
+    capacity: usize,
+}
+
+impl Slot {
+    pub fn at(index: usize, capacity: usize) -> Slot {
+        assert!(index < capacity, "slot {index} out of range");
+        Slot { index, capacity }
+    }
+
+    pub fn advance(&mut self) {
+        self.index = (self.index + 1) % self.capacity;
+        debug_assert!(self.index < self.capacity);
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn advance_wraps() {
+        let mut slot = Slot::at(2, 3);
+        assert_eq!(slot.index(), 2);
+        slot.advance();
+        assert_eq!(slot.index(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn at_rejects_out_of_range() {
+        Slot::at(3, 3);
+    }
+}
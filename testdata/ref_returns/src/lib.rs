@@ -0,0 +1,36 @@
+//! Getters that return references to values owned by `self`.
+//!
+//! Mutants of these must return references that live long enough, either to
+//! promoted constants or to leaked values.
+
+use std::collections::HashMap;
+
+pub struct Inventory {
+    bytes: Vec<u8>,
+    counts: HashMap<String, u32>,
+    label: String,
+    size: u32,
+    owner: Option<String>,
+}
+
+impl Inventory {
+    pub fn bytes(&self) -> &Vec<u8> {
+        &self.bytes
+    }
+
+    pub fn counts(&self) -> &HashMap<String, u32> {
+        &self.counts
+    }
+
+    pub fn label(&self) -> &String {
+        &self.label
+    }
+
+    pub fn size(&self) -> &u32 {
+        &self.size
+    }
+
+    pub fn owner(&self) -> &Option<String> {
+        &self.owner
+    }
+}
@@ -0,0 +1,7 @@
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    Empty,
+    Invalid,
+}
+
+pub type Result<T> = std::result::Result<T, ParseError>;
@@ -0,0 +1,29 @@
+//! Functions returning the crate's own `Result<T>` alias, which is defined in a module
+//! that's visited after this file.
+
+mod error;
+
+pub use error::{ParseError, Result};
+
+pub fn parse_count(s: &str) -> Result<u32> {
+    if s.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    s.parse().map_err(|_| ParseError::Invalid)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_valid_count() {
+        assert_eq!(parse_count("12"), Ok(12));
+    }
+
+    #[test]
+    fn parse_invalid_counts() {
+        assert_eq!(parse_count(""), Err(ParseError::Empty));
+        assert_eq!(parse_count("twelve"), Err(ParseError::Invalid));
+    }
+}
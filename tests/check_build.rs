@@ -2,6 +2,8 @@
 
 //! Tests for `--check`
 
+use std::fs::read_to_string;
+
 use indoc::indoc;
 use predicates::prelude::*;
 use pretty_assertions::assert_eq;
@@ -63,6 +65,46 @@ fn small_well_tested_tree_check_only_shuffled() {
     );
 }
 
+#[test]
+fn shuffle_seed_is_logged_and_reproduces_order() {
+    let tmp_src_dir = copy_of_testdata("small_well_tested");
+    let run_with_seed = || {
+        let assert = run()
+            .args(["mutants", "--check", "--no-times", "--shuffle-seed=20"])
+            .current_dir(tmp_src_dir.path())
+            .assert()
+            .success();
+        String::from_utf8(assert.get_output().stdout.clone()).unwrap()
+    };
+    assert_eq!(run_with_seed(), run_with_seed());
+    let debug_log = read_to_string(tmp_src_dir.path().join("mutants.out/debug.log")).unwrap();
+    assert!(debug_log.contains("Shuffling mutants with --shuffle-seed=20"));
+}
+
+#[test]
+fn random_shuffle_seed_is_shown() {
+    let tmp_src_dir = copy_of_testdata("small_well_tested");
+    run()
+        .args(["mutants", "--check", "--no-times", "--shuffle"])
+        .current_dir(tmp_src_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"\nShuffled with --shuffle-seed=\d+\n").unwrap());
+}
+
+#[test]
+fn reference_return_mutants_are_viable() {
+    // References to values that aren't constants are leaked, rather than borrowing a
+    // temporary, which wouldn't compile.
+    let tmp_src_dir = copy_of_testdata("ref_returns");
+    run()
+        .args(["mutants", "--check", "--no-times", "--no-shuffle"])
+        .current_dir(tmp_src_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("15 mutants tested: 15 succeeded"));
+}
+
 #[test]
 fn warning_when_no_mutants_found() {
     let tmp_src_dir = copy_of_testdata("everything_skipped");
@@ -124,14 +166,15 @@ fn check_tree_with_mutants_skip() {
         .assert()
         .success()
         .stdout(indoc! { r"
-            Found 5 mutants to test
+            Found 7 mutants to test
             ok       Unmutated baseline
             ok       src/lib.rs:15:5: replace controlled_loop with ()
+            ok       src/lib.rs:19:13: replace break with continue in controlled_loop
             ok       src/lib.rs:21:28: replace > with == in controlled_loop
             ok       src/lib.rs:21:28: replace > with < in controlled_loop
             ok       src/lib.rs:21:53: replace * with + in controlled_loop
             ok       src/lib.rs:21:53: replace * with / in controlled_loop
-            5 mutants tested: 5 succeeded
+            7 mutants tested: 1 unviable, 6 succeeded
             "})
         .stderr("");
     assert_eq!(
@@ -139,10 +182,10 @@ fn check_tree_with_mutants_skip() {
         serde_json::json!({
             "caught": 0,
             "missed": 0,
-            "success": 5,
+            "success": 6,
             "timeout": 0,
-            "unviable": 0,
-            "total_mutants": 5,
+            "unviable": 1,
+            "total_mutants": 7,
         })
     );
 }
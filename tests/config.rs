@@ -2,7 +2,8 @@
 
 //! Test handling of `mutants.toml` configuration.
 
-use std::fs::{create_dir, write};
+use std::fs::{create_dir, write, OpenOptions};
+use std::io::Write;
 
 use indoc::indoc;
 use insta::assert_snapshot;
@@ -20,6 +21,14 @@ fn write_config_file(tempdir: &TempDir, config: &str) {
     write(path.join(".cargo/mutants.toml"), config.as_bytes()).unwrap();
 }
 
+fn append_package_metadata(tempdir: &TempDir, config: &str) {
+    let mut manifest = OpenOptions::new()
+        .append(true)
+        .open(tempdir.path().join("Cargo.toml"))
+        .unwrap();
+    write!(manifest, "\n[package.metadata.mutants]\n{config}").unwrap();
+}
+
 #[test]
 fn invalid_toml_rejected() {
     let testdata = copy_of_testdata("well_tested");
@@ -102,6 +111,100 @@ fn list_with_config_file_inclusion() {
         .stdout(predicates::str::contains("simple_fns.rs").not());
 }
 
+#[test]
+fn config_option_reads_file_outside_tree() {
+    let testdata = copy_of_testdata("well_tested");
+    // The tree's own config is ignored when `--config` is given.
+    write_config_file(
+        &testdata,
+        r#"examine_globs = ["src/simple_fns.rs"]
+        "#,
+    );
+    let config_dir = TempDir::new().unwrap();
+    let config_path = config_dir.path().join("shared.toml");
+    write(&config_path, r#"examine_globs = ["src/*_mod.rs"]"#).unwrap();
+    run()
+        .args(["mutants", "--list-files", "-d"])
+        .arg(testdata.path())
+        .arg("--config")
+        .arg(&config_path)
+        .assert()
+        .success()
+        .stdout(predicates::str::diff(indoc! { "\
+            src/inside_mod.rs
+            src/item_mod.rs
+        " }));
+}
+
+#[test]
+fn config_from_package_metadata_is_honored() {
+    let testdata = copy_of_testdata("well_tested");
+    append_package_metadata(&testdata, r#"examine_globs = ["src/*_mod.rs"]"#);
+    run()
+        .args(["mutants", "--list-files", "-d"])
+        .arg(testdata.path())
+        .assert()
+        .success()
+        .stdout(predicates::str::diff(indoc! { "\
+            src/inside_mod.rs
+            src/item_mod.rs
+        " }));
+}
+
+#[test]
+fn config_file_takes_precedence_over_package_metadata() {
+    let testdata = copy_of_testdata("well_tested");
+    append_package_metadata(
+        &testdata,
+        indoc! { r#"
+            examine_globs = ["src/simple_fns.rs"]
+            exclude_globs = ["src/inside_mod.rs"]
+        "# },
+    );
+    write_config_file(&testdata, r#"examine_globs = ["src/*_mod.rs"]"#);
+    run()
+        .args(["mutants", "--list-files", "-d"])
+        .arg(testdata.path())
+        .assert()
+        .success()
+        .stdout(predicates::str::diff("src/item_mod.rs\n"));
+    // With `--no-config`, the package metadata is ignored too.
+    run()
+        .args(["mutants", "--list-files", "--no-config", "-d"])
+        .arg(testdata.path())
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("src/simple_fns.rs"))
+        .stdout(predicates::str::contains("src/inside_mod.rs"));
+}
+
+#[test]
+fn invalid_field_in_package_metadata_rejected() {
+    let testdata = copy_of_testdata("well_tested");
+    append_package_metadata(&testdata, "wobble = false\n");
+    run()
+        .args(["mutants", "--list-files", "-d"])
+        .arg(testdata.path())
+        .assert()
+        .failure()
+        .stderr(
+            predicates::str::contains("parse [package.metadata.mutants] from Cargo.toml")
+                .and(predicates::str::contains("unknown field `wobble`")),
+        );
+}
+
+#[test]
+fn missing_config_file_is_an_error() {
+    let testdata = copy_of_testdata("well_tested");
+    run()
+        .args(["mutants", "--list-files", "-d"])
+        .arg(testdata.path())
+        .args(["--config", "nonexistent.toml"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("read config"));
+}
+
 #[test]
 fn file_argument_overrides_config_examine_globs_key() {
     let testdata = copy_of_testdata("well_tested");
@@ -311,3 +414,49 @@ fn output_option_use_config() {
         assert!(mutants_out.join(name).is_file(), "{name} is in mutants.out",);
     }
 }
+
+#[test]
+fn always_examine_names_overrides_exclusions() {
+    let testdata = copy_of_testdata("well_tested");
+    write_config_file(
+        &testdata,
+        indoc! { r#"
+            exclude_globs = ["src/booleans.rs"]
+            always_examine_names = ["xor"]
+        "#},
+    );
+    run()
+        .args(["mutants", "--list", "--exclude-re", "xor", "-d"])
+        .arg(testdata.path())
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("src/booleans.rs:10:5: replace xor -> bool with true")
+                .and(predicates::str::contains(
+                    "src/booleans.rs:10:7: replace ^ with | in xor",
+                ))
+                .and(predicates::str::contains("replace and -> bool").not())
+                .and(predicates::str::contains("src/simple_fns.rs")),
+        );
+}
+
+#[test]
+fn always_examine_names_overrides_in_diff() {
+    let testdata = copy_of_testdata("well_tested");
+    write_config_file(&testdata, "always_examine_names = [\"xor\"]\n");
+    let diff_path = testdata.path().join("empty.diff");
+    write(&diff_path, b"").unwrap();
+    run()
+        .args(["mutants", "--list", "--in-diff"])
+        .arg(&diff_path)
+        .arg("-d")
+        .arg(testdata.path())
+        .assert()
+        .success()
+        .stdout(predicates::str::diff(indoc! { "\
+            src/booleans.rs:10:5: replace xor -> bool with true
+            src/booleans.rs:10:5: replace xor -> bool with false
+            src/booleans.rs:10:7: replace ^ with | in xor
+            src/booleans.rs:10:7: replace ^ with & in xor
+        " }));
+}
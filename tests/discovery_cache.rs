@@ -0,0 +1,94 @@
+// Copyright 2025 Martin Pool
+
+//! Test `--discovery-cache`.
+
+use std::fs::{create_dir, write, OpenOptions};
+use std::io::Write;
+
+mod util;
+use util::{copy_of_testdata, run};
+
+#[test]
+fn touching_one_file_only_rediscovers_that_file() {
+    let tmp = copy_of_testdata("well_tested");
+    let list = || {
+        let output = run()
+            .args(["mutants", "--list", "--discovery-cache", "-Ldebug"])
+            .current_dir(tmp.path())
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        (
+            String::from_utf8(output.stdout).unwrap(),
+            String::from_utf8(output.stderr).unwrap(),
+        )
+    };
+    let discovered_lines = |stderr: &str| {
+        stderr
+            .lines()
+            .filter(|line| line.contains("Discovering mutants in changed file"))
+            .map(str::to_owned)
+            .collect::<Vec<_>>()
+    };
+
+    let (first_list, first_stderr) = list();
+    assert!(tmp
+        .path()
+        .join("mutants.out.cache/discovery.json")
+        .is_file());
+    assert!(!first_stderr.contains("Reusing cached mutants"));
+
+    let (second_list, second_stderr) = list();
+    assert_eq!(second_list, first_list);
+    assert_eq!(discovered_lines(&second_stderr), Vec::<String>::new());
+
+    OpenOptions::new()
+        .append(true)
+        .open(tmp.path().join("src/numbers.rs"))
+        .unwrap()
+        .write_all(b"\npub fn added() -> u32 {\n    7\n}\n")
+        .unwrap();
+    let (third_list, third_stderr) = list();
+    let discovered = discovered_lines(&third_stderr);
+    assert_eq!(discovered.len(), 1, "{third_stderr}");
+    assert!(discovered[0].contains("src/numbers.rs"));
+    assert!(third_stderr.contains("Reusing cached mutants path=\"src/booleans.rs\""));
+    assert!(third_list.contains("replace added -> u32 with 0"));
+
+    let uncached_list = run()
+        .args(["mutants", "--list"])
+        .current_dir(tmp.path())
+        .output()
+        .unwrap()
+        .stdout;
+    assert_eq!(String::from_utf8(uncached_list).unwrap(), third_list);
+}
+
+#[test]
+fn changing_error_values_rediscovers_mutants() {
+    let tmp = copy_of_testdata("well_tested");
+    let list = || {
+        run()
+            .args(["mutants", "--list", "--discovery-cache"])
+            .current_dir(tmp.path())
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone()
+    };
+    let first_list = String::from_utf8(list()).unwrap();
+    assert!(!first_list.contains("with Err(())"));
+
+    create_dir(tmp.path().join(".cargo")).unwrap();
+    write(
+        tmp.path().join(".cargo/mutants.toml"),
+        "error_values = [\"()\"]\n",
+    )
+    .unwrap();
+    let second_list = String::from_utf8(list()).unwrap();
+    assert!(
+        second_list.contains("replace simple_result -> Result<&'static str, ()> with Err(())"),
+        "{second_list}"
+    );
+}
@@ -185,3 +185,19 @@ fn fail_when_error_value_does_not_parse() {
         "}))
         .stdout(predicate::str::is_empty());
 }
+
+#[test]
+fn error_value_for_type_of_result_alias_defined_in_later_module() {
+    let tmp_src_dir = copy_of_testdata("result_alias");
+    run()
+        .args(["mutants", "--list", "-d"])
+        .arg(tmp_src_dir.path())
+        .assert()
+        .success()
+        .stdout(indoc! { "
+            src/lib.rs:9:5: replace parse_count -> Result<u32> with Ok(0)
+            src/lib.rs:9:5: replace parse_count -> Result<u32> with Ok(1)
+            src/lib.rs:9:5: replace parse_count -> Result<u32> with Err(crate::ParseError::Empty)
+            src/lib.rs:10:9: delete return Err(ParseError::Empty); in parse_count
+        "});
+}
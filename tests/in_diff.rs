@@ -1,7 +1,9 @@
 // Copyright 2023 Martin Pool
 
-use std::fs::read_to_string;
+use std::fs::{copy, read_to_string};
 use std::io::Write;
+use std::path::Path;
+use std::process::Command;
 
 use indoc::indoc;
 use predicates::prelude::predicate;
@@ -171,3 +173,60 @@ fn diff_with_multiple_deletions_is_ok() {
         ))
         .success();
 }
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .expect("run git");
+    assert!(status.success(), "git {args:?} failed");
+}
+
+/// `--since` diffs the working tree against a git ref, including uncommitted changes.
+#[test]
+fn list_mutants_changed_since_git_ref() {
+    let tmp = copy_of_testdata("diff0");
+    git(tmp.path(), &["init", "-q"]);
+    git(tmp.path(), &["add", "Cargo.toml", "src"]);
+    git(tmp.path(), &["commit", "-q", "-m", "initial"]);
+    copy("testdata/diff1/src/lib.rs", tmp.path().join("src/lib.rs")).unwrap();
+
+    run()
+        .args(["mutants", "--no-shuffle", "--list", "--since", "HEAD", "-d"])
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(indoc! { "\
+            src/lib.rs:6:5: replace two -> String with String::new()
+            src/lib.rs:6:5: replace two -> String with \"xyzzy\".into()
+        "});
+}
+
+#[test]
+fn since_unknown_git_ref_is_an_error() {
+    let tmp = copy_of_testdata("diff0");
+    git(tmp.path(), &["init", "-q"]);
+    run()
+        .args(["mutants", "--list", "--since", "no-such-ref", "-d"])
+        .arg(tmp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("git diff \"no-such-ref\" failed"));
+}
+
+#[test]
+fn since_git_ref_starting_with_dash_is_rejected() {
+    let tmp = copy_of_testdata("diff0");
+    git(tmp.path(), &["init", "-q"]);
+    run()
+        .args(["mutants", "--list", "--since=--output=diff.txt", "-d"])
+        .arg(tmp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Git ref for --since must not start with '-'",
+        ));
+    assert!(!tmp.path().join("diff.txt").exists());
+}
@@ -2,10 +2,11 @@
 
 //! Test handling of `--jobs` concurrency option.
 
-use std::fs::read_to_string;
+use std::fs::{read_dir, read_to_string};
 
 use itertools::Itertools;
 use regex::Regex;
+use tempfile::TempDir;
 
 mod util;
 use util::{copy_of_testdata, run};
@@ -59,3 +60,38 @@ fn warn_about_too_many_jobs() {
         ))
         .success();
 }
+
+/// Parallel jobs each build in their own target directory, even if `CARGO_TARGET_DIR`
+/// would make them share one, so the results are the same as a serial run.
+#[test]
+fn parallel_jobs_with_cargo_target_dir_give_consistent_results() {
+    let testdata = copy_of_testdata("small_well_tested");
+    let shared_target = TempDir::new().unwrap();
+    let mut summaries = Vec::new();
+    for jobs in ["-j1", "-j3"] {
+        let output = run()
+            .arg("mutants")
+            .arg("-d")
+            .arg(testdata.path())
+            .arg(jobs)
+            .args(["--no-times", "--minimum-test-timeout=120"])
+            .env("CARGO_TARGET_DIR", shared_target.path())
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        let stdout = String::from_utf8(output).unwrap();
+        let summary = stdout
+            .lines()
+            .find(|line| line.contains("mutants tested"))
+            .expect("summary line")
+            .to_owned();
+        summaries.push(summary);
+    }
+    assert_eq!(summaries[0], summaries[1]);
+    assert!(
+        read_dir(shared_target.path()).unwrap().next().is_none(),
+        "shared CARGO_TARGET_DIR should not be used by copied build dirs"
+    );
+}
@@ -4,13 +4,202 @@
 
 use std::env;
 use std::fmt::Write;
+use std::fs;
 
+use indoc::indoc;
 use predicates::prelude::*;
 use pretty_assertions::assert_eq;
 
 mod util;
 use util::{all_testdata_tree_names, copy_of_testdata, run, CommandInstaExt, OUTER_TIMEOUT};
 
+#[test]
+fn list_genres_shows_descriptions_and_defaults() {
+    let output = run()
+        .args(["mutants", "--list-genres"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let stdout = String::from_utf8(output).unwrap();
+    let lines = stdout.lines().collect::<Vec<_>>();
+    assert_eq!(
+        lines[0],
+        "FnValue\tdefault\treplace function body with a value"
+    );
+    assert!(lines.contains(&"ConstDefault\toptional\treplace const generic default"));
+    assert!(lines.iter().all(|line| line.split('\t').count() == 3));
+
+    let output = run()
+        .args(["mutants", "--list-genres", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let genres = json.as_array().unwrap();
+    assert_eq!(genres.len(), lines.len());
+    assert_eq!(
+        genres[0],
+        serde_json::json!({
+            "genre": "FnValue",
+            "description": "replace function body with a value",
+            "default": true,
+        })
+    );
+}
+
+#[test]
+fn discovery_only_reports_counts_without_building() {
+    let tmp = copy_of_testdata("factorial");
+    run()
+        .args(["mutants", "--discovery-only", "--no-times", "-d"])
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout("Found 5 mutants in 1 source file\n");
+    assert!(!tmp.path().join("mutants.out").exists());
+    assert!(!tmp.path().join("target").exists());
+    run()
+        .args(["mutants", "--discovery-only", "-d"])
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::is_match(r"^Found 5 mutants in 1 source file in \d+\.\d{3}s\n$")
+                .unwrap(),
+        );
+}
+
+#[test]
+fn mutants_from_file_selects_named_mutants() {
+    let tmp = copy_of_testdata("factorial");
+    let names_path = tmp.path().join("names.txt");
+    fs::write(
+        &names_path,
+        indoc! {"
+            src/bin/factorial.rs:8:5: replace factorial -> u32 with 1
+
+            src/bin/factorial.rs:10:11: replace *= with /= in factorial
+            src/bin/factorial.rs:99:1: replace gone with ()
+        "},
+    )
+    .unwrap();
+    run()
+        .args(["mutants", "--list", "--mutants-from-file"])
+        .arg(&names_path)
+        .arg("-d")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(indoc! {"
+            src/bin/factorial.rs:8:5: replace factorial -> u32 with 1
+            src/bin/factorial.rs:10:11: replace *= with /= in factorial
+        "})
+        .stderr(predicate::str::contains(
+            "Mutant \"src/bin/factorial.rs:99:1: replace gone with ()\" from",
+        ));
+
+    // The output of `--list --json` can be edited and passed back in.
+    let json_output = run()
+        .args(["mutants", "--list", "--json", "-d"])
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let mut mutants: Vec<serde_json::Value> = serde_json::from_slice(&json_output).unwrap();
+    mutants.retain(|m| m["genre"] == "BinaryOperator");
+    let json_path = tmp.path().join("mutants.json");
+    fs::write(&json_path, serde_json::to_string(&mutants).unwrap()).unwrap();
+    run()
+        .args(["mutants", "--list", "--mutants-from-file"])
+        .arg(&json_path)
+        .arg("-d")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(indoc! {"
+            src/bin/factorial.rs:10:11: replace *= with += in factorial
+            src/bin/factorial.rs:10:11: replace *= with /= in factorial
+        "});
+}
+
+#[test]
+fn function_selects_mutants_in_exactly_named_functions() {
+    let tmp = copy_of_testdata("well_tested");
+    run()
+        .args([
+            "mutants",
+            "--list",
+            "--function",
+            "Foo::double",
+            "--function",
+            "outer::inner::name",
+            "--function",
+            "inner::name",
+        ])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(indoc! {r#"
+            src/inside_mod.rs:4:13: replace outer::inner::name -> &'static str with ""
+            src/inside_mod.rs:4:13: replace outer::inner::name -> &'static str with "xyzzy"
+            src/methods.rs:17:9: replace Foo::double with ()
+            src/methods.rs:17:16: replace *= with += in Foo::double
+            src/methods.rs:17:16: replace *= with /= in Foo::double
+        "#})
+        .stderr(predicate::str::contains(
+            "No mutants were found in function \"inner::name\"",
+        ));
+}
+
+#[test]
+fn explain_type_prints_rule_and_replacements() {
+    let tmp = copy_of_testdata("factorial");
+    run()
+        .args(["mutants", "--explain-type", "Option<Vec<u8>>", "-d"])
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(indoc! {"
+            Option<Vec<u8>> matches rule: Option
+            None
+            Some(vec![])
+            Some(vec![0])
+            Some(vec![1])
+        "});
+    run()
+        .args(["mutants", "--explain-type", "Vec<", "-d"])
+        .arg(tmp.path())
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("Failed to parse type \"Vec<\""));
+}
+
+#[test]
+fn unsafe_fn_is_listed_only_with_mutate_unsafe() {
+    let tmp = copy_of_testdata("unsafe");
+    run()
+        .args(["mutants", "--list", "-d"])
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout("");
+    run()
+        .args(["mutants", "--list", "--mutate-unsafe", "-d"])
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(indoc! {"
+            src/lib.rs:2:5: replace unsafe_fn -> usize with 0
+            src/lib.rs:2:5: replace unsafe_fn -> usize with 1
+        "});
+}
+
 #[test]
 fn list_diff_json_contains_diffs() {
     let tmp = copy_of_testdata("factorial");
@@ -95,6 +284,121 @@ fn list_mutants_in_factorial_json() {
         .assert_insta("list_mutants_in_factorial_json");
 }
 
+#[test]
+fn list_count_in_factorial() {
+    let tmp = copy_of_testdata("factorial");
+    run()
+        .args(["mutants", "--list", "--count"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(indoc! {"
+            5 mutants in 1 source file
+
+            By file:
+                   5  src/bin/factorial.rs
+
+            By genre:
+                   3  FnValue
+                   2  BinaryOperator
+        "});
+}
+
+#[test]
+fn list_count_in_factorial_json() {
+    let tmp = copy_of_testdata("factorial");
+    let cmd = run()
+        .args(["mutants", "--list", "--count", "--json"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+    let json: serde_json::Value = serde_json::from_slice(&cmd.get_output().stdout).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "total_mutants": 5,
+            "total_files": 1,
+            "files": [{"file": "src/bin/factorial.rs", "count": 5}],
+            "genres": [
+                {"genre": "FnValue", "count": 3},
+                {"genre": "BinaryOperator", "count": 2},
+            ],
+        })
+    );
+}
+
+#[test]
+fn list_count_includes_only_files_with_selected_mutants() {
+    let tmp = copy_of_testdata("well_tested");
+    let cmd = run()
+        .args(["mutants", "--list", "--count", "--json", "-F", "return_arc"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+    let json: serde_json::Value = serde_json::from_slice(&cmd.get_output().stdout).unwrap();
+    assert_eq!(json["total_files"], 1);
+    assert_eq!(json["files"][0]["file"], "src/arc.rs");
+}
+
+#[test]
+fn max_files_limits_mutants_to_that_many_files() {
+    let tmp = copy_of_testdata("well_tested");
+    for max_files in [1, 3] {
+        let cmd = run()
+            .args(["mutants", "--list", "--count", "--json", "--max-files"])
+            .arg(max_files.to_string())
+            .current_dir(tmp.path())
+            .assert()
+            .success();
+        let json: serde_json::Value = serde_json::from_slice(&cmd.get_output().stdout).unwrap();
+        assert_eq!(json["files"].as_array().unwrap().len(), max_files);
+    }
+}
+
+#[test]
+fn list_with_context_shows_source_around_each_mutant() {
+    let tmp = copy_of_testdata("factorial");
+    run()
+        .args([
+            "mutants",
+            "--list",
+            "--context",
+            "2",
+            "-F",
+            "replace \\*= with \\+=",
+        ])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(indoc! {"
+            src/bin/factorial.rs:10:11: replace *= with += in factorial
+              8 |     let mut a = 1;
+              9 |     for i in 2..=n {
+            -10 |         a *= i;
+            +10 |         a += i;
+             11 |     }
+             12 |     a
+        "});
+}
+
+#[test]
+fn context_requires_list() {
+    run()
+        .args(["mutants", "--context", "2"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--list"));
+}
+
+#[test]
+fn count_requires_list() {
+    run()
+        .args(["mutants", "--count"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--list"));
+}
+
 #[test]
 fn list_mutants_in_cfg_attr_mutants_skip() {
     let tmp_src_dir = copy_of_testdata("cfg_attr_mutants_skip");
@@ -243,6 +547,219 @@ fn list_mutants_regex_anchored_matches_full_line() {
         .stdout("src/simple_fns.rs:8:5: replace returns_unit with ()\n");
 }
 
+#[test]
+fn list_mutants_regex_matches_genre_qualified_name() {
+    let tmp = copy_of_testdata("well_tested");
+    run()
+        .arg("mutants")
+        .args([
+            "--list",
+            "--re",
+            "src/simple_fns.rs",
+            "--exclude-re",
+            "^FnValue: ",
+        ])
+        .arg("-d")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(indoc! {"
+            src/simple_fns.rs:8:8: replace += with -= in returns_unit
+            src/simple_fns.rs:8:8: replace += with *= in returns_unit
+            src/simple_fns.rs:18:11: replace == with != in divisible_by_three
+            src/simple_fns.rs:18:7: replace % with / in divisible_by_three
+            src/simple_fns.rs:18:7: replace % with + in divisible_by_three
+        "});
+}
+
+#[test]
+fn list_mutants_as_markdown_table() {
+    let tmp = copy_of_testdata("factorial");
+    run()
+        .arg("mutants")
+        .args(["--list", "--markdown"])
+        .arg("-d")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(indoc! {"
+            | File | Line | Function | Genre | Replacement |
+            | ---- | ---: | -------- | ----- | ----------- |
+            | `src/bin/factorial.rs` | 2 | `main` | FnValue | `()` |
+            | `src/bin/factorial.rs` | 8 | `factorial` | FnValue | `0` |
+            | `src/bin/factorial.rs` | 8 | `factorial` | FnValue | `1` |
+            | `src/bin/factorial.rs` | 10 | `factorial` | BinaryOperator | `+=` |
+            | `src/bin/factorial.rs` | 10 | `factorial` | BinaryOperator | `/=` |
+        "});
+}
+
+#[test]
+fn list_mutants_markdown_conflicts_with_json() {
+    let tmp = copy_of_testdata("factorial");
+    run()
+        .arg("mutants")
+        .args(["--list", "--markdown", "--json"])
+        .arg("-d")
+        .arg(tmp.path())
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+#[cfg(unix)]
+fn list_mutants_with_filter_script() {
+    use std::fs::{set_permissions, write, Permissions};
+    use std::os::unix::fs::PermissionsExt;
+
+    let tmp = copy_of_testdata("factorial");
+    let script = tmp.path().join("keep_binary_operators.sh");
+    write(
+        &script,
+        "#!/bin/sh\nexec grep -q '\"genre\":\"BinaryOperator\"'\n",
+    )
+    .unwrap();
+    set_permissions(&script, Permissions::from_mode(0o755)).unwrap();
+    run()
+        .arg("mutants")
+        .args(["--list", "--filter-script"])
+        .arg(&script)
+        .arg("-d")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(indoc! {"
+            src/bin/factorial.rs:10:11: replace *= with += in factorial
+            src/bin/factorial.rs:10:11: replace *= with /= in factorial
+        "});
+}
+
+#[test]
+#[cfg(unix)]
+fn filter_script_runs_only_for_mutants_in_the_shard() {
+    use std::fs::{read_to_string, set_permissions, write, Permissions};
+    use std::os::unix::fs::PermissionsExt;
+
+    let tmp = copy_of_testdata("well_tested");
+    let log = tmp.path().join("filter.log");
+    let script = tmp.path().join("log_and_keep.sh");
+    write(
+        &script,
+        format!(
+            "#!/bin/sh
+cat >>'{}'
+echo >>'{}'
+",
+            log.display(),
+            log.display()
+        ),
+    )
+    .unwrap();
+    set_permissions(&script, Permissions::from_mode(0o755)).unwrap();
+    let list = |extra_args: &[&str]| {
+        let output = run()
+            .arg("mutants")
+            .args(["--list", "--shard", "0/4"])
+            .args(extra_args)
+            .arg("-d")
+            .arg(tmp.path())
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        String::from_utf8(output).unwrap()
+    };
+    let shard_list = list(&[]);
+    let filtered_list = list(&["--filter-script", script.to_str().unwrap()]);
+    assert_eq!(filtered_list, shard_list);
+    assert_eq!(
+        read_to_string(&log).unwrap().lines().count(),
+        shard_list.lines().count(),
+        "filter script is run once for each mutant in the shard"
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn filter_script_that_cannot_be_run_is_an_error() {
+    let tmp = copy_of_testdata("factorial");
+    run()
+        .arg("mutants")
+        .args(["--list", "--filter-script", "/nonexistent/filter"])
+        .arg("-d")
+        .arg(tmp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Failed to run filter script"));
+}
+
+#[test]
+fn list_mutants_with_exclude_range() {
+    let tmp = copy_of_testdata("factorial");
+    run()
+        .arg("mutants")
+        .args([
+            "--list",
+            "--exclude-range",
+            "src/bin/factorial.rs:7-13",
+            "-d",
+        ])
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(indoc! {"
+            src/bin/factorial.rs:2:5: replace main with ()
+        "});
+}
+
+#[test]
+fn exclude_range_warns_about_unknown_file() {
+    let tmp = copy_of_testdata("factorial");
+    run()
+        .arg("mutants")
+        .args([
+            "--list",
+            "--exclude-range",
+            "src/bin/factorail.rs:7-13",
+            "-d",
+        ])
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("replace main with ()"))
+        .stderr(predicate::str::contains(
+            "Exclude range file is not one of the source files",
+        ));
+}
+
+#[test]
+fn list_mutants_skips_modules_disabled_by_features() {
+    let tmp = copy_of_testdata("feature_gated_mod");
+    run()
+        .arg("mutants")
+        .args(["--list", "-d"])
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(indoc! {"
+            src/lib.rs:9:5: replace sum -> u32 with 0
+            src/lib.rs:9:5: replace sum -> u32 with 1
+            src/fallback.rs:2:5: replace sum -> u32 with 0
+            src/fallback.rs:2:5: replace sum -> u32 with 1
+        "});
+    run()
+        .arg("mutants")
+        .args(["--list", "--features", "simd", "-d"])
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "src/simd.rs:5:5: replace sum -> u32 with 0",
+        ))
+        .stdout(predicate::str::contains("src/fallback.rs").not());
+}
+
 #[test]
 fn list_mutants_regex_filters_json() {
     let tmp = copy_of_testdata("well_tested");
@@ -307,6 +824,37 @@ fn list_files_respects_file_filters() {
         .stdout(predicate::str::contains("lib.rs").not());
 }
 
+#[test]
+fn examine_from_file_lists_only_named_files_and_warns_about_stale_entries() {
+    let tmp = copy_of_testdata("with_child_directories");
+    let manifest = tmp.path().join("mutation-ready.txt");
+    fs::write(
+        &manifest,
+        indoc! {"
+            # Files whose tests are good enough for mutation testing.
+            src/methods.rs
+            ./src/module/utils/nested_function.rs
+
+            src/removed.rs
+        "},
+    )
+    .unwrap();
+    run()
+        .arg("mutants")
+        .arg("--list-files")
+        .arg("--examine-from-file")
+        .arg(&manifest)
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(indoc! {"
+            src/methods.rs
+            src/module/utils/nested_function.rs
+        "})
+        .stderr(predicate::str::contains("File \"src/removed.rs\" from"))
+        .stderr(predicate::str::contains("methods.rs").not());
+}
+
 #[test]
 fn list_files_json_well_tested() {
     let tmp = copy_of_testdata("well_tested");
@@ -54,6 +54,10 @@ fn show_help() {
         .success()
         .stdout(predicates::str::contains(
             "Usage: cargo mutants [OPTIONS] [-- <CARGO_TEST_ARGS>...]",
+        ))
+        .stdout(predicates::str::contains("Exit codes:"))
+        .stdout(predicates::str::contains(
+            "  4    The tests already fail or hang in the unmutated baseline tree",
         ));
 }
 
@@ -208,6 +212,39 @@ fn test_small_well_tested_tree_with_baseline_skip() {
         .exists());
 }
 
+#[test]
+fn total_timeout_leaves_mutants_unfinished() {
+    let tmp_src_dir = copy_of_testdata("small_well_tested");
+    run()
+        .arg("mutants")
+        .args(["--no-times", "--no-shuffle", "--total-timeout=0s"])
+        .arg("-d")
+        .arg(tmp_src_dir.path())
+        .assert()
+        .code(5)
+        .stdout(indoc! {"
+            Found 4 mutants to test
+            ok       Unmutated baseline
+            0 mutants tested: 4 not tested before the total timeout
+        "})
+        .stderr(predicate::str::contains(
+            "Total timeout reached: 4 mutants not tested",
+        ));
+    let out_dir = tmp_src_dir.path().join("mutants.out");
+    assert_eq!(
+        read_to_string(out_dir.join("unfinished.txt")).unwrap(),
+        indoc! {"
+            src/lib.rs:5:5: replace factorial -> u32 with 0
+            src/lib.rs:5:5: replace factorial -> u32 with 1
+            src/lib.rs:7:11: replace *= with += in factorial
+            src/lib.rs:7:11: replace *= with /= in factorial
+        "}
+    );
+    let outcomes: serde_json::Value =
+        serde_json::from_str(&read_to_string(out_dir.join("outcomes.json")).unwrap()).unwrap();
+    assert_eq!(outcomes["unfinished"], 4);
+}
+
 #[test]
 fn cdylib_tree_is_well_tested() {
     let tmp_src_dir = copy_of_testdata("cdylib");
@@ -233,8 +270,244 @@ fn proc_macro_tree_is_well_tested() {
         .assert()
         .success()
         .stdout(predicate::str::contains(
-            "2 mutants tested: 1 caught, 1 unviable",
+            "4 mutants tested: 3 caught, 1 unviable",
+        ));
+}
+
+#[test]
+fn no_default_features_skips_mutants_in_modules_gated_by_default_features() {
+    let tmp_src_dir = copy_of_testdata("default_feature_mod");
+    run()
+        .arg("mutants")
+        .args(["--no-times", "--no-shuffle"])
+        .current_dir(tmp_src_dir.path())
+        .timeout(OUTER_TIMEOUT)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("8 mutants tested: 8 caught"));
+
+    let tmp_src_dir = copy_of_testdata("default_feature_mod");
+    run()
+        .arg("mutants")
+        .args(["--no-times", "--no-shuffle", "--no-default-features"])
+        .current_dir(tmp_src_dir.path())
+        .timeout(OUTER_TIMEOUT)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("4 mutants tested: 4 caught"));
+}
+
+#[test]
+fn failing_pre_mutant_command_marks_mutants_failed() {
+    let tmp_src_dir = copy_of_testdata("small_well_tested");
+    run()
+        .arg("mutants")
+        .args([
+            "--no-times",
+            "--no-shuffle",
+            "--pre-mutant-command",
+            "exit 1",
+        ])
+        .current_dir(tmp_src_dir.path())
+        .timeout(OUTER_TIMEOUT)
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains(
+            "FAILED   src/lib.rs:5:5: replace factorial -> u32 with 0",
+        ))
+        .stdout(predicate::str::contains("4 mutants tested: 4 failed"))
+        .stdout(predicate::str::contains("MISSED").not());
+}
+
+#[test]
+fn retry_reports_mutants_caught_by_flaky_tests_as_missed() {
+    let tmp_src_dir = copy_of_testdata("flaky_when_mutated");
+    run()
+        .arg("mutants")
+        .args(["--no-times", "--no-shuffle"])
+        .current_dir(tmp_src_dir.path())
+        .timeout(OUTER_TIMEOUT)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("4 mutants tested: 4 caught"));
+
+    let tmp_src_dir = copy_of_testdata("flaky_when_mutated");
+    run()
+        .arg("mutants")
+        .args(["--no-times", "--no-shuffle", "--retry=2"])
+        .current_dir(tmp_src_dir.path())
+        .timeout(OUTER_TIMEOUT)
+        .assert()
+        .code(2) // missed mutants
+        .stdout(predicate::str::contains(
+            "MISSED   src/lib.rs:7:5: replace double -> u32 with 0",
+        ))
+        .stdout(predicate::str::contains("4 mutants tested: 4 missed"))
+        .stderr(predicate::str::contains(
+            "src/lib.rs:7:5: replace double -> u32 with 0 was caught on the first run, \
+            but the tests passed on retry 1: the tests may be flaky",
+        ));
+    let outcomes_json: serde_json::Value =
+        read_to_string(tmp_src_dir.path().join("mutants.out/outcomes.json"))
+            .unwrap()
+            .parse()
+            .unwrap();
+    let phases = outcomes_json["outcomes"][1]["phase_results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|result| result["phase"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(phases, ["Build", "Test", "Test"]);
+}
+
+#[test]
+fn proc_macro_tested_by_sibling_crate() {
+    let tmp_src_dir = copy_of_testdata("proc_macro_test_crate");
+    run()
+        .arg("mutants")
+        .args(["--no-times", "--no-shuffle", "-v", "-V", "-d", "macros"])
+        .current_dir(tmp_src_dir.path())
+        .timeout(OUTER_TIMEOUT)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "unviable macros/src/lib.rs:8:5: replace count_items -> TokenStream with TokenStream::new()",
+        ))
+        .stdout(predicate::str::contains(
+            "9 mutants tested: 7 caught, 2 unviable",
+        ));
+}
+
+#[test]
+fn untested_production_assert_is_missed() {
+    let tmp_src_dir = copy_of_testdata("production_assert");
+    run()
+        .arg("mutants")
+        .args(["--no-times", "--no-shuffle"])
+        .current_dir(tmp_src_dir.path())
+        .timeout(OUTER_TIMEOUT)
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains(
+            "MISSED   src/lib.rs:20:9: neutralize debug_assert! in Slot::advance",
+        ))
+        .stdout(predicate::str::contains(
+            "10 mutants tested: 1 missed, 8 caught, 1 unviable",
+        ));
+}
+
+#[test]
+fn untested_if_let_error_branch_is_missed() {
+    let tmp_src_dir = copy_of_testdata("if_let_result");
+    run()
+        .arg("mutants")
+        .args(["--no-times", "--no-shuffle", "--caught"])
+        .args(["--error", "\"x\".parse::<u16>().unwrap_err()"])
+        .current_dir(tmp_src_dir.path())
+        .timeout(OUTER_TIMEOUT)
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains(
+            "caught   src/lib.rs:5:23: replace s.parse::<u16>() with s.parse::<u16>().and_then(|_| Err(\"x\".parse::<u16>().unwrap_err())) in parse_port",
+        ))
+        .stdout(predicate::str::contains(
+            "MISSED   src/lib.rs:5:23: replace s.parse::<u16>() with s.parse::<u16>().or_else(|_| Ok::<_, ()>(Default::default())) in parse_port",
+        ))
+        .stdout(predicate::str::contains(
+            "4 mutants tested: 1 missed, 3 caught",
+        ));
+}
+
+#[test]
+fn quiet_prints_only_missed_mutants_and_summary() {
+    let tmp_src_dir = copy_of_testdata("missing_test");
+    run()
+        .args([
+            "mutants",
+            "--no-times",
+            "--no-shuffle",
+            "--quiet",
+            "--caught",
+        ])
+        .current_dir(tmp_src_dir.path())
+        .timeout(OUTER_TIMEOUT)
+        .assert()
+        .code(2)
+        .stdout(indoc! {"
+            MISSED   src/lib.rs:2:5: replace is_symlink -> bool with true
+            MISSED   src/lib.rs:2:22: replace & with | in is_symlink
+            MISSED   src/lib.rs:2:22: replace & with ^ in is_symlink
+            5 mutants tested: 3 missed, 2 caught
+        "})
+        .stderr("");
+}
+
+#[test]
+fn missed_only_prints_only_missed_mutants() {
+    let tmp_src_dir = copy_of_testdata("missing_test");
+    run()
+        .args(["mutants", "--no-times", "--no-shuffle", "--missed-only"])
+        .current_dir(tmp_src_dir.path())
+        .timeout(OUTER_TIMEOUT)
+        .assert()
+        .code(2)
+        .stdout(indoc! {"
+            Found 5 mutants to test
+            MISSED   src/lib.rs:2:5: replace is_symlink -> bool with true
+            MISSED   src/lib.rs:2:22: replace & with | in is_symlink
+            MISSED   src/lib.rs:2:22: replace & with ^ in is_symlink
+            5 mutants tested: 3 missed, 2 caught
+        "});
+}
+
+#[test]
+fn caught_only_prints_only_caught_mutants_in_format() {
+    let tmp_src_dir = copy_of_testdata("missing_test");
+    run()
+        .args(["mutants", "--no-times", "--no-shuffle", "--caught-only"])
+        .args(["--format", "{outcome} {line}: {replacement}"])
+        .current_dir(tmp_src_dir.path())
+        .timeout(OUTER_TIMEOUT)
+        .assert()
+        .code(2)
+        .stdout(indoc! {"
+            Found 5 mutants to test
+            caught 2: false
+            caught 2: ==
+            5 mutants tested: 3 missed, 2 caught
+        "});
+}
+
+#[test]
+fn caught_only_conflicts_with_missed_only() {
+    run()
+        .args(["mutants", "--caught-only", "--missed-only"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn verbose_prints_commands_and_logs_environment() {
+    let tmp_src_dir = copy_of_testdata("missing_test");
+    run()
+        .args(["mutants", "--no-times", "--no-shuffle", "--verbose"])
+        .args(["--file", "src/lib.rs", "--re", "with true"])
+        .current_dir(tmp_src_dir.path())
+        .timeout(OUTER_TIMEOUT)
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains(
+            "cargo test --verbose --package=cargo-mutants-testdata-missing-test",
         ));
+    let log = read_to_string(
+        tmp_src_dir
+            .path()
+            .join("mutants.out/log/src__lib.rs_line_2_col_5.log"),
+    )
+    .unwrap();
+    assert!(log.contains("*** env INSTA_UPDATE=no\n"), "{log}");
 }
 
 #[test]
@@ -338,6 +611,63 @@ fn integration_test_source_is_not_mutated() {
     check_text_list_output(tmp_src_dir.path(), "integration_test_source_is_not_mutated");
 }
 
+#[test]
+fn examples_are_mutated_only_when_requested() {
+    let tmp_src_dir = copy_of_testdata("integration_tests");
+    run()
+        .args(["mutants", "--list"])
+        .current_dir(tmp_src_dir.path())
+        .assert()
+        .success()
+        .stdout(contains("quadruple").not())
+        .stdout(contains("forty_two").not());
+    run()
+        .args(["mutants", "--list-files", "--examine-examples"])
+        .current_dir(tmp_src_dir.path())
+        .assert()
+        .success()
+        .stdout("src/lib.rs\nexamples/demo.rs\n");
+    run()
+        .args(["mutants", "--list", "--examine-examples"])
+        .current_dir(tmp_src_dir.path())
+        .assert()
+        .success()
+        .stdout(contains(
+            "examples/demo.rs:9:5: replace quadruple -> u32 with 0",
+        ))
+        .stdout(contains("forty_two").not());
+}
+
+/// With `--toolchain`, cargo-mutants builds with that toolchain even if it was run
+/// by another toolchain's cargo.
+#[test]
+fn nightly_only_tree_builds_with_toolchain_option() {
+    let nightly_available = std::process::Command::new("rustup")
+        .args(["run", "nightly", "rustc", "--version"])
+        .output()
+        .is_ok_and(|output| output.status.success());
+    if !nightly_available {
+        eprintln!("nightly toolchain is not installed; skipping");
+        return;
+    }
+    let tmp_src_dir = copy_of_testdata("nightly_only");
+    run()
+        .args([
+            "mutants",
+            "--no-times",
+            "--no-shuffle",
+            "--toolchain",
+            "nightly",
+        ])
+        .arg("-d")
+        .arg(tmp_src_dir.path())
+        .timeout(OUTER_TIMEOUT)
+        .assert()
+        .success()
+        .stdout(contains("3 mutants tested"))
+        .stdout(contains("3 caught"));
+}
+
 #[test]
 fn uncaught_mutant_in_factorial() {
     let tmp_src_dir = copy_of_testdata("factorial");
@@ -377,6 +707,53 @@ fn uncaught_mutant_in_factorial() {
     check_text_list_output(tmp_src_dir.path(), "uncaught_mutant_in_factorial");
 }
 
+#[test]
+fn result_lines_use_format_template() {
+    let tmp_src_dir = copy_of_testdata("factorial");
+    run()
+        .args(["mutants", "--no-shuffle", "-v", "-d"])
+        .arg(tmp_src_dir.path())
+        .args([
+            "--format",
+            "{outcome}|{file}|{line}|{function}|{genre}|{replacement}",
+        ])
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains(indoc! {"
+            MISSED|src/bin/factorial.rs|2|main|FnValue|()
+            caught|src/bin/factorial.rs|8|factorial|FnValue|0
+            caught|src/bin/factorial.rs|8|factorial|FnValue|1
+            caught|src/bin/factorial.rs|10|factorial|BinaryOperator|+=
+            caught|src/bin/factorial.rs|10|factorial|BinaryOperator|/=
+        "}));
+}
+
+#[test]
+fn error_while_testing_exits_with_software_error_code() {
+    let tmp_src_dir = copy_of_testdata("small_well_tested");
+    let scratch_file = tmp_src_dir.path().join("not_a_directory");
+    fs::write(&scratch_file, "").unwrap();
+    run()
+        .args(["mutants", "--no-times", "--scratch-dir"])
+        .arg(&scratch_file)
+        .arg("-d")
+        .arg(tmp_src_dir.path())
+        .assert()
+        .code(70)
+        .stderr(contains("Error: create scratch directory"));
+}
+
+#[test]
+fn unknown_format_placeholder_is_an_error() {
+    run()
+        .args(["mutants", "--list", "--format", "{outcome} {path}"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "unknown placeholder {path} in format",
+        ));
+}
+
 #[test]
 fn factorial_mutants_with_all_logs() {
     // The log contains a lot of build output, which is hard to deal with, but let's check that
@@ -424,7 +801,7 @@ fn factorial_mutants_no_copy_target() {
     let tmp_src_dir = copy_of_testdata("factorial");
     run()
         .arg("mutants")
-        .args(["--no-times"])
+        .args(["--no-times", "--no-shuffle"])
         .arg("-d")
         .arg(tmp_src_dir.path())
         .assert()
@@ -441,7 +818,7 @@ fn small_well_tested_mutants_with_cargo_arg_release() {
     let tmp_src_dir = copy_of_testdata("small_well_tested");
     run()
         .arg("mutants")
-        .args(["--no-times", "--cargo-arg", "--release"])
+        .args(["--no-times", "--no-shuffle", "--cargo-arg", "--release"])
         .arg("-d")
         .arg(tmp_src_dir.path())
         .assert()
@@ -773,7 +1150,17 @@ fn mutants_causing_tests_to_hang_are_stopped_by_manual_timeout() {
             .expect("read outcomes.json")
             .parse()
             .expect("parse outcomes.json");
-    assert_eq!(outcomes_json["timeout"], 1);
+    // Flipping either `true` literal in `should_stop` also makes it spin forever.
+    assert!(
+        timeout_txt.contains("src/lib.rs:26:16: replace true with false in should_stop"),
+        "expected text not found in:\n{timeout_txt}"
+    );
+    // So does deleting either early return, in `should_stop` or in `controlled_loop`.
+    assert!(
+        timeout_txt.contains("src/lib.rs:42:13: delete return i; in controlled_loop"),
+        "expected text not found in:\n{timeout_txt}"
+    );
+    assert_eq!(outcomes_json["timeout"], 5);
 
     let phases_for_const_fn = outcomes_json["outcomes"]
         .as_array()
@@ -955,6 +1342,41 @@ fn cargo_mutants_in_patch_dependency_tree_passes() {
 /// testing, which would cause all later mutants to be incorrectly marked as
 /// caught.
 ///
+#[test]
+fn sarif_report_lists_missed_mutants() {
+    let tmp_src_dir = copy_of_testdata("missing_test");
+    let sarif_path = tmp_src_dir.path().join("mutants.sarif");
+    run()
+        .args(["mutants", "--no-shuffle", "--sarif"])
+        .arg(&sarif_path)
+        .arg("-d")
+        .arg(tmp_src_dir.path())
+        .timeout(OUTER_TIMEOUT)
+        .assert()
+        .code(2);
+    let sarif: serde_json::Value =
+        serde_json::from_str(&read_to_string(&sarif_path).unwrap()).unwrap();
+    assert_eq!(sarif["version"], "2.1.0");
+    let results = sarif["runs"][0]["results"].as_array().unwrap();
+    let messages = results
+        .iter()
+        .map(|result| result["message"]["text"].as_str().unwrap())
+        .collect_vec();
+    assert_eq!(
+        messages,
+        [
+            "Missed mutant: replace is_symlink -> bool with true was not caught by any test",
+            "Missed mutant: replace & with | in is_symlink was not caught by any test",
+            "Missed mutant: replace & with ^ in is_symlink was not caught by any test",
+        ]
+    );
+    for result in results {
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/lib.rs");
+        assert_eq!(location["region"]["startLine"], 2);
+    }
+}
+
 /// This was suggested by `Mutant::unapply` being marked as missed.
 #[test]
 fn mutants_are_unapplied_after_testing_so_later_missed_mutants_are_found() {
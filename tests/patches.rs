@@ -0,0 +1,68 @@
+// Copyright 2024 Martin Pool
+
+//! Test `--emit-patches`
+
+use std::fs::{read_dir, read_to_string};
+use std::process::Command;
+
+use itertools::Itertools;
+
+mod util;
+use util::{copy_of_testdata, run};
+
+#[test]
+fn emit_patches_writes_one_patch_per_mutant() {
+    let tmp = copy_of_testdata("factorial");
+    let patch_dir = tmp.path().join("patches");
+    let listed = String::from_utf8(
+        run()
+            .args(["mutants", "--list", "-d"])
+            .arg(tmp.path())
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone(),
+    )
+    .unwrap();
+    run()
+        .args(["mutants", "--emit-patches"])
+        .arg(&patch_dir)
+        .arg("-d")
+        .arg(tmp.path())
+        .assert()
+        .success();
+    let patch_names = read_dir(&patch_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .sorted()
+        .collect_vec();
+    assert_eq!(patch_names.len(), listed.lines().count());
+    assert_eq!(
+        patch_names[0],
+        "0000_src__bin__factorial.rs_line_2_col_5.patch"
+    );
+    assert!(!tmp.path().join("mutants.out").exists());
+
+    // Each patch applies cleanly to the original tree, and can be reversed.
+    let original = read_to_string(tmp.path().join("src/bin/factorial.rs")).unwrap();
+    for name in &patch_names {
+        let patch = read_to_string(patch_dir.join(name)).unwrap();
+        assert!(patch.starts_with("--- a/src/bin/factorial.rs\n+++ b/src/bin/factorial.rs\n"));
+        for reverse in [false, true] {
+            let mut cmd = Command::new("patch");
+            cmd.args(["-p1", "--quiet", "-i"])
+                .arg(patch_dir.join(name))
+                .current_dir(tmp.path());
+            if reverse {
+                cmd.arg("-R");
+            }
+            let status = cmd.status().expect("run patch");
+            assert!(status.success(), "patch {name} failed to apply");
+        }
+        assert_eq!(
+            read_to_string(tmp.path().join("src/bin/factorial.rs")).unwrap(),
+            original
+        );
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright 2025 Martin Pool
+
+//! Test `--scratch-dir` and `--leak-scratch`.
+
+use std::fs::read_dir;
+
+use tempfile::TempDir;
+
+mod util;
+use util::{copy_of_testdata, run};
+
+#[test]
+fn scratch_dir_is_created_where_specified_and_cleaned_up() {
+    let tmp = copy_of_testdata("small_well_tested");
+    let scratch = TempDir::with_prefix("scratch_dir_is_created_where_specified").unwrap();
+    let scratch_dir = scratch.path().join("scratch");
+    run()
+        .args(["mutants", "--check", "--no-times", "--scratch-dir"])
+        .arg(&scratch_dir)
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+    assert!(scratch_dir.is_dir());
+    assert_eq!(
+        read_dir(&scratch_dir).unwrap().count(),
+        0,
+        "build dirs are deleted after the run"
+    );
+}
+
+#[test]
+fn leak_scratch_keeps_build_dir_in_scratch_dir() {
+    let tmp = copy_of_testdata("small_well_tested");
+    let scratch = TempDir::with_prefix("leak_scratch_keeps_build_dir").unwrap();
+    run()
+        .args([
+            "mutants",
+            "--check",
+            "--no-times",
+            "--leak-scratch",
+            "--scratch-dir",
+        ])
+        .arg(scratch.path())
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+    let build_dirs = read_dir(scratch.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect::<Vec<_>>();
+    assert_eq!(build_dirs.len(), 1, "one build dir for one job");
+    assert!(build_dirs[0].join("Cargo.toml").is_file());
+    assert!(build_dirs[0].join("src/lib.rs").is_file());
+}
+
+#[test]
+fn scratch_dir_conflicts_with_in_place() {
+    run()
+        .args(["mutants", "--in-place", "--scratch-dir", "scratch"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("cannot be used with"));
+}
@@ -1,10 +1,11 @@
 #![cfg(unix)]
 
+use std::fs::read_to_string;
 use std::thread::sleep;
 use std::time::Duration;
 
 mod util;
-use util::{copy_of_testdata, MAIN_BINARY};
+use util::{copy_of_testdata, run, MAIN_BINARY};
 
 /// If the test hangs and the user (in this case the test suite) interrupts it, then
 /// the `cargo test` child should be killed.
@@ -80,12 +81,38 @@ fn interrupt_caught_and_kills_children() {
     println!("stderr:\n{stderr}");
 
     assert!(stderr.contains("interrupted"));
+    assert_eq!(output.status.code(), Some(130));
     // We used to look here for some other trace messages about how it's interrupted, but
     // that seems to be racy: sometimes the parent sees the child interrupted before it
     // emits these messages? Anyhow, it's not essential.
 
-    // This shouldn't cause a panic though (#333)
-    assert!(!stderr.contains("panic"));
+    // This shouldn't cause a panic though (#333). (The traces of the config and options
+    // name panic-related options, so skip them.)
+    assert!(!stderr
+        .lines()
+        .filter(|line| !line.contains(" config=Config {") && !line.contains(" options=Options {"))
+        .any(|line| line.contains("panic")));
     // And we don't want duplicate messages about workers failing.
     assert!(!stderr.contains("Worker thread failed"));
 }
+
+#[test]
+fn pre_and_post_mutant_commands_run_around_each_mutant() {
+    let tmp_src_dir = copy_of_testdata("small_well_tested");
+    let hook_log = tmp_src_dir.path().join("hooks.log");
+    run()
+        .arg("mutants")
+        .args([
+            "--no-shuffle",
+            "--pre-mutant-command",
+            "echo pre >> \"$HOOK_LOG\"",
+            "--post-mutant-command",
+            "echo post >> \"$HOOK_LOG\"",
+        ])
+        .env("HOOK_LOG", &hook_log)
+        .current_dir(tmp_src_dir.path())
+        .assert()
+        .success();
+    // Run for each of the four mutants, but not for the baseline.
+    assert_eq!(read_to_string(hook_log).unwrap(), "pre\npost\n".repeat(4));
+}
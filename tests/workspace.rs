@@ -2,6 +2,7 @@
 
 //! Tests for cargo workspaces with multiple packages.
 
+use std::collections::BTreeSet;
 use std::fs::{self, create_dir, read_to_string, write};
 
 use insta::assert_snapshot;
@@ -27,13 +28,14 @@ fn open_by_manifest_path() {
 }
 
 #[test]
-fn list_warns_about_unmatched_packages() {
+fn list_fails_on_unmatched_packages() {
+    let tmp = copy_of_testdata("workspace");
     run()
+        .args(["mutants", "--list", "-d"])
+        .arg(tmp.path())
         .args([
-            "mutants",
-            "--list",
-            "-d",
-            "testdata/workspace",
+            "-p",
+            "cargo_mutants_testdata_workspace_utils",
             "-p",
             "notapackage",
         ])
@@ -41,7 +43,8 @@ fn list_warns_about_unmatched_packages() {
         .stderr(predicates::str::contains(
             "Package \"notapackage\" not found in source tree",
         ))
-        .code(0);
+        .stdout(predicates::str::is_empty())
+        .failure();
 }
 
 #[test]
@@ -103,6 +106,41 @@ fn list_files_as_json_in_workspace_subdir() {
     );
 }
 
+/// The package containing a file in the `workspace` testdata tree.
+fn workspace_package_for_file(file: &str) -> &'static str {
+    match file.split_once('/').unwrap().0 {
+        "main" => "main",
+        "main2" => "main2",
+        "utils" => "cargo_mutants_testdata_workspace_utils",
+        dir => panic!("unexpected directory {dir:?}"),
+    }
+}
+
+/// Mutants from every member of a virtual workspace are listed, each attributed to the
+/// package that contains it.
+#[test]
+fn list_mutants_in_virtual_workspace_attributes_packages() {
+    let tmp = copy_of_testdata("workspace");
+    let cmd = run()
+        .args(["mutants", "--list", "--json"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+    let json: serde_json::Value = serde_json::from_slice(&cmd.get_output().stdout).unwrap();
+    let mutants = json.as_array().unwrap();
+    let mut packages = BTreeSet::new();
+    for mutant in mutants {
+        let file = mutant["file"].as_str().unwrap();
+        let package = mutant["package"].as_str().unwrap();
+        assert_eq!(package, workspace_package_for_file(file), "{file}");
+        packages.insert(package);
+    }
+    assert_eq!(
+        packages.into_iter().collect_vec(),
+        ["cargo_mutants_testdata_workspace_utils", "main", "main2"]
+    );
+}
+
 #[test]
 fn workspace_tree_is_well_tested() {
     let tmp_src_dir = copy_of_testdata("workspace");
@@ -167,10 +205,15 @@ fn workspace_tree_is_well_tested() {
     }
 
     assert!(outcomes.len() > 9);
+    let mut tested_packages = BTreeSet::new();
     for outcome in &outcomes[1..] {
         let mutant = &outcome["scenario"]["Mutant"];
         let package_name = mutant["package"].as_str().unwrap();
-        assert!(!package_name.is_empty());
+        // Each mutant is attributed to the member crate containing its file, even
+        // though this is a virtual workspace with no root package.
+        let file = mutant["file"].as_str().unwrap();
+        assert_eq!(package_name, workspace_package_for_file(file), "{file}");
+        tested_packages.insert(package_name);
         assert_eq!(outcome["summary"], "CaughtMutant");
         let mutant_phases = outcome["phase_results"].as_array().unwrap();
         assert_eq!(mutant_phases.len(), 2);
@@ -181,10 +224,19 @@ fn workspace_tree_is_well_tested() {
         );
         assert_eq!(mutant_phases[1]["process_status"], json!({"Failure": 101}));
         assert_eq!(
-            mutant_phases[1]["argv"].as_array().unwrap()[1..=2],
-            ["test", "--verbose"],
+            mutant_phases[1]["argv"].as_array().unwrap()[1..],
+            [
+                "test",
+                "--verbose",
+                &format!("--package={package_name}@0.1.0")
+            ],
+            "only the mutated package's tests are run"
         );
     }
+    assert_eq!(
+        tested_packages.into_iter().collect_vec(),
+        ["cargo_mutants_testdata_workspace_utils", "main", "main2"]
+    );
     {
         let baseline = json["outcomes"][0].as_object().unwrap();
         assert_eq!(baseline["scenario"].as_str().unwrap(), "Baseline");